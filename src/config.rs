@@ -0,0 +1,203 @@
+use std::path::{Path, PathBuf};
+
+/// Repo-local defaults file, looked up in the analyzed directory.
+pub const REPO_CONFIG_FILE: &str = ".vibereport.toml";
+
+/// Default values for CLI flags, read from config files.
+///
+/// ```toml
+/// since = "1y"
+/// no_share = true
+/// width = 60
+/// color = false
+/// roasts = "roasts.toml"  # relative to this file
+/// ai_curve = "quadratic"
+///
+/// [weights]               # see score::calculator::ScoreWeights
+/// hardcoded_secrets = 40
+/// no_readme = 0
+/// ```
+///
+/// Precedence: CLI flags > `.vibereport.toml` in the repo > the user config
+/// (`~/.config/vibereport/config.toml`) > built-in defaults, except that a
+/// repo can only turn sharing off: `no_share = false` there never overrides
+/// the user's `no_share = true`. Unknown keys are ignored so older binaries
+/// can read newer configs.
+#[derive(Debug, Default, Clone, PartialEq, serde::Deserialize)]
+pub struct Defaults {
+    pub since: Option<String>,
+    pub no_share: Option<bool>,
+    pub width: Option<usize>,
+    pub color: Option<bool>,
+    /// Custom roasts file (`--roasts`).
+    pub roasts: Option<PathBuf>,
+    /// AI ratio points curve (`--ai-curve`).
+    pub ai_curve: Option<String>,
+    /// Score weight overrides, merged key by key across config files.
+    pub weights: Option<toml::Table>,
+}
+
+impl Defaults {
+    /// Merge the user config and the repo-local config in `repo_dir`.
+    /// Unreadable or malformed files are reported and skipped.
+    pub fn load(repo_dir: Option<&Path>) -> Self {
+        let user = user_config_path().and_then(|p| read_file(&p));
+        let repo = repo_dir.and_then(|dir| read_file(&dir.join(REPO_CONFIG_FILE)));
+        user.unwrap_or_default().overlay(repo.unwrap_or_default())
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| e.to_string())
+    }
+
+    /// Values set in `other` (the repo config) win over ours, except that
+    /// `no_share` only tightens: a cloned repo can't opt the user back in.
+    fn overlay(self, other: Self) -> Self {
+        Self {
+            since: other.since.or(self.since),
+            no_share: match (self.no_share, other.no_share) {
+                (Some(true), _) => Some(true),
+                (ours, theirs) => theirs.or(ours),
+            },
+            width: other.width.or(self.width),
+            color: other.color.or(self.color),
+            roasts: other.roasts.or(self.roasts),
+            ai_curve: other.ai_curve.or(self.ai_curve),
+            weights: match (self.weights, other.weights) {
+                (Some(mut ours), Some(theirs)) => {
+                    ours.extend(theirs);
+                    Some(ours)
+                }
+                (ours, theirs) => theirs.or(ours),
+            },
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/vibereport/config.toml`, falling back to
+/// `~/.config/vibereport/config.toml` (or `%APPDATA%\vibereport` on Windows).
+fn user_config_path() -> Option<PathBuf> {
+    let non_empty = |key: &str| std::env::var_os(key).filter(|v| !v.is_empty());
+    non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| non_empty("APPDATA").map(PathBuf::from))
+        .map(|dir| dir.join("vibereport").join("config.toml"))
+}
+
+fn read_file(path: &Path) -> Option<Defaults> {
+    if !path.is_file() {
+        return None;
+    }
+    let parsed = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| Defaults::parse(&content));
+    match parsed {
+        // Paths in a config file are relative to that file
+        Ok(mut defaults) => {
+            let dir = path.parent().unwrap_or(Path::new("."));
+            defaults.roasts = defaults.roasts.map(|r| dir.join(r));
+            Some(defaults)
+        }
+        Err(e) => {
+            eprintln!("Warning: ignoring {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//  Tests
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_keys() {
+        let d = Defaults::parse("since = \"1y\"\nno_share = true\nwidth = 60\ncolor = false\n")
+            .unwrap();
+        assert_eq!(
+            d,
+            Defaults {
+                since: Some("1y".into()),
+                no_share: Some(true),
+                width: Some(60),
+                color: Some(false),
+                roasts: None,
+                ai_curve: None,
+                weights: None,
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_keys() {
+        let d = Defaults::parse("since = \"6m\"\ntheme = \"dark\"\n[extra]\nx = 1\n").unwrap();
+        assert_eq!(d.since.as_deref(), Some("6m"));
+        assert_eq!(d.width, None);
+    }
+
+    #[test]
+    fn reads_ai_curve() {
+        let d = Defaults::parse("ai_curve = \"logistic\"\n").unwrap();
+        assert_eq!(d.ai_curve.as_deref(), Some("logistic"));
+    }
+
+    #[test]
+    fn rejects_wrong_types() {
+        assert!(Defaults::parse("width = \"wide\"").is_err());
+    }
+
+    #[test]
+    fn repo_config_overrides_user_config() {
+        let user = Defaults::parse("since = \"2y\"\nwidth = 80\n").unwrap();
+        let repo = Defaults::parse("since = \"6m\"\n").unwrap();
+        let merged = user.overlay(repo);
+        assert_eq!(merged.since.as_deref(), Some("6m"));
+        assert_eq!(merged.width, Some(80));
+    }
+
+    #[test]
+    fn repo_config_cannot_turn_sharing_back_on() {
+        let opted_out = || Defaults::parse("no_share = true\n").unwrap();
+        let repo = Defaults::parse("no_share = false\n").unwrap();
+        assert_eq!(opted_out().overlay(repo).no_share, Some(true));
+
+        let repo = Defaults::parse("no_share = true\n").unwrap();
+        assert_eq!(Defaults::default().overlay(repo).no_share, Some(true));
+        assert_eq!(
+            opted_out().overlay(Defaults::default()).no_share,
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn weights_merge_key_by_key() {
+        let user = Defaults::parse("[weights]\nno_readme = 0\nno_tests = 30\n").unwrap();
+        let repo = Defaults::parse("[weights]\nno_tests = 40\n").unwrap();
+        let weights = user.overlay(repo).weights.unwrap();
+        assert_eq!(weights["no_readme"].as_integer(), Some(0));
+        assert_eq!(weights["no_tests"].as_integer(), Some(40));
+    }
+
+    #[test]
+    fn loads_repo_local_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(REPO_CONFIG_FILE), "no_share = true\n").unwrap();
+        assert_eq!(Defaults::load(Some(dir.path())).no_share, Some(true));
+    }
+
+    #[test]
+    fn roasts_path_is_relative_to_config_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join(REPO_CONFIG_FILE),
+            "roasts = \"team.toml\"\n",
+        )
+        .unwrap();
+        let defaults = Defaults::load(Some(dir.path()));
+        assert_eq!(defaults.roasts, Some(dir.path().join("team.toml")));
+    }
+}
@@ -1,5 +1,8 @@
 /// Detects if a commit was AI-authored based on commit message patterns.
-/// Supports: Claude Code, Cursor, Aider, Codex CLI, GitHub Copilot, Gemini CLI.
+/// Supports: Claude Code, Cursor, Aider, Codex CLI, GitHub Copilot, Gemini CLI,
+/// Windsurf, Sourcegraph Cody, Tabnine — plus user-defined rules from `vibereport.toml`.
+use std::collections::BTreeMap;
+use std::path::Path;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AiTool {
@@ -9,7 +12,9 @@ pub enum AiTool {
     CodexCli,
     GithubCopilot,
     GeminiCli,
-    #[allow(dead_code)]
+    Windsurf,
+    Cody,
+    Tabnine,
     Other(String),
     Human,
 }
@@ -23,66 +28,331 @@ impl std::fmt::Display for AiTool {
             AiTool::CodexCli => write!(f, "Codex CLI"),
             AiTool::GithubCopilot => write!(f, "GitHub Copilot"),
             AiTool::GeminiCli => write!(f, "Gemini CLI"),
+            AiTool::Windsurf => write!(f, "Windsurf"),
+            AiTool::Cody => write!(f, "Cody"),
+            AiTool::Tabnine => write!(f, "Tabnine"),
             AiTool::Other(name) => write!(f, "{}", name),
             AiTool::Human => write!(f, "Human"),
         }
     }
 }
 
-/// Analyze a commit message + author email and return which AI tool authored it (if any).
-pub fn detect_ai_tool(commit_message: &str, author_email: &str) -> AiTool {
-    let msg = commit_message.to_lowercase();
-    let email = author_email.to_lowercase();
+/// Config file looked up at the repo root when `--config` isn't given.
+pub const CONFIG_FILE: &str = "vibereport.toml";
+
+/// User-defined detection rules, checked before the built-in patterns.
+///
+/// ```toml
+/// [tools."Acme AI"]
+/// contains = ["[AI]"]             # case-insensitive substrings
+/// regex = ["(?m)^ai-assisted:"]   # matched against the raw message
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CustomRules {
+    tools: Vec<CustomTool>,
+}
+
+#[derive(Debug, Clone)]
+struct CustomTool {
+    tool: AiTool,
+    contains: Vec<String>,
+    regex: Vec<regex::Regex>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    tools: BTreeMap<String, ToolConfig>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolConfig {
+    #[serde(default)]
+    contains: Vec<String>,
+    #[serde(default)]
+    regex: Vec<String>,
+}
 
-    // Claude Code patterns (message OR author email)
-    if msg.contains("co-authored-by: claude")
-        || msg.contains("noreply@anthropic.com")
-        || msg.contains("generated with claude code")
-        || msg.contains("claude.ai/code/")
-        || msg.contains("claude.ai/chat/")
-        || email == "noreply@anthropic.com"
-    {
-        return AiTool::ClaudeCode;
+impl CustomRules {
+    /// Load rules from `explicit` if given, else from `<repo_root>/vibereport.toml`
+    /// if it exists. No config means no custom rules.
+    pub fn discover(
+        repo_root: &Path,
+        explicit: Option<&Path>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = match explicit {
+            Some(p) => p.to_path_buf(),
+            None => {
+                let p = repo_root.join(CONFIG_FILE);
+                if !p.is_file() {
+                    return Ok(Self::default());
+                }
+                p
+            }
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e).into())
     }
 
-    // Cursor patterns
-    if msg.contains("co-authored-by: cursor") {
-        return AiTool::Cursor;
+    /// Parse and validate a `vibereport.toml`. Malformed regexes and empty
+    /// patterns are rejected rather than silently ignored.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let config: ConfigFile = toml::from_str(content).map_err(|e| e.to_string())?;
+
+        let mut tools = Vec::new();
+        for (name, rule) in config.tools {
+            if rule.contains.is_empty() && rule.regex.is_empty() {
+                return Err(format!(
+                    "tool \"{}\" has no `contains` or `regex` patterns",
+                    name
+                ));
+            }
+            if rule.contains.iter().any(|c| c.is_empty()) {
+                return Err(format!("tool \"{}\" has an empty `contains` pattern", name));
+            }
+            let regex = rule
+                .regex
+                .iter()
+                .map(|r| {
+                    regex::Regex::new(r).map_err(|e| {
+                        format!("tool \"{}\" has an invalid regex {:?}: {}", name, r, e)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            tools.push(CustomTool {
+                tool: tool_for_name(&name),
+                contains: rule.contains.iter().map(|c| c.to_lowercase()).collect(),
+                regex,
+            });
+        }
+        Ok(Self { tools })
     }
 
-    // Aider patterns (message OR author email)
-    if msg.contains("co-authored-by: aider")
-        || msg.contains("noreply@aider.chat")
-        || msg.contains("aider: ")
-        || email == "noreply@aider.chat"
-    {
-        return AiTool::Aider;
+    fn classify(&self, commit_message: &str) -> Option<Classification> {
+        let msg = commit_message.to_lowercase();
+        self.tools.iter().find_map(|t| {
+            let reason = if let Some(c) = t.contains.iter().find(|c| msg.contains(c.as_str())) {
+                format!("custom rule \"{}\": message contains {:?}", t.tool, c)
+            } else {
+                let r = t.regex.iter().find(|r| r.is_match(commit_message))?;
+                format!("custom rule \"{}\": message matches /{}/", t.tool, r)
+            };
+            Some(Classification {
+                tool: t.tool.clone(),
+                reason: Some(reason),
+            })
+        })
     }
+}
+
+/// Resolve a configured tool name to a built-in variant when it names one
+/// (so "Claude Code" rules merge with built-in counts), else `Other`.
+fn tool_for_name(name: &str) -> AiTool {
+    [
+        AiTool::ClaudeCode,
+        AiTool::Cursor,
+        AiTool::Aider,
+        AiTool::CodexCli,
+        AiTool::GithubCopilot,
+        AiTool::GeminiCli,
+        AiTool::Windsurf,
+        AiTool::Cody,
+        AiTool::Tabnine,
+    ]
+    .into_iter()
+    .find(|t| t.to_string().eq_ignore_ascii_case(name))
+    .unwrap_or_else(|| AiTool::Other(name.to_string()))
+}
 
-    // Codex CLI patterns
-    if msg.contains("co-authored-by: codex")
-        || msg.contains("generated by codex")
-        || msg.contains("codex-cli")
-    {
-        return AiTool::CodexCli;
+/// Like [`detect_ai_tool`], but user-defined rules win over the built-ins.
+pub fn detect_ai_tool_with(
+    commit_message: &str,
+    author_email: &str,
+    rules: &CustomRules,
+) -> AiTool {
+    match rules.classify(commit_message) {
+        Some(custom) => custom.tool,
+        None => detect_ai_tool(commit_message, author_email),
     }
+}
+
+/// How a commit was classified, and which pattern decided it
+/// (`vibereport classify`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    pub tool: AiTool,
+    /// The rule that matched; `None` for `Human`.
+    pub reason: Option<String>,
+}
+
+/// [`detect_ai_tool_with`], explained: the tool plus the rule that matched.
+pub fn classify(commit_message: &str, author_email: &str, rules: &CustomRules) -> Classification {
+    rules
+        .classify(commit_message)
+        .unwrap_or_else(|| classify_builtin(commit_message, author_email))
+}
 
-    // GitHub Copilot patterns
-    if msg.contains("co-authored-by: copilot")
-        || (msg.contains("noreply@github.com") && msg.contains("copilot"))
-        || msg.contains("github-copilot")
-    {
-        return AiTool::GithubCopilot;
+/// Map a known AI co-author / author email to its tool.
+fn tool_for_email(email: &str) -> Option<AiTool> {
+    match email {
+        "noreply@anthropic.com" => Some(AiTool::ClaudeCode),
+        "cursoragent@cursor.com" => Some(AiTool::Cursor),
+        "noreply@aider.chat" => Some(AiTool::Aider),
+        "copilot@github.com" => Some(AiTool::GithubCopilot),
+        e if e.ends_with("+copilot@users.noreply.github.com") => Some(AiTool::GithubCopilot),
+        "noreply@windsurf.com" | "noreply@codeium.com" => Some(AiTool::Windsurf),
+        "cody@sourcegraph.com" => Some(AiTool::Cody),
+        "noreply@tabnine.com" => Some(AiTool::Tabnine),
+        _ => None,
     }
+}
+
+/// Extract the emails from `Co-authored-by: Name <email>` trailers anywhere
+/// in a (lowercased) multi-line commit message.
+fn co_author_emails(msg: &str) -> impl Iterator<Item = &str> {
+    msg.lines().filter_map(|line| {
+        let rest = line.trim().strip_prefix("co-authored-by:")?;
+        let start = rest.find('<')? + 1;
+        let end = start + rest[start..].find('>')?;
+        Some(rest[start..end].trim())
+    })
+}
+
+/// One built-in detection rule. Message patterns are lowercase substrings.
+enum Rule {
+    /// The message contains this pattern.
+    Contains(&'static str),
+    /// The message contains both patterns.
+    ContainsBoth(&'static str, &'static str),
+    /// The commit author's email is exactly this.
+    AuthorEmail(&'static str),
+}
+
+/// Built-in rules, first match wins. A tool's message patterns and author
+/// email are checked together, before the next tool's.
+const RULES: &[(AiTool, Rule)] = &[
+    // Claude Code
+    (AiTool::ClaudeCode, Rule::Contains("co-authored-by: claude")),
+    (AiTool::ClaudeCode, Rule::Contains("noreply@anthropic.com")),
+    (
+        AiTool::ClaudeCode,
+        Rule::Contains("generated with claude code"),
+    ),
+    (AiTool::ClaudeCode, Rule::Contains("claude.ai/code/")),
+    (AiTool::ClaudeCode, Rule::Contains("claude.ai/chat/")),
+    (
+        AiTool::ClaudeCode,
+        Rule::AuthorEmail("noreply@anthropic.com"),
+    ),
+    // Cursor
+    (AiTool::Cursor, Rule::Contains("co-authored-by: cursor")),
+    // Aider
+    (AiTool::Aider, Rule::Contains("co-authored-by: aider")),
+    (AiTool::Aider, Rule::Contains("noreply@aider.chat")),
+    (AiTool::Aider, Rule::Contains("aider: ")),
+    (AiTool::Aider, Rule::AuthorEmail("noreply@aider.chat")),
+    // Codex CLI
+    (AiTool::CodexCli, Rule::Contains("co-authored-by: codex")),
+    (AiTool::CodexCli, Rule::Contains("generated by codex")),
+    (AiTool::CodexCli, Rule::Contains("codex-cli")),
+    // GitHub Copilot
+    (
+        AiTool::GithubCopilot,
+        Rule::Contains("co-authored-by: copilot"),
+    ),
+    (
+        AiTool::GithubCopilot,
+        Rule::ContainsBoth("noreply@github.com", "copilot"),
+    ),
+    (AiTool::GithubCopilot, Rule::Contains("github-copilot")),
+    // Gemini CLI
+    (AiTool::GeminiCli, Rule::Contains("co-authored-by: gemini")),
+    (
+        AiTool::GeminiCli,
+        Rule::ContainsBoth("noreply@google.com", "gemini"),
+    ),
+    // Windsurf (formerly Codeium)
+    (AiTool::Windsurf, Rule::Contains("co-authored-by: windsurf")),
+    (AiTool::Windsurf, Rule::Contains("generated with windsurf")),
+    (AiTool::Windsurf, Rule::Contains("co-authored-by: codeium")),
+    // Sourcegraph Cody — a bare "Co-authored-by: Cody" could be a human
+    // named Cody, so require the Sourcegraph address alongside it
+    (
+        AiTool::Cody,
+        Rule::ContainsBoth("co-authored-by: cody", "sourcegraph"),
+    ),
+    (AiTool::Cody, Rule::Contains("generated with cody")),
+    (AiTool::Cody, Rule::Contains("generated by cody")),
+    // Tabnine
+    (AiTool::Tabnine, Rule::Contains("co-authored-by: tabnine")),
+    (AiTool::Tabnine, Rule::Contains("generated with tabnine")),
+    (AiTool::Tabnine, Rule::Contains("generated by tabnine")),
+];
+
+/// Analyze a commit message + author email and return which AI tool authored it (if any).
+/// `commit_message` must be the full message — trailers live in the body, not the subject.
+pub fn detect_ai_tool(commit_message: &str, author_email: &str) -> AiTool {
+    classify_builtin(commit_message, author_email).tool
+}
 
-    // Gemini CLI patterns
-    if msg.contains("co-authored-by: gemini")
-        || msg.contains("noreply@google.com") && msg.contains("gemini")
-    {
-        return AiTool::GeminiCli;
+fn classify_builtin(commit_message: &str, author_email: &str) -> Classification {
+    let msg = commit_message.to_lowercase();
+    let email = author_email.to_lowercase();
+
+    for (tool, rule) in RULES {
+        let reason = match *rule {
+            Rule::Contains(p) if msg.contains(p) => format!("message contains {:?}", p),
+            Rule::ContainsBoth(a, b) if msg.contains(a) && msg.contains(b) => {
+                format!("message contains {:?} and {:?}", a, b)
+            }
+            Rule::AuthorEmail(e) if email == e => format!("author email is {}", e),
+            _ => continue,
+        };
+        return Classification {
+            tool: tool.clone(),
+            reason: Some(reason),
+        };
     }
 
-    AiTool::Human
+    // Known AI emails in Co-authored-by trailers or as the commit author
+    // (e.g. Copilot coding agent: `123+Copilot@users.noreply.github.com`)
+    let co_authors = co_author_emails(&msg).map(|address| ("co-author", address));
+    for (role, address) in co_authors.chain(std::iter::once(("author", email.as_str()))) {
+        if let Some(tool) = tool_for_email(address) {
+            return Classification {
+                tool,
+                reason: Some(format!("{} email {} is a known AI address", role, address)),
+            };
+        }
+    }
+
+    Classification {
+        tool: AiTool::Human,
+        reason: None,
+    }
+}
+
+/// Automation accounts that don't carry the `[bot]` suffix.
+const BOT_NAMES: &[&str] = &[
+    "dependabot",
+    "dependabot-preview",
+    "renovate-bot",
+    "greenkeeper",
+    "snyk-bot",
+    "github-actions",
+];
+
+/// Check whether a commit author is an automation bot (Dependabot, Renovate,
+/// GitHub Actions, ...). Matches the `[bot]` suffix GitHub gives app accounts
+/// (`dependabot[bot]`, `49699333+dependabot[bot]@users.noreply.github.com`)
+/// plus a few well-known bot names.
+pub fn is_bot(author_name: &str, author_email: &str) -> bool {
+    let name = author_name.trim().to_lowercase();
+    let email = author_email.trim().to_lowercase();
+    name.ends_with("[bot]") || email.contains("[bot]@") || BOT_NAMES.contains(&name.as_str())
 }
 
 #[cfg(test)]
@@ -193,6 +463,240 @@ mod tests {
         assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::GeminiCli);
     }
 
+    // ── Trailers in the commit body ──
+
+    #[test]
+    fn detects_trailer_below_plain_subject() {
+        let msg = "Update README\n\nReword the install section.\n\nCo-authored-by: Claude <noreply@anthropic.com>\n";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::ClaudeCode);
+    }
+
+    #[test]
+    fn detects_copilot_agent_email_trailer() {
+        let msg = "Fix flaky test\n\nSigned-off-by: Jane <jane@example.com>\nCo-authored-by: Copilot Agent <198982749+Copilot@users.noreply.github.com>";
+        assert_eq!(
+            detect_ai_tool(msg, "jane@example.com"),
+            AiTool::GithubCopilot
+        );
+    }
+
+    #[test]
+    fn detects_copilot_author_email() {
+        assert_eq!(
+            detect_ai_tool("Fix flaky test", "copilot@github.com"),
+            AiTool::GithubCopilot
+        );
+    }
+
+    #[test]
+    fn human_co_author_trailer_is_human() {
+        let msg = "Pair on parser\n\nCo-authored-by: Jane Doe <jane@example.com>";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Human);
+    }
+
+    #[test]
+    fn subject_alone_misses_body_trailer() {
+        // Regression guard: detection must get the full message, not just line one
+        let msg = "Update README\n\nCo-authored-by: Cursor Agent <cursoragent@cursor.com>";
+        let subject = msg.lines().next().unwrap();
+        assert_eq!(detect_ai_tool(subject, "user@example.com"), AiTool::Human);
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Cursor);
+    }
+
+    // ── Custom rules (vibereport.toml) ──
+
+    const ACME: &str = r#"
+[tools."Acme AI"]
+contains = ["[AI]"]
+regex = ["(?m)^ai-assisted:"]
+"#;
+
+    #[test]
+    fn custom_substring_rule() {
+        let rules = CustomRules::parse(ACME).unwrap();
+        assert_eq!(
+            detect_ai_tool_with("[ai] refactor parser", "user@example.com", &rules),
+            AiTool::Other("Acme AI".into())
+        );
+    }
+
+    #[test]
+    fn custom_regex_rule() {
+        let rules = CustomRules::parse(ACME).unwrap();
+        let msg = "Refactor parser\n\nai-assisted: yes";
+        assert_eq!(
+            detect_ai_tool_with(msg, "user@example.com", &rules),
+            AiTool::Other("Acme AI".into())
+        );
+    }
+
+    #[test]
+    fn custom_rules_checked_before_builtins() {
+        let rules = CustomRules::parse(ACME).unwrap();
+        let msg = "[AI] fix\n\nCo-Authored-By: Claude <noreply@anthropic.com>";
+        assert_eq!(
+            detect_ai_tool_with(msg, "user@example.com", &rules),
+            AiTool::Other("Acme AI".into())
+        );
+    }
+
+    #[test]
+    fn custom_rules_fall_back_to_builtins() {
+        let rules = CustomRules::parse(ACME).unwrap();
+        let msg = "fix\n\nCo-Authored-By: Claude <noreply@anthropic.com>";
+        assert_eq!(
+            detect_ai_tool_with(msg, "user@example.com", &rules),
+            AiTool::ClaudeCode
+        );
+        assert_eq!(
+            detect_ai_tool_with("fix typo", "user@example.com", &CustomRules::default()),
+            AiTool::Human
+        );
+    }
+
+    #[test]
+    fn classify_names_the_matching_rule() {
+        let rules = CustomRules::parse(ACME).unwrap();
+        let reason = |msg: &str, email: &str| classify(msg, email, &rules).reason;
+
+        assert_eq!(
+            reason("[ai] refactor", "user@example.com").as_deref(),
+            Some("custom rule \"Acme AI\": message contains \"[ai]\"")
+        );
+        assert_eq!(
+            reason("fix\nai-assisted: yes", "user@example.com").as_deref(),
+            Some("custom rule \"Acme AI\": message matches /(?m)^ai-assisted:/")
+        );
+        assert_eq!(
+            reason("feat: x\n\nGenerated with Claude Code", "user@example.com").as_deref(),
+            Some("message contains \"generated with claude code\"")
+        );
+        assert_eq!(
+            reason("fix", "noreply@aider.chat").as_deref(),
+            Some("author email is noreply@aider.chat")
+        );
+        assert_eq!(
+            reason(
+                "fix\n\nCo-authored-by: x <noreply@tabnine.com>",
+                "user@example.com"
+            )
+            .as_deref(),
+            Some("co-author email noreply@tabnine.com is a known AI address")
+        );
+
+        let human = classify("fix typo", "user@example.com", &rules);
+        assert_eq!(human.tool, AiTool::Human);
+        assert_eq!(human.reason, None);
+    }
+
+    #[test]
+    fn custom_rule_named_after_builtin_maps_to_it() {
+        let rules = CustomRules::parse("[tools.\"claude code\"]\ncontains = [\"cc:\"]").unwrap();
+        assert_eq!(
+            detect_ai_tool_with("cc: add tests", "user@example.com", &rules),
+            AiTool::ClaudeCode
+        );
+    }
+
+    #[test]
+    fn custom_rules_reject_malformed_regex() {
+        let err = CustomRules::parse("[tools.Acme]\nregex = [\"(unclosed\"]").unwrap_err();
+        assert!(err.contains("invalid regex"), "{}", err);
+        assert!(err.contains("Acme"), "{}", err);
+    }
+
+    #[test]
+    fn custom_rules_reject_empty_tool() {
+        assert!(CustomRules::parse("[tools.Acme]").is_err());
+        assert!(CustomRules::parse("[tools.Acme]\ncontains = [\"\"]").is_err());
+    }
+
+    #[test]
+    fn custom_rules_discover_missing_file_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let rules = CustomRules::discover(dir.path(), None).unwrap();
+        assert!(rules.tools.is_empty());
+
+        std::fs::write(dir.path().join(CONFIG_FILE), ACME).unwrap();
+        let rules = CustomRules::discover(dir.path(), None).unwrap();
+        assert_eq!(rules.tools.len(), 1);
+    }
+
+    // ── Windsurf ──
+
+    #[test]
+    fn detects_windsurf() {
+        let msg = "feat: add api\n\nCo-authored-by: Windsurf <noreply@windsurf.com>";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Windsurf);
+    }
+
+    #[test]
+    fn detects_codeium_as_windsurf() {
+        let msg = "fix: bug\n\nCo-authored-by: Codeium <noreply@codeium.com>";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Windsurf);
+    }
+
+    // ── Cody ──
+
+    #[test]
+    fn detects_cody() {
+        let msg = "refactor: split module\n\nGenerated with Cody";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Cody);
+    }
+
+    #[test]
+    fn detects_cody_co_authored() {
+        let msg = "feat: x\n\nCo-authored-by: Cody <cody@sourcegraph.com>";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Cody);
+    }
+
+    #[test]
+    fn human_named_cody_is_not_ai() {
+        let msg = "feat: x\n\nCo-authored-by: Cody Smith <cody@example.com>";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Human);
+    }
+
+    // ── Tabnine ──
+
+    #[test]
+    fn detects_tabnine() {
+        let msg = "feat: add tests\n\nCo-authored-by: Tabnine <noreply@tabnine.com>";
+        assert_eq!(detect_ai_tool(msg, "user@example.com"), AiTool::Tabnine);
+    }
+
+    #[test]
+    fn new_tools_display_names() {
+        assert_eq!(AiTool::Windsurf.to_string(), "Windsurf");
+        assert_eq!(AiTool::Cody.to_string(), "Cody");
+        assert_eq!(AiTool::Tabnine.to_string(), "Tabnine");
+    }
+
+    // ── Bots ──
+
+    #[test]
+    fn detects_dependabot() {
+        assert!(is_bot(
+            "dependabot[bot]",
+            "49699333+dependabot[bot]@users.noreply.github.com"
+        ));
+    }
+
+    #[test]
+    fn detects_renovate_and_actions() {
+        assert!(is_bot("renovate[bot]", "bot@renovateapp.com"));
+        assert!(is_bot(
+            "github-actions[bot]",
+            "41898282+github-actions[bot]@users.noreply.github.com"
+        ));
+        assert!(is_bot("github-actions", "actions@github.com"));
+    }
+
+    #[test]
+    fn human_author_is_not_bot() {
+        assert!(!is_bot("Jane Doe", "jane@example.com"));
+        assert!(!is_bot("robot-fan", "robot@example.com"));
+    }
+
     // ── Human ──
 
     #[test]
@@ -0,0 +1,60 @@
+/// Commit subjects shorter than this many chars ("fix", "wip", "asdf") say nothing.
+const LOW_EFFORT_MIN_LEN: usize = 10;
+
+/// Longer subjects that still say nothing, compared lowercased without
+/// trailing punctuation.
+const LOW_EFFORT_SUBJECTS: &[&str] = &[
+    "work in progress",
+    "minor changes",
+    "minor fixes",
+    "small changes",
+    "small fixes",
+    "some changes",
+    "more changes",
+    "more fixes",
+    "fixed stuff",
+    "update stuff",
+    "updated files",
+    "final version",
+];
+
+/// Whether a commit message's subject line is a low-effort one: under
+/// `LOW_EFFORT_MIN_LEN` chars or one of `LOW_EFFORT_SUBJECTS`.
+pub(crate) fn is_low_effort_message(message: &str) -> bool {
+    let subject = message.lines().next().unwrap_or_default().trim();
+    if subject.chars().count() < LOW_EFFORT_MIN_LEN {
+        return true;
+    }
+    let subject = subject.trim_end_matches(['.', '!', ' ']).to_lowercase();
+    LOW_EFFORT_SUBJECTS.contains(&subject.as_str())
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//  Tests
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_effort_subjects() {
+        for message in [
+            "fix",
+            "wip",
+            "asdf",
+            "update",
+            "Minor changes.",
+            "WORK IN PROGRESS",
+        ] {
+            assert!(is_low_effort_message(message), "{}", message);
+        }
+        for message in [
+            "fix: handle empty config file",
+            "Add retry to the upload client\n\nwip",
+            "Update README install section",
+        ] {
+            assert!(!is_low_effort_message(message), "{}", message);
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod ai_detect;
+pub mod low_effort;
 pub mod parser;
 pub mod timeline;
+pub mod velocity;
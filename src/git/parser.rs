@@ -1,9 +1,11 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Offset, Utc};
 
-use super::ai_detect::{detect_ai_tool, AiTool};
+use super::ai_detect::{detect_ai_tool_with, is_bot, AiTool, CustomRules};
+use super::low_effort::is_low_effort_message;
+use super::timeline::DailyCounts;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -11,40 +13,94 @@ pub struct CommitInfo {
     pub hash: String,
     pub message: String,
     pub author: String,
-    pub timestamp: DateTime<Utc>,
+    /// Author time in the author's own UTC offset, so days and months
+    /// (timeline, velocity) are the ones the author lived through.
+    pub timestamp: DateTime<FixedOffset>,
     pub ai_tool: AiTool,
     pub lines_added: u64,
     pub lines_removed: u64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GitStats {
     pub total_commits: usize,
     pub ai_commits: usize,
     pub human_commits: usize,
+    /// Bot commits (Dependabot, Renovate, ...) excluded from the counts above.
+    pub bot_commits: usize,
     pub ai_ratio: f64,
     pub ai_tools: Vec<(AiTool, usize)>,
+    /// Every counted commit, newest first. Empty with `low_memory`: use
+    /// the tallies (`daily`, `low_effort_commits`, ...) instead.
     pub commits: Vec<CommitInfo>,
-    #[allow(dead_code)]
+    /// Counted commits per day, for the timelines and velocity.
+    pub daily: DailyCounts,
+    /// Counted commits with a low-effort subject ("wip", "fix", ...).
+    pub low_effort_commits: usize,
+    /// Oldest and newest counted commit (`--json` dates, "active for" span).
     pub first_commit_date: Option<DateTime<Utc>>,
-    #[allow(dead_code)]
     pub last_commit_date: Option<DateTime<Utc>>,
     pub repo_fingerprint: Option<String>,
     /// Sanitized remote origin URL (credentials stripped).
     pub remote_url: Option<String>,
+    /// Lines added by AI-authored commits.
+    pub ai_lines_added: u64,
+    /// Lines added across all counted commits.
+    pub total_lines_added: u64,
+    /// Share of added lines that came from AI commits (0.0 to 1.0).
+    pub ai_line_ratio: f64,
+    /// `--all-branches` stopped after `ALL_BRANCHES_MAX_COMMITS`; the oldest
+    /// history is missing from the counts.
+    pub commits_capped: bool,
+    /// Set by `--since-first-ai`: the counts above only cover the AI era.
+    pub ai_era: Option<AiEra>,
+    /// HEAD was detached or unborn, so this branch (origin's default, e.g.
+    /// `origin/main`) was walked instead.
+    pub head_fallback: Option<String>,
+    /// No history was read (`--no-git`, or the path isn't in a repo): the
+    /// counts above are zero and history-based checks and sections are skipped.
+    pub no_git: bool,
+}
+
+/// The window `--since-first-ai` cut the history to, with the lifetime numbers
+/// it would otherwise have reported.
+#[derive(Debug, Clone)]
+pub struct AiEra {
+    /// Author time of the earliest AI-authored commit.
+    pub since: DateTime<Utc>,
+    /// Commits counted before the cut (still honoring --since / --until).
+    pub lifetime_commits: usize,
+    pub lifetime_ai_ratio: f64,
 }
 
 /// Parse a --since value into an optional cutoff DateTime.
 /// Accepts: "all", "6m", "1y", "2y", "YYYY-MM-DD"
 pub fn parse_since(since: &str) -> Option<DateTime<Utc>> {
-    match since.trim().to_lowercase().as_str() {
+    parse_date_bound(since, false)
+}
+
+/// Parse an --until value into an optional upper cutoff DateTime.
+/// Same formats as `parse_since`; a plain date includes that whole day,
+/// so `--until 2025-03-31` keeps commits made on March 31st.
+pub fn parse_until(until: &str) -> Option<DateTime<Utc>> {
+    parse_date_bound(until, true)
+}
+
+fn parse_date_bound(value: &str, end_of_day: bool) -> Option<DateTime<Utc>> {
+    match value.trim().to_lowercase().as_str() {
         "all" | "" => None,
         "6m" => Some(Utc::now() - chrono::Duration::days(180)),
         "1y" => Some(Utc::now() - chrono::Duration::days(365)),
         "2y" => Some(Utc::now() - chrono::Duration::days(730)),
         date => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
             .ok()
-            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .and_then(|d| {
+                if end_of_day {
+                    d.and_hms_opt(23, 59, 59)
+                } else {
+                    d.and_hms_opt(0, 0, 0)
+                }
+            })
             .map(|dt| dt.and_utc()),
     }
 }
@@ -86,21 +142,216 @@ fn normalize_github_url(url: &str) -> Option<String> {
     None
 }
 
-/// Walk all commits in HEAD and classify each as AI or Human.
-/// If `since` is Some, only commits at or after the cutoff are counted,
+/// Which commits `analyze_repo` should count.
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzeOptions {
+    /// Only count commits at or after this time (--since).
+    pub since: Option<DateTime<Utc>>,
+    /// Only count commits at or before this time (--until).
+    pub until: Option<DateTime<Utc>>,
+    /// Count merge commits (more than one parent). Off by default —
+    /// merges inflate totals and rarely carry meaningful AI authorship.
+    pub include_merges: bool,
+    /// Count bot commits (Dependabot, Renovate, ...) as human. Off by default.
+    pub include_bots: bool,
+    /// User-defined detection rules from `vibereport.toml` / `--config`.
+    pub rules: CustomRules,
+    /// Walk this branch (or any ref name) instead of HEAD (--branch).
+    pub branch: Option<String>,
+    /// Walk every local branch (and HEAD), each commit counted once (--all-branches).
+    pub all_branches: bool,
+    /// Start counting at the earliest AI-authored commit (--since-first-ai).
+    pub since_first_ai: bool,
+    /// `path` may be a subdirectory of the repo: only count commits that
+    /// change something under it, and only its lines (--subtree).
+    pub subtree: bool,
+    /// Tally commits as they're walked instead of keeping every `CommitInfo`
+    /// (--low-memory). Leaves `GitStats.commits` empty, and ignores
+    /// `since_first_ai`, which needs the whole history to find its cut.
+    pub low_memory: bool,
+}
+
+/// Most commits `--all-branches` walks (newest first) before giving up on the
+/// rest: every counted commit gets a diff, and long-lived branch sprawl adds up.
+pub const ALL_BRANCHES_MAX_COMMITS: usize = 100_000;
+
+/// Returned by `analyze_repo` when `--branch` names a ref that doesn't exist.
+#[derive(Debug)]
+pub struct BranchNotFound {
+    pub name: String,
+    /// Local and remote-tracking branch names, to suggest alternatives.
+    pub available: Vec<String>,
+}
+
+impl std::fmt::Display for BranchNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "branch '{}' not found", self.name)
+    }
+}
+
+impl std::error::Error for BranchNotFound {}
+
+/// Returned by `analyze_repo` when HEAD is unborn (`git init` with no commits yet).
+#[derive(Debug)]
+pub struct NoCommits;
+
+impl std::fmt::Display for NoCommits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "repository has no commits yet")
+    }
+}
+
+impl std::error::Error for NoCommits {}
+
+/// Resolve `name` (`main`, `origin/dev`, `refs/heads/x`, ...) to the commit it points at.
+fn resolve_branch<'repo>(
+    repo: &'repo gix::Repository,
+    name: &str,
+) -> Result<gix::Commit<'repo>, Box<dyn std::error::Error>> {
+    match repo.try_find_reference(name) {
+        Ok(Some(mut reference)) => Ok(reference.peel_to_commit()?),
+        _ => Err(Box::new(BranchNotFound {
+            name: name.to_string(),
+            available: list_branches(repo),
+        })),
+    }
+}
+
+/// The remote's default branch (`origin/main`) and its tip, per
+/// `refs/remotes/origin/HEAD` (set by `git clone`).
+fn remote_default_branch(repo: &gix::Repository) -> Option<(String, gix::Commit<'_>)> {
+    let mut reference = repo.try_find_reference("refs/remotes/origin/HEAD").ok()??;
+    let name = match reference.target() {
+        gix::refs::TargetRef::Symbolic(target) => target.shorten().to_string(),
+        gix::refs::TargetRef::Object(_) => "origin/HEAD".to_string(),
+    };
+    Some((name, reference.peel_to_commit().ok()?))
+}
+
+/// Commits at HEAD and at the tip of every local branch, deduplicated.
+fn all_branch_tips(repo: &gix::Repository) -> Vec<gix::ObjectId> {
+    let mut tips: Vec<gix::ObjectId> = repo
+        .head_id()
+        .ok()
+        .map(|id| id.detach())
+        .into_iter()
+        .collect();
+    if let Ok(refs) = repo.references() {
+        if let Ok(local) = refs.local_branches() {
+            tips.extend(
+                local
+                    .flatten()
+                    .filter_map(|mut r| r.peel_to_id_in_place().ok().map(|id| id.detach())),
+            );
+        }
+    }
+    tips.sort();
+    tips.dedup();
+    tips
+}
+
+/// Short names of local branches, then remote-tracking ones (`origin/main`).
+fn list_branches(repo: &gix::Repository) -> Vec<String> {
+    let Ok(refs) = repo.references() else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    if let Ok(local) = refs.local_branches() {
+        names.extend(local.flatten().map(|r| r.name().shorten().to_string()));
+    }
+    if let Ok(remote) = refs.remote_branches() {
+        names.extend(
+            remote
+                .flatten()
+                .map(|r| r.name().shorten().to_string())
+                .filter(|n| !n.ends_with("/HEAD")),
+        );
+    }
+    names
+}
+
+/// Stable identity of a repo, used by the API to deduplicate reports.
+/// The origin URL is the key whenever there is one (GitHub URLs normalized to
+/// `github.com/user/repo`), so shallow and full clones of a repo agree. Without
+/// a remote, fall back to `root_commit:` — but only on a complete clone: in a
+/// shallow one the oldest reachable commit is just the depth boundary.
+fn fingerprint(remote_url: Option<&str>, root_commit: &str, complete: bool) -> Option<String> {
+    if let Some(url) = remote_url {
+        return Some(normalize_github_url(url).unwrap_or_else(|| {
+            url.trim_end_matches('/')
+                .trim_end_matches(".git")
+                .to_string()
+        }));
+    }
+    if !complete || root_commit.is_empty() {
+        return None;
+    }
+    Some(format!("{}:", root_commit))
+}
+
+/// Walk all commits in HEAD (or `options.branch`, or every local branch with
+/// `options.all_branches`) and classify each as AI or Human.
+/// Commits outside the `options` window (and merges, unless included) are skipped,
 /// but the root commit hash is still tracked for fingerprinting.
 pub fn analyze_repo(
     path: &Path,
-    since: Option<DateTime<Utc>>,
+    options: &AnalyzeOptions,
 ) -> Result<GitStats, Box<dyn std::error::Error>> {
-    let repo = gix::open(path)?;
+    let (repo, subtree) = if options.subtree {
+        open_subtree(path)?
+    } else {
+        (gix::open(path)?, String::new())
+    };
 
-    let head = repo.head_commit()?;
+    let mut head_fallback = None;
+    let walk = if options.all_branches {
+        let tips = all_branch_tips(&repo);
+        if tips.is_empty() {
+            return Err(Box::new(NoCommits));
+        }
+        // Newest first across branches, so hitting the cap drops the oldest history
+        repo.rev_walk(tips)
+            .sorting(gix::revision::walk::Sorting::ByCommitTime(
+                gix::traverse::commit::simple::CommitTimeOrder::NewestFirst,
+            ))
+    } else {
+        let head = match &options.branch {
+            Some(name) => resolve_branch(&repo, name)?,
+            None => {
+                let head_ref = repo.head()?;
+                // A detached HEAD (CI checkouts, a bisect left running in a --scan-all
+                // dir) or an unborn one says nothing about the main line: prefer
+                // origin's default branch when the clone recorded it
+                let default_branch = if head_ref.is_detached() || head_ref.is_unborn() {
+                    remote_default_branch(&repo)
+                } else {
+                    None
+                };
+                match default_branch {
+                    Some((name, commit)) => {
+                        head_fallback = Some(name);
+                        commit
+                    }
+                    None if head_ref.is_unborn() => return Err(Box::new(NoCommits)),
+                    None => repo.head_commit()?,
+                }
+            }
+        };
+        head.ancestors()
+    };
     let mut commits = Vec::new();
+    let mut tally = Tally::default();
+    let mut bot_times = Vec::new();
     let mut root_commit_full_hash = String::new();
+    let mut commits_capped = false;
 
-    // Walk all ancestors of HEAD
-    for info in head.ancestors().all()? {
+    // Walk all ancestors of HEAD (or the requested branch, or every branch);
+    // commits shared by several branches come out once
+    for (walked, info) in walk.all()?.enumerate() {
+        if options.all_branches && walked == ALL_BRANCHES_MAX_COMMITS {
+            commits_capped = true;
+            break;
+        }
         let info = info?;
         let commit = info.object()?;
         let message = commit.message_raw_sloppy().to_string();
@@ -108,21 +359,51 @@ pub fn analyze_repo(
         let author_name = author_sig.name.to_string();
         let author_email = author_sig.email.to_string();
         let seconds = author_sig.seconds();
+        let offset = author_sig.time().map_or(0, |time| time.offset);
 
-        let timestamp = DateTime::from_timestamp(seconds, 0).unwrap_or_default();
+        let timestamp = DateTime::from_timestamp(seconds, 0)
+            .unwrap_or_default()
+            .with_timezone(&FixedOffset::east_opt(offset).unwrap_or_else(|| Utc.fix()));
 
-        let ai_tool = detect_ai_tool(&message, &author_email);
+        // Classify on the full message: AI trailers live in the body
+        let ai_tool = detect_ai_tool_with(&message, &author_email, &options.rules);
 
         let id_str = info.id.to_string();
         // Track the full hash; last iteration = oldest (root) commit
         root_commit_full_hash = id_str.clone();
 
-        // Filter by --since if specified
-        if let Some(cutoff) = since {
+        // Skip merge commits unless --include-merges
+        if !options.include_merges && commit.parent_ids().count() > 1 {
+            continue;
+        }
+
+        // Filter by --since / --until if specified
+        if let Some(cutoff) = options.since {
             if timestamp < cutoff {
                 continue;
             }
         }
+        if let Some(cutoff) = options.until {
+            if timestamp > cutoff {
+                continue;
+            }
+        }
+
+        // --subtree: skip commits that leave the subdirectory untouched
+        if !subtree.is_empty() && !touches_subtree(&commit, &subtree) {
+            continue;
+        }
+
+        // Exclude automation bots unless --include-bots.
+        // AI-signed commits are never treated as bots.
+        if !options.include_bots && ai_tool == AiTool::Human && is_bot(&author_name, &author_email)
+        {
+            bot_times.push(timestamp);
+            continue;
+        }
+
+        let (lines_added, lines_removed) =
+            diff_line_counts(&repo, &commit, &subtree).unwrap_or((0, 0));
 
         let short_hash = if id_str.len() >= 8 {
             id_str[..8].to_string()
@@ -130,82 +411,795 @@ pub fn analyze_repo(
             id_str
         };
 
-        commits.push(CommitInfo {
+        let commit = CommitInfo {
             hash: short_hash,
             message: message.lines().next().unwrap_or("").to_string(),
             author: author_name,
             timestamp,
             ai_tool,
-            lines_added: 0, // TODO: compute from diff in v0.2
-            lines_removed: 0,
-        });
-    }
-
-    // Count AI tools
-    let ai_commits = commits
-        .iter()
-        .filter(|c| c.ai_tool != AiTool::Human)
-        .count();
-    let human_commits = commits.len() - ai_commits;
-    let ai_ratio = if commits.is_empty() {
+            lines_added,
+            lines_removed,
+        };
+        if options.low_memory {
+            tally.add(&commit);
+        } else {
+            commits.push(commit);
+        }
+    }
+
+    // --since-first-ai: drop everything before the first AI commit, keeping
+    // the lifetime ratio for comparison
+    let mut ai_era = None;
+    if options.since_first_ai && !options.low_memory {
+        let first_ai = commits
+            .iter()
+            .filter(|c| c.ai_tool != AiTool::Human)
+            .map(|c| c.timestamp)
+            .min();
+        if let Some(first_ai) = first_ai {
+            let lifetime_ai = commits
+                .iter()
+                .filter(|c| c.ai_tool != AiTool::Human)
+                .count();
+            ai_era = Some(AiEra {
+                since: first_ai.to_utc(),
+                lifetime_commits: commits.len(),
+                lifetime_ai_ratio: lifetime_ai as f64 / commits.len() as f64,
+            });
+            commits.retain(|c| c.timestamp >= first_ai);
+            bot_times.retain(|t| *t >= first_ai);
+        }
+    }
+    let bot_commits = bot_times.len();
+    // Without --low-memory the tally runs after the --since-first-ai cut
+    for commit in &commits {
+        tally.add(commit);
+    }
+    let ai_ratio = if tally.total == 0 {
         0.0
     } else {
-        ai_commits as f64 / commits.len() as f64
+        tally.ai as f64 / tally.total as f64
     };
 
-    // Count by tool
-    let ai_tools: Vec<(AiTool, usize)> = commits
-        .iter()
-        .filter(|c| c.ai_tool != AiTool::Human)
-        .fold(HashMap::new(), |mut acc, c| {
-            *acc.entry(c.ai_tool.clone()).or_insert(0usize) += 1;
-            acc
-        })
-        .into_iter()
-        .collect();
-
-    let first_commit_date = commits.last().map(|c| c.timestamp);
-    let last_commit_date = commits.first().map(|c| c.timestamp);
-
-    // Compute repo fingerprint
-    // For GitHub repos: use normalized URL only (stable across shallow/full clones)
-    // For other repos: use root_commit_hash:url (or root_commit_hash alone if no remote)
     let remote_url = repo.find_remote("origin").ok().and_then(|r| {
         r.url(gix::remote::Direction::Fetch)
             .map(|u| u.to_bstring().to_string())
     });
     let sanitized_remote_url = remote_url.map(|u| strip_url_credentials(&u));
-    let repo_fingerprint = if let Some(normalized) = sanitized_remote_url
-        .as_deref()
-        .and_then(normalize_github_url)
-    {
-        // GitHub URL → use normalized URL as stable fingerprint
-        Some(normalized)
-    } else if !root_commit_full_hash.is_empty() {
-        // Non-GitHub or no remote → use root commit hash + URL
-        let url_part = sanitized_remote_url.as_deref().unwrap_or_default();
-        Some(format!("{}:{}", root_commit_full_hash, url_part))
-    } else {
-        None
-    };
+    // `Ok(None)`: no shallow file, so the walk reached the true root commit
+    // A capped walk never reached the root either
+    let complete_clone = matches!(repo.shallow_commits(), Ok(None)) && !commits_capped;
+    let repo_fingerprint = fingerprint(
+        sanitized_remote_url.as_deref(),
+        &root_commit_full_hash,
+        complete_clone,
+    );
 
     Ok(GitStats {
-        total_commits: commits.len(),
-        ai_commits,
-        human_commits,
+        total_commits: tally.total,
+        ai_commits: tally.ai,
+        human_commits: tally.total - tally.ai,
+        bot_commits,
         ai_ratio,
-        ai_tools,
+        ai_tools: tally.ai_tools.into_iter().collect(),
         commits,
-        first_commit_date,
-        last_commit_date,
+        daily: tally.daily,
+        low_effort_commits: tally.low_effort,
+        first_commit_date: tally.first,
+        last_commit_date: tally.last,
         repo_fingerprint,
         remote_url: sanitized_remote_url,
+        ai_lines_added: tally.ai_lines_added,
+        total_lines_added: tally.total_lines_added,
+        ai_line_ratio: line_ratio(tally.ai_lines_added, tally.total_lines_added),
+        commits_capped,
+        ai_era,
+        head_fallback,
+        no_git: false,
     })
 }
 
+/// Running totals over the counted commits: everything in `GitStats` but
+/// the commit list itself, built in one pass either way.
+#[derive(Default)]
+struct Tally {
+    total: usize,
+    ai: usize,
+    ai_tools: HashMap<AiTool, usize>,
+    ai_lines_added: u64,
+    total_lines_added: u64,
+    /// Walk order isn't strictly chronological across merged branches
+    first: Option<DateTime<Utc>>,
+    last: Option<DateTime<Utc>>,
+    daily: DailyCounts,
+    low_effort: usize,
+}
+
+impl Tally {
+    fn add(&mut self, commit: &CommitInfo) {
+        self.total += 1;
+        self.total_lines_added += commit.lines_added;
+        if commit.ai_tool != AiTool::Human {
+            self.ai += 1;
+            self.ai_lines_added += commit.lines_added;
+            *self.ai_tools.entry(commit.ai_tool.clone()).or_insert(0) += 1;
+        }
+        let time = commit.timestamp.to_utc();
+        self.first = Some(self.first.map_or(time, |first| first.min(time)));
+        self.last = Some(self.last.map_or(time, |last| last.max(time)));
+        self.daily.add(commit);
+        if is_low_effort_message(&commit.message) {
+            self.low_effort += 1;
+        }
+    }
+}
+
+/// Open the repo containing `path`, which may be a subdirectory of its
+/// worktree, along with `path`'s location in it: `/`-separated, `""` at the
+/// root (and for bare repos).
+pub fn open_subtree(path: &Path) -> Result<(gix::Repository, String), Box<dyn std::error::Error>> {
+    // Canonical first: discovery walks a relative path's parents lexically
+    let full = path.canonicalize()?;
+    let repo = gix::discover(&full)?;
+    let subtree = match repo.workdir() {
+        Some(workdir) => {
+            let root = workdir.canonicalize()?;
+            full.strip_prefix(&root)
+                .map(|rel| {
+                    rel.components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/")
+                })
+                .unwrap_or_default()
+        }
+        None => String::new(),
+    };
+    Ok((repo, subtree))
+}
+
+/// Canonical working-tree root of the repo containing `path` (the repo
+/// root itself for a `--subtree` path), `None` outside a repo or in a bare one.
+pub fn workdir_root(path: &Path) -> Option<PathBuf> {
+    let (repo, _) = open_subtree(path).ok()?;
+    repo.workdir()?.canonicalize().ok()
+}
+
+/// The tree at `subtree` inside `tree` (`tree` itself for `""`), or `None`
+/// when there is no directory there.
+fn subtree_of<'repo>(tree: gix::Tree<'repo>, subtree: &str) -> Option<gix::Tree<'repo>> {
+    if subtree.is_empty() {
+        return Some(tree);
+    }
+    tree.lookup_entry_by_path(subtree)
+        .ok()??
+        .object()
+        .ok()?
+        .try_into_tree()
+        .ok()
+}
+
+/// Whether `commit` changed anything under `subtree` compared to its first
+/// parent: the directory's tree id differs (or it appeared / went away).
+/// This is how git prunes a directory pathspec without diffing whole trees.
+fn touches_subtree(commit: &gix::Commit<'_>, subtree: &str) -> bool {
+    let id_at = |tree: Option<gix::Tree<'_>>| {
+        tree?
+            .lookup_entry_by_path(subtree)
+            .ok()?
+            .map(|entry| entry.object_id())
+    };
+    let parent_tree = commit
+        .parent_ids()
+        .next()
+        .and_then(|id| id.object().ok()?.try_into_commit().ok()?.tree().ok());
+    id_at(commit.tree().ok()) != id_at(parent_tree)
+}
+
+/// Count lines added/removed by a commit against its first parent
+/// (or against the empty tree for a root commit), only under `subtree`
+/// when it isn't `""`.
+/// Renames are tracked (git's default 50% similarity), so moving a file
+/// only counts the lines that changed, not a full delete + add.
+fn diff_line_counts(
+    repo: &gix::Repository,
+    commit: &gix::Commit<'_>,
+    subtree: &str,
+) -> Option<(u64, u64)> {
+    let tree = subtree_of(commit.tree().ok()?, subtree).unwrap_or_else(|| repo.empty_tree());
+    let parent_tree = match commit.parent_ids().next() {
+        Some(id) => {
+            let tree = id.object().ok()?.try_into_commit().ok()?.tree().ok()?;
+            subtree_of(tree, subtree).unwrap_or_else(|| repo.empty_tree())
+        }
+        None => repo.empty_tree(),
+    };
+    let stats = parent_tree
+        .changes()
+        .ok()?
+        .options(|opts| {
+            opts.track_rewrites(Some(gix::diff::Rewrites::default()));
+        })
+        .stats(&tree)
+        .ok()?;
+    Some((stats.lines_added, stats.lines_removed))
+}
+
+/// AI share of added lines. Returns 0.0 when no lines were added.
+fn line_ratio(ai_lines: u64, total_lines: u64) -> f64 {
+    if total_lines == 0 {
+        0.0
+    } else {
+        ai_lines as f64 / total_lines as f64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Write a commit with an empty tree to `reference` and return its id.
+    fn commit(
+        repo: &gix::Repository,
+        reference: &str,
+        message: &str,
+        parents: &[gix::ObjectId],
+    ) -> gix::ObjectId {
+        commit_by(repo, reference, message, "dev", "dev@example.com", parents)
+    }
+
+    fn commit_by(
+        repo: &gix::Repository,
+        reference: &str,
+        message: &str,
+        name: &str,
+        email: &str,
+        parents: &[gix::ObjectId],
+    ) -> gix::ObjectId {
+        commit_at(
+            repo,
+            reference,
+            message,
+            name,
+            email,
+            "1735689600 +0000",
+            parents,
+        )
+    }
+
+    /// `time` is git's raw `<unix seconds> <+hhmm>` form.
+    fn commit_at(
+        repo: &gix::Repository,
+        reference: &str,
+        message: &str,
+        name: &str,
+        email: &str,
+        time: &str,
+        parents: &[gix::ObjectId],
+    ) -> gix::ObjectId {
+        let sig = gix::actor::SignatureRef {
+            name: name.into(),
+            email: email.into(),
+            time,
+        };
+        let tree = gix::ObjectId::empty_tree(repo.object_hash());
+        repo.commit_as(sig, sig, reference, message, tree, parents.iter().copied())
+            .unwrap()
+            .detach()
+    }
+
+    /// root -> (main, side) -> merge
+    fn repo_with_merge(dir: &Path) -> gix::ObjectId {
+        let repo = gix::init(dir).unwrap();
+        let root = commit(&repo, "HEAD", "init", &[]);
+        let side = commit(
+            &repo,
+            "refs/heads/side",
+            "feat: side\n\nCo-authored-by: Claude <noreply@anthropic.com>",
+            &[root],
+        );
+        let main = commit(&repo, "HEAD", "fix: main", &[root]);
+        commit(&repo, "HEAD", "Merge branch 'side'", &[main, side]);
+        root
+    }
+
+    #[test]
+    fn merge_commits_are_skipped_by_default() {
+        let dir = TempDir::new().unwrap();
+        repo_with_merge(dir.path());
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.total_commits, 3);
+        assert_eq!(stats.ai_commits, 1);
+        assert!(stats
+            .commits
+            .iter()
+            .all(|c| !c.message.starts_with("Merge")));
+    }
+
+    /// Commit one file per `(dir, content)` pair as `dir/file.txt`.
+    fn commit_dirs(
+        repo: &gix::Repository,
+        message: &str,
+        dirs: &[(&str, &str)],
+        parents: &[gix::ObjectId],
+    ) -> gix::ObjectId {
+        use gix::objs::tree::{Entry, EntryKind};
+        let mut entries: Vec<Entry> = dirs
+            .iter()
+            .map(|(dir, content)| {
+                let file = gix::objs::Tree {
+                    entries: vec![Entry {
+                        mode: EntryKind::Blob.into(),
+                        filename: "file.txt".into(),
+                        oid: repo.write_blob(content.as_bytes()).unwrap().detach(),
+                    }],
+                };
+                Entry {
+                    mode: EntryKind::Tree.into(),
+                    filename: (*dir).into(),
+                    oid: repo.write_object(file).unwrap().detach(),
+                }
+            })
+            .collect();
+        entries.sort();
+        let tree = repo
+            .write_object(gix::objs::Tree { entries })
+            .unwrap()
+            .detach();
+        let sig = gix::actor::SignatureRef {
+            name: "dev".into(),
+            email: "dev@example.com".into(),
+            time: "1735689600 +0000",
+        };
+        repo.commit_as(sig, sig, "HEAD", message, tree, parents.iter().copied())
+            .unwrap()
+            .detach()
+    }
+
+    #[test]
+    fn subtree_counts_only_commits_and_lines_under_it() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let root = commit_dirs(&repo, "init", &[("api", "a\n"), ("web", "w\n")], &[]);
+        let api = commit_dirs(
+            &repo,
+            "feat: api\n\nCo-authored-by: Claude <noreply@anthropic.com>",
+            &[("api", "a\nb\n"), ("web", "w\n")],
+            &[root],
+        );
+        commit_dirs(
+            &repo,
+            "feat: web",
+            &[("api", "a\nb\n"), ("web", "x\ny\nz\n")],
+            &[api],
+        );
+        fs::create_dir_all(dir.path().join("web")).unwrap();
+
+        let options = AnalyzeOptions {
+            subtree: true,
+            ..Default::default()
+        };
+        let web = analyze_repo(&dir.path().join("web"), &options).unwrap();
+        assert_eq!(web.total_commits, 2);
+        assert_eq!(web.ai_commits, 0);
+        // 1 line in the root commit, then "w" -> "x y z"
+        assert_eq!(web.total_lines_added, 4);
+
+        let whole = analyze_repo(dir.path(), &options).unwrap();
+        assert_eq!(whole.total_commits, 3);
+        assert_eq!(whole.ai_commits, 1);
+
+        // Without --subtree a subdirectory isn't a repo
+        assert!(analyze_repo(&dir.path().join("web"), &AnalyzeOptions::default()).is_err());
+        let (_, subtree) = open_subtree(&dir.path().join("web")).unwrap();
+        assert_eq!(subtree, "web");
+    }
+
+    #[test]
+    fn renames_only_count_the_changed_lines() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let body: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let root = commit_dirs(&repo, "init", &[("api", &body)], &[]);
+        let moved = commit_dirs(&repo, "refactor: move", &[("server", &body)], &[root]);
+        let edited = body.replace("line 20\n", "line twenty\n");
+        commit_dirs(&repo, "refactor: move again", &[("srv", &edited)], &[moved]);
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        let churn = |message: &str| {
+            let c = stats.commits.iter().find(|c| c.message == message).unwrap();
+            (c.lines_added, c.lines_removed)
+        };
+        assert_eq!(churn("init"), (20, 0));
+        assert_eq!(churn("refactor: move"), (0, 0));
+        assert_eq!(churn("refactor: move again"), (1, 1));
+        assert_eq!(stats.total_lines_added, 21);
+    }
+
+    #[test]
+    fn low_memory_tallies_match_the_full_walk() {
+        let dir = TempDir::new().unwrap();
+        repo_with_merge(dir.path());
+
+        let full = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        let options = AnalyzeOptions {
+            low_memory: true,
+            ..Default::default()
+        };
+        let streamed = analyze_repo(dir.path(), &options).unwrap();
+
+        assert!(streamed.commits.is_empty());
+        assert_eq!(streamed.total_commits, full.total_commits);
+        assert_eq!(streamed.ai_commits, full.ai_commits);
+        assert_eq!(streamed.ai_tools, full.ai_tools);
+        assert_eq!(streamed.daily, full.daily);
+        assert_eq!(streamed.low_effort_commits, full.low_effort_commits);
+        assert_eq!(streamed.first_commit_date, full.first_commit_date);
+        assert_eq!(streamed.repo_fingerprint, full.repo_fingerprint);
+        // "init" and "fix: main" are under 10 chars
+        assert_eq!(full.low_effort_commits, 2);
+    }
+
+    #[test]
+    fn include_merges_counts_merge_commits() {
+        let dir = TempDir::new().unwrap();
+        repo_with_merge(dir.path());
+
+        let options = AnalyzeOptions {
+            include_merges: true,
+            ..Default::default()
+        };
+        let stats = analyze_repo(dir.path(), &options).unwrap();
+        assert_eq!(stats.total_commits, 4);
+    }
+
+    #[test]
+    fn fingerprint_uses_true_root_when_merges_skipped() {
+        let dir = TempDir::new().unwrap();
+        let root = repo_with_merge(dir.path());
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.repo_fingerprint, Some(format!("{}:", root)));
+    }
+
+    #[test]
+    fn fingerprint_prefers_normalized_github_url() {
+        let url = "https://github.com/User/Repo.git";
+        let expected = normalize_github_url(url);
+        assert!(expected.is_some());
+        assert_eq!(fingerprint(Some(url), "abc", true), expected);
+        assert_eq!(fingerprint(Some(url), "def", false), expected);
+    }
+
+    #[test]
+    fn fingerprint_uses_remote_url_regardless_of_clone_depth() {
+        let expected = Some("https://gitlab.com/user/repo".to_string());
+        for url in [
+            "https://gitlab.com/user/repo.git",
+            "https://gitlab.com/user/repo/",
+        ] {
+            assert_eq!(fingerprint(Some(url), "abc", true), expected);
+            assert_eq!(fingerprint(Some(url), "def", false), expected);
+        }
+    }
+
+    #[test]
+    fn fingerprint_without_remote_needs_complete_clone() {
+        assert_eq!(fingerprint(None, "abc", true), Some("abc:".into()));
+        assert_eq!(fingerprint(None, "abc", false), None);
+        assert_eq!(fingerprint(None, "", true), None);
+    }
+
+    #[test]
+    fn shallow_clone_fingerprint_skips_boundary_commit() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let root = commit(&repo, "HEAD", "init", &[]);
+        let boundary = commit(&repo, "HEAD", "second", &[root]);
+        commit(&repo, "HEAD", "third", &[boundary]);
+        std::fs::write(
+            dir.path().join(".git").join("shallow"),
+            format!("{}\n", boundary),
+        )
+        .unwrap();
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.total_commits, 2);
+        assert_eq!(stats.repo_fingerprint, None);
+    }
+
+    #[test]
+    fn commit_time_keeps_author_offset() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        // 2025-01-01 00:00 UTC, but 2024-12-31 19:00 for an author at -05:00
+        commit_at(
+            &repo,
+            "HEAD",
+            "init",
+            "dev",
+            "dev@example.com",
+            "1735689600 -0500",
+            &[],
+        );
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        let timestamp = stats.commits[0].timestamp;
+        assert_eq!(timestamp.offset().local_minus_utc(), -5 * 3600);
+        assert_eq!(timestamp.to_rfc3339(), "2024-12-31T19:00:00-05:00");
+        assert_eq!(
+            stats.first_commit_date.unwrap().to_rfc3339(),
+            "2025-01-01T00:00:00+00:00"
+        );
+
+        let timeline = crate::git::timeline::build_timeline(&stats.daily);
+        assert_eq!((timeline[0].year, timeline[0].month), (2024, 12));
+    }
+
+    /// root -> second on `main`, with HEAD detached at root
+    fn repo_with_detached_head(dir: &Path) -> (gix::ObjectId, gix::ObjectId) {
+        let repo = gix::init(dir).unwrap();
+        let root = commit(&repo, "HEAD", "init", &[]);
+        let tip = commit(
+            &repo,
+            "HEAD",
+            "feat: more\n\nCo-authored-by: Claude <noreply@anthropic.com>",
+            &[root],
+        );
+        std::fs::write(dir.join(".git").join("HEAD"), format!("{}\n", root)).unwrap();
+        (root, tip)
+    }
+
+    #[test]
+    fn detached_head_without_remote_walks_head() {
+        let dir = TempDir::new().unwrap();
+        repo_with_detached_head(dir.path());
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.total_commits, 1);
+        assert_eq!(stats.ai_commits, 0);
+        assert_eq!(stats.head_fallback, None);
+    }
+
+    #[test]
+    fn detached_head_prefers_remote_default_branch() {
+        let dir = TempDir::new().unwrap();
+        let (_, tip) = repo_with_detached_head(dir.path());
+        let remotes = dir
+            .path()
+            .join(".git")
+            .join("refs")
+            .join("remotes")
+            .join("origin");
+        std::fs::create_dir_all(&remotes).unwrap();
+        std::fs::write(remotes.join("main"), format!("{}\n", tip)).unwrap();
+        std::fs::write(remotes.join("HEAD"), "ref: refs/remotes/origin/main\n").unwrap();
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.total_commits, 2);
+        assert_eq!(stats.ai_commits, 1);
+        assert!(stats.ai_ratio > 0.0);
+        assert_eq!(stats.head_fallback.as_deref(), Some("origin/main"));
+    }
+
+    /// root -> main, plus an unmerged `feature` branch with an AI commit
+    fn repo_with_unmerged_branch(dir: &Path) {
+        let repo = gix::init(dir).unwrap();
+        let root = commit(&repo, "HEAD", "init", &[]);
+        commit(&repo, "HEAD", "fix: main", &[root]);
+        commit(
+            &repo,
+            "refs/heads/feature",
+            "feat: wip\n\nCo-authored-by: Claude <noreply@anthropic.com>",
+            &[root],
+        );
+    }
+
+    #[test]
+    fn head_walk_ignores_unmerged_branches() {
+        let dir = TempDir::new().unwrap();
+        repo_with_unmerged_branch(dir.path());
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.total_commits, 2);
+        assert_eq!(stats.ai_commits, 0);
+    }
+
+    #[test]
+    fn since_first_ai_drops_the_pre_ai_history() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let mut parent = Vec::new();
+        for (message, time) in [
+            ("init", "1600000000 +0000"),
+            ("fix: old bug", "1610000000 +0000"),
+            ("feat: old feature", "1620000000 +0000"),
+            (
+                "feat: agent\n\nCo-authored-by: Claude <noreply@anthropic.com>",
+                "1730000000 +0200",
+            ),
+            ("fix: after", "1740000000 +0000"),
+        ] {
+            let id = commit_at(
+                &repo,
+                "HEAD",
+                message,
+                "dev",
+                "dev@example.com",
+                time,
+                &parent,
+            );
+            parent = vec![id];
+        }
+
+        let lifetime = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(lifetime.total_commits, 5);
+        assert!(lifetime.ai_era.is_none());
+
+        let options = AnalyzeOptions {
+            since_first_ai: true,
+            ..Default::default()
+        };
+        let stats = analyze_repo(dir.path(), &options).unwrap();
+        assert_eq!(stats.total_commits, 2);
+        assert_eq!(stats.ai_commits, 1);
+        assert!((stats.ai_ratio - 0.5).abs() < f64::EPSILON);
+        let era = stats.ai_era.unwrap();
+        assert_eq!(era.since.timestamp(), 1730000000);
+        assert_eq!(stats.first_commit_date, Some(era.since));
+        assert_eq!(era.lifetime_commits, 5);
+        assert!((era.lifetime_ai_ratio - 0.2).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn since_first_ai_without_ai_keeps_everything() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let root = commit(&repo, "HEAD", "init", &[]);
+        commit(&repo, "HEAD", "fix: by hand", &[root]);
+        let options = AnalyzeOptions {
+            since_first_ai: true,
+            ..Default::default()
+        };
+        let stats = analyze_repo(dir.path(), &options).unwrap();
+        assert!(stats.ai_era.is_none());
+        assert_eq!(stats.total_commits, 2);
+    }
+
+    #[test]
+    fn all_branches_counts_shared_commits_once() {
+        let dir = TempDir::new().unwrap();
+        repo_with_unmerged_branch(dir.path());
+
+        let options = AnalyzeOptions {
+            all_branches: true,
+            ..Default::default()
+        };
+        let stats = analyze_repo(dir.path(), &options).unwrap();
+        assert_eq!(stats.total_commits, 3);
+        assert_eq!(stats.ai_commits, 1);
+        assert!(!stats.commits_capped);
+    }
+
+    fn repo_with_dependabot(dir: &Path) {
+        let repo = gix::init(dir).unwrap();
+        let root = commit(&repo, "HEAD", "init", &[]);
+        let bump = commit_by(
+            &repo,
+            "HEAD",
+            "chore(deps): bump serde",
+            "dependabot[bot]",
+            "49699333+dependabot[bot]@users.noreply.github.com",
+            &[root],
+        );
+        commit(
+            &repo,
+            "HEAD",
+            "feat: x\n\nCo-authored-by: Claude <noreply@anthropic.com>",
+            &[bump],
+        );
+    }
+
+    #[test]
+    fn bot_commits_are_excluded_by_default() {
+        let dir = TempDir::new().unwrap();
+        repo_with_dependabot(dir.path());
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.total_commits, 2);
+        assert_eq!(stats.human_commits, 1);
+        assert_eq!(stats.ai_commits, 1);
+        assert_eq!(stats.bot_commits, 1);
+    }
+
+    #[test]
+    fn include_bots_counts_them_as_human() {
+        let dir = TempDir::new().unwrap();
+        repo_with_dependabot(dir.path());
+
+        let options = AnalyzeOptions {
+            include_bots: true,
+            ..Default::default()
+        };
+        let stats = analyze_repo(dir.path(), &options).unwrap();
+        assert_eq!(stats.total_commits, 3);
+        assert_eq!(stats.human_commits, 2);
+        assert_eq!(stats.bot_commits, 0);
+    }
+
+    #[test]
+    fn empty_repo_reports_no_commits() {
+        let dir = TempDir::new().unwrap();
+        gix::init(dir.path()).unwrap();
+
+        let err = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap_err();
+        assert!(err.is::<NoCommits>());
+        assert_eq!(err.to_string(), "repository has no commits yet");
+    }
+
+    #[test]
+    fn detects_trailer_in_commit_body() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        commit(
+            &repo,
+            "HEAD",
+            "Update docs\n\nMore context here.\n\nCo-authored-by: Claude <noreply@anthropic.com>\n",
+            &[],
+        );
+
+        let stats = analyze_repo(dir.path(), &AnalyzeOptions::default()).unwrap();
+        assert_eq!(stats.ai_commits, 1);
+        // Only the subject is kept for display
+        assert_eq!(stats.commits[0].message, "Update docs");
+    }
+
+    /// main: one human commit; feature: branches off it with an AI commit on top.
+    fn repo_with_feature_branch(dir: &Path) {
+        let repo = gix::init(dir).unwrap();
+        let base = commit(&repo, "refs/heads/main", "init", &[]);
+        commit(
+            &repo,
+            "refs/heads/feature",
+            "Add feature\n\nCo-authored-by: Claude <noreply@anthropic.com>\n",
+            &[base],
+        );
+    }
+
+    #[test]
+    fn branch_option_walks_that_branch() {
+        let dir = TempDir::new().unwrap();
+        repo_with_feature_branch(dir.path());
+
+        let options = |branch: &str| AnalyzeOptions {
+            branch: Some(branch.to_string()),
+            ..Default::default()
+        };
+        let main = analyze_repo(dir.path(), &options("main")).unwrap();
+        assert_eq!((main.total_commits, main.ai_commits), (1, 0));
+        let feature = analyze_repo(dir.path(), &options("feature")).unwrap();
+        assert_eq!((feature.total_commits, feature.ai_commits), (2, 1));
+    }
+
+    #[test]
+    fn missing_branch_lists_available_ones() {
+        let dir = TempDir::new().unwrap();
+        repo_with_feature_branch(dir.path());
+
+        let options = AnalyzeOptions {
+            branch: Some("nope".to_string()),
+            ..Default::default()
+        };
+        let err = analyze_repo(dir.path(), &options).unwrap_err();
+        let not_found = err.downcast_ref::<BranchNotFound>().unwrap();
+        assert_eq!(not_found.to_string(), "branch 'nope' not found");
+        let mut available = not_found.available.clone();
+        available.sort();
+        assert_eq!(available, ["feature", "main"]);
+    }
 
     #[test]
     fn parse_since_all_returns_none() {
@@ -257,6 +1251,41 @@ mod tests {
         assert!(parse_since("yesterday").is_none());
     }
 
+    #[test]
+    fn line_ratio_handles_zero_lines() {
+        assert_eq!(line_ratio(0, 0), 0.0);
+        assert_eq!(line_ratio(10, 0), 0.0);
+    }
+
+    #[test]
+    fn line_ratio_computes_share() {
+        assert!((line_ratio(3000, 4000) - 0.75).abs() < 1e-9);
+        assert_eq!(line_ratio(0, 500), 0.0);
+    }
+
+    #[test]
+    fn parse_until_date_includes_whole_day() {
+        let dt = parse_until("2025-03-31").unwrap();
+        assert_eq!(
+            dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2025-03-31 23:59:59"
+        );
+    }
+
+    #[test]
+    fn parse_until_all_returns_none() {
+        assert!(parse_until("all").is_none());
+        assert!(parse_until("").is_none());
+    }
+
+    #[test]
+    fn since_and_until_compose_into_quarter() {
+        let since = parse_since("2025-01-01").unwrap();
+        let until = parse_until("2025-03-31").unwrap();
+        assert!(since < until);
+        assert_eq!((until - since).num_days(), 89);
+    }
+
     #[test]
     fn strip_credentials_from_https_url() {
         assert_eq!(
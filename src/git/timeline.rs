@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate, Weekday};
 
 use super::ai_detect::AiTool;
 use super::parser::CommitInfo;
@@ -23,18 +23,190 @@ pub struct DailyStats {
     pub ai_commits: usize,
 }
 
+/// Counted commits per author-local day, `(total, ai)`. Tallied during the
+/// history walk, so timelines and velocity never need every `CommitInfo`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DailyCounts(BTreeMap<NaiveDate, (usize, usize)>);
+
+impl DailyCounts {
+    pub fn add(&mut self, commit: &CommitInfo) {
+        let entry = self
+            .0
+            .entry(commit.timestamp.date_naive())
+            .or_insert((0, 0));
+        entry.0 += 1;
+        if commit.ai_tool != AiTool::Human {
+            entry.1 += 1;
+        }
+    }
+
+    /// `(day, total, ai)` for each active day, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDate, usize, usize)> + '_ {
+        self.0.iter().map(|(&day, &(total, ai))| (day, total, ai))
+    }
+
+    /// Number of active days.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> FromIterator<&'a CommitInfo> for DailyCounts {
+    fn from_iter<I: IntoIterator<Item = &'a CommitInfo>>(commits: I) -> Self {
+        let mut days = DailyCounts::default();
+        for commit in commits {
+            days.add(commit);
+        }
+        days
+    }
+}
+
+/// Bucket size for `build_timeline_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    /// Pick a bucket size from the span of history: daily under 60 days,
+    /// weekly under a year, monthly otherwise.
+    pub fn for_span(days: &DailyCounts) -> Self {
+        let days = match (days.0.keys().next(), days.0.keys().next_back()) {
+            (Some(&first), Some(&last)) => (last - first).num_days(),
+            _ => 0,
+        };
+        if days < 60 {
+            Granularity::Day
+        } else if days < 365 {
+            Granularity::Week
+        } else {
+            Granularity::Month
+        }
+    }
+
+    /// Lowercase unit name ("day", "week", "month").
+    pub fn unit(self) -> &'static str {
+        match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+}
+
+impl std::str::FromStr for Granularity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" | "daily" => Ok(Granularity::Day),
+            "week" | "weekly" => Ok(Granularity::Week),
+            "month" | "monthly" => Ok(Granularity::Month),
+            _ => Err(format!("expected day, week, or month (got \"{}\")", s)),
+        }
+    }
+}
+
+/// One bucket of a `build_timeline_with` timeline.
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct TimelineBucket {
+    /// "YYYY-MM-DD", "YYYY-Www" (ISO week), or "YYYY-MM".
+    pub key: String,
+    /// First day of the bucket (Monday for weeks).
+    pub start: NaiveDate,
+    pub total_commits: usize,
+    pub ai_commits: usize,
+    pub ai_ratio: f64,
+}
+
+/// Group commits by day, ISO week, or month. Returns sorted oldest-first.
+pub fn build_timeline_with(days: &DailyCounts, granularity: Granularity) -> Vec<TimelineBucket> {
+    let mut buckets: BTreeMap<NaiveDate, (usize, usize)> = BTreeMap::new();
+
+    for (date, total, ai) in days.iter() {
+        let start = match granularity {
+            Granularity::Day => date,
+            Granularity::Week => {
+                let week = date.iso_week();
+                NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Mon).unwrap_or(date)
+            }
+            Granularity::Month => date.with_day(1).unwrap_or(date),
+        };
+        let entry = buckets.entry(start).or_insert((0, 0));
+        entry.0 += total;
+        entry.1 += ai;
+    }
+
+    // Fill empty buckets between the first and last commit so bars are evenly spaced in time
+    if let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) {
+        let mut start = first;
+        while start < last {
+            start = match granularity {
+                Granularity::Day => start + chrono::Days::new(1),
+                Granularity::Week => start + chrono::Days::new(7),
+                Granularity::Month => start + chrono::Months::new(1),
+            };
+            buckets.entry(start).or_insert((0, 0));
+        }
+    }
+
+    buckets
+        .into_iter()
+        .map(|(start, (total, ai))| {
+            let key = match granularity {
+                Granularity::Day => start.format("%Y-%m-%d").to_string(),
+                Granularity::Week => {
+                    let week = start.iso_week();
+                    format!("{}-W{:02}", week.year(), week.week())
+                }
+                Granularity::Month => start.format("%Y-%m").to_string(),
+            };
+            TimelineBucket {
+                key,
+                start,
+                total_commits: total,
+                ai_commits: ai,
+                ai_ratio: if total == 0 {
+                    0.0
+                } else {
+                    ai as f64 / total as f64
+                },
+            }
+        })
+        .collect()
+}
+
 /// Group commits by month and compute AI ratio per month.
-/// Returns sorted by date (oldest first).
-pub fn build_timeline(commits: &[CommitInfo]) -> Vec<MonthlyStats> {
+/// Returns sorted by date (oldest first), with a zero-commit entry for every
+/// month between the first and last commit.
+pub fn build_timeline(days: &DailyCounts) -> Vec<MonthlyStats> {
     // Use BTreeMap so keys are naturally sorted (oldest first).
     let mut buckets: BTreeMap<(i32, u32), (usize, usize)> = BTreeMap::new();
 
-    for commit in commits {
-        let key = (commit.timestamp.year(), commit.timestamp.month());
-        let entry = buckets.entry(key).or_insert((0, 0));
-        entry.0 += 1; // total
-        if commit.ai_tool != AiTool::Human {
-            entry.1 += 1; // ai
+    for (date, total, ai) in days.iter() {
+        let entry = buckets.entry((date.year(), date.month())).or_insert((0, 0));
+        entry.0 += total;
+        entry.1 += ai;
+    }
+
+    // Fill gap months so the chart's x-axis is proportional to real time
+    if let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) {
+        let (mut year, mut month) = first;
+        while (year, month) < last {
+            if month == 12 {
+                year += 1;
+                month = 1;
+            } else {
+                month += 1;
+            }
+            buckets.entry((year, month)).or_insert((0, 0));
         }
     }
 
@@ -61,26 +233,10 @@ pub fn build_timeline(commits: &[CommitInfo]) -> Vec<MonthlyStats> {
 
 /// Group commits by day. Returns sorted oldest-first.
 /// Each entry shows commits on that specific day (not cumulative).
-pub fn build_daily_timeline(commits: &[CommitInfo]) -> Vec<DailyStats> {
-    let mut buckets: BTreeMap<(i32, u32, u32), (usize, usize)> = BTreeMap::new();
-
-    for commit in commits {
-        let key = (
-            commit.timestamp.year(),
-            commit.timestamp.month(),
-            commit.timestamp.day(),
-        );
-        let entry = buckets.entry(key).or_insert((0, 0));
-        entry.0 += 1;
-        if commit.ai_tool != AiTool::Human {
-            entry.1 += 1;
-        }
-    }
-
-    buckets
-        .into_iter()
-        .map(|((y, m, d), (total, ai))| DailyStats {
-            date: format!("{:04}-{:02}-{:02}", y, m, d),
+pub fn build_daily_timeline(days: &DailyCounts) -> Vec<DailyStats> {
+    days.iter()
+        .map(|(date, total, ai)| DailyStats {
+            date: date.format("%Y-%m-%d").to_string(),
             total_commits: total,
             ai_commits: ai,
         })
@@ -97,16 +253,41 @@ mod tests {
             hash: "abcd1234".to_string(),
             message: "test commit".to_string(),
             author: "dev".to_string(),
-            timestamp: Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap(),
+            timestamp: Utc
+                .with_ymd_and_hms(year, month, day, 12, 0, 0)
+                .unwrap()
+                .fixed_offset(),
             ai_tool,
             lines_added: 0,
             lines_removed: 0,
         }
     }
 
+    fn days(commits: &[CommitInfo]) -> DailyCounts {
+        commits.iter().collect()
+    }
+
+    #[test]
+    fn months_follow_author_local_time_across_the_dateline() {
+        // Same instant (2025-01-31 20:00 UTC): already February at +14:00,
+        // still January at -12:00
+        let instant = Utc.with_ymd_and_hms(2025, 1, 31, 20, 0, 0).unwrap();
+        let at = |hours: i32| CommitInfo {
+            timestamp: instant.with_timezone(&chrono::FixedOffset::east_opt(hours * 3600).unwrap()),
+            ..make_commit(2025, 1, 1, AiTool::Human)
+        };
+        let timeline = build_timeline(&days(&[at(14), at(-12)]));
+        let months: Vec<_> = timeline.iter().map(|m| (m.year, m.month)).collect();
+        assert_eq!(months, [(2025, 1), (2025, 2)]);
+
+        let daily = build_daily_timeline(&days(&[at(14), at(-12)]));
+        let dates: Vec<_> = daily.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2025-01-31", "2025-02-01"]);
+    }
+
     #[test]
     fn empty_commits_yields_empty_timeline() {
-        let timeline = build_timeline(&[]);
+        let timeline = build_timeline(&DailyCounts::default());
         assert!(timeline.is_empty());
     }
 
@@ -117,7 +298,7 @@ mod tests {
             make_commit(2025, 6, 15, AiTool::Human),
             make_commit(2025, 6, 28, AiTool::Aider),
         ];
-        let timeline = build_timeline(&commits);
+        let timeline = build_timeline(&days(&commits));
         assert_eq!(timeline.len(), 1);
         let entry = &timeline[0];
         assert_eq!(entry.year, 2025);
@@ -135,11 +316,40 @@ mod tests {
             make_commit(2025, 3, 10, AiTool::ClaudeCode),
             make_commit(2025, 6, 20, AiTool::Cursor),
         ];
-        let timeline = build_timeline(&commits);
-        assert_eq!(timeline.len(), 3);
+        let timeline = build_timeline(&days(&commits));
+        assert_eq!(timeline.len(), 7);
         assert_eq!((timeline[0].year, timeline[0].month), (2025, 3));
-        assert_eq!((timeline[1].year, timeline[1].month), (2025, 6));
-        assert_eq!((timeline[2].year, timeline[2].month), (2025, 9));
+        assert_eq!((timeline[3].year, timeline[3].month), (2025, 6));
+        assert_eq!((timeline[6].year, timeline[6].month), (2025, 9));
+    }
+
+    #[test]
+    fn gap_months_filled_with_zero_buckets() {
+        let commits = vec![
+            make_commit(2025, 1, 10, AiTool::ClaudeCode),
+            make_commit(2025, 3, 10, AiTool::Human),
+        ];
+        let timeline = build_timeline(&days(&commits));
+        assert_eq!(timeline.len(), 3);
+        let feb = &timeline[1];
+        assert_eq!((feb.year, feb.month), (2025, 2));
+        assert_eq!(feb.total_commits, 0);
+        assert_eq!(feb.ai_commits, 0);
+        assert_eq!(feb.human_commits, 0);
+        assert_eq!(feb.ai_ratio, 0.0);
+    }
+
+    #[test]
+    fn gap_fill_crosses_year_boundary() {
+        let commits = vec![
+            make_commit(2024, 11, 1, AiTool::Human),
+            make_commit(2025, 2, 1, AiTool::Human),
+        ];
+        let months: Vec<_> = build_timeline(&days(&commits))
+            .iter()
+            .map(|m| (m.year, m.month))
+            .collect();
+        assert_eq!(months, [(2024, 11), (2024, 12), (2025, 1), (2025, 2)]);
     }
 
     #[test]
@@ -157,7 +367,7 @@ mod tests {
             make_commit(2025, 3, 15, AiTool::CodexCli),
             make_commit(2025, 3, 28, AiTool::GithubCopilot),
         ];
-        let timeline = build_timeline(&commits);
+        let timeline = build_timeline(&days(&commits));
         assert_eq!(timeline.len(), 3);
 
         // January
@@ -179,13 +389,102 @@ mod tests {
         assert!((timeline[2].ai_ratio - 1.0).abs() < 1e-9);
     }
 
+    #[test]
+    fn weekly_buckets_keyed_by_iso_week() {
+        let commits = vec![
+            // 2024-12-30 (Mon) and 2025-01-02 share ISO week 2025-W01
+            make_commit(2024, 12, 30, AiTool::ClaudeCode),
+            make_commit(2025, 1, 2, AiTool::Human),
+            make_commit(2025, 1, 8, AiTool::Human),
+        ];
+        let timeline = build_timeline_with(&days(&commits), Granularity::Week);
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline[0].key, "2025-W01");
+        assert_eq!(
+            timeline[0].start,
+            NaiveDate::from_ymd_opt(2024, 12, 30).unwrap()
+        );
+        assert_eq!(timeline[0].total_commits, 2);
+        assert_eq!(timeline[0].ai_commits, 1);
+        assert_eq!(timeline[1].key, "2025-W02");
+    }
+
+    #[test]
+    fn daily_and_monthly_keys() {
+        let commits = vec![
+            make_commit(2025, 6, 1, AiTool::ClaudeCode),
+            make_commit(2025, 6, 1, AiTool::Human),
+            make_commit(2025, 6, 3, AiTool::Human),
+        ];
+        let daily = build_timeline_with(&days(&commits), Granularity::Day);
+        let keys: Vec<_> = daily.iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(keys, ["2025-06-01", "2025-06-02", "2025-06-03"]);
+        assert!((daily[0].ai_ratio - 0.5).abs() < 1e-9);
+        assert_eq!(daily[1].total_commits, 0);
+        assert_eq!(daily[1].ai_ratio, 0.0);
+
+        let monthly = build_timeline_with(&days(&commits), Granularity::Month);
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].key, "2025-06");
+        assert_eq!(monthly[0].total_commits, 3);
+    }
+
+    /// `daily_commits` in `--json`; the VPS worker's backfill sums these per scan date.
+    #[test]
+    fn daily_timeline_counts_each_active_day_oldest_first() {
+        let commits = vec![
+            make_commit(2025, 6, 3, AiTool::Human),
+            make_commit(2025, 6, 1, AiTool::ClaudeCode),
+            make_commit(2025, 6, 1, AiTool::Human),
+        ];
+        let daily = build_daily_timeline(&days(&commits));
+        let days: Vec<_> = daily
+            .iter()
+            .map(|d| (d.date.as_str(), d.total_commits, d.ai_commits))
+            .collect();
+        // Days without commits are left out, unlike build_timeline_with
+        assert_eq!(days, [("2025-06-01", 2, 1), ("2025-06-03", 1, 0)]);
+    }
+
+    #[test]
+    fn granularity_follows_span() {
+        let short = vec![
+            make_commit(2025, 1, 1, AiTool::Human),
+            make_commit(2025, 2, 15, AiTool::Human),
+        ];
+        assert_eq!(Granularity::for_span(&days(&short)), Granularity::Day);
+
+        let medium = vec![
+            make_commit(2025, 1, 1, AiTool::Human),
+            make_commit(2025, 6, 1, AiTool::Human),
+        ];
+        assert_eq!(Granularity::for_span(&days(&medium)), Granularity::Week);
+
+        let long = vec![
+            make_commit(2023, 1, 1, AiTool::Human),
+            make_commit(2025, 6, 1, AiTool::Human),
+        ];
+        assert_eq!(Granularity::for_span(&days(&long)), Granularity::Month);
+        assert_eq!(
+            Granularity::for_span(&DailyCounts::default()),
+            Granularity::Day
+        );
+    }
+
+    #[test]
+    fn granularity_from_str() {
+        assert_eq!("week".parse::<Granularity>(), Ok(Granularity::Week));
+        assert_eq!("Monthly".parse::<Granularity>(), Ok(Granularity::Month));
+        assert!("hour".parse::<Granularity>().is_err());
+    }
+
     #[test]
     fn spans_across_years() {
         let commits = vec![
             make_commit(2024, 12, 25, AiTool::ClaudeCode),
             make_commit(2025, 1, 5, AiTool::Human),
         ];
-        let timeline = build_timeline(&commits);
+        let timeline = build_timeline(&days(&commits));
         assert_eq!(timeline.len(), 2);
         assert_eq!((timeline[0].year, timeline[0].month), (2024, 12));
         assert_eq!((timeline[1].year, timeline[1].month), (2025, 1));
@@ -0,0 +1,149 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use super::timeline::DailyCounts;
+
+/// Activity summary for the "VELOCITY" section and `velocity` in `--json`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Velocity {
+    /// Distinct author-local days with at least one commit.
+    pub active_days: usize,
+    pub commits_per_active_day: f64,
+    /// Most consecutive calendar days with commits.
+    pub longest_streak: usize,
+    /// Weekday with the most commits (earliest in the week on ties); None without commits.
+    pub busiest_weekday: Option<Weekday>,
+}
+
+/// Compute velocity stats from the counted commits. All zeros without commits.
+pub fn compute(days: &DailyCounts) -> Velocity {
+    if days.is_empty() {
+        return Velocity::default();
+    }
+    let mut per_weekday = [0usize; 7];
+    let mut commits = 0;
+    for (day, total, _) in days.iter() {
+        per_weekday[day.weekday().num_days_from_monday() as usize] += total;
+        commits += total;
+    }
+
+    let mut longest_streak = 0;
+    let mut streak = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for (day, _, _) in days.iter() {
+        streak = match previous {
+            Some(prev) if prev.succ_opt() == Some(day) => streak + 1,
+            _ => 1,
+        };
+        longest_streak = longest_streak.max(streak);
+        previous = Some(day);
+    }
+
+    // max_by_key keeps the last maximum; reverse so ties go to the earliest weekday
+    let busiest = (0..7u8)
+        .rev()
+        .max_by_key(|&i| per_weekday[i as usize])
+        .and_then(|i| Weekday::try_from(i).ok());
+
+    let active_days = days.len();
+    Velocity {
+        active_days,
+        commits_per_active_day: commits as f64 / active_days as f64,
+        longest_streak,
+        busiest_weekday: busiest,
+    }
+}
+
+/// Full English weekday name ("Monday"), for the report and JSON.
+pub fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//  Tests
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::ai_detect::AiTool;
+    use crate::git::parser::CommitInfo;
+    use chrono::{TimeZone, Utc};
+
+    fn commit_on(year: i32, month: u32, day: u32) -> CommitInfo {
+        CommitInfo {
+            hash: "abcd1234".to_string(),
+            message: "test commit".to_string(),
+            author: "dev".to_string(),
+            timestamp: Utc
+                .with_ymd_and_hms(year, month, day, 12, 0, 0)
+                .unwrap()
+                .fixed_offset(),
+            ai_tool: AiTool::Human,
+            lines_added: 0,
+            lines_removed: 0,
+        }
+    }
+
+    fn compute(commits: &[CommitInfo]) -> Velocity {
+        super::compute(&commits.iter().collect())
+    }
+
+    #[test]
+    fn no_commits_is_all_zero() {
+        assert_eq!(compute(&[]), Velocity::default());
+    }
+
+    #[test]
+    fn single_commit() {
+        let v = compute(&[commit_on(2025, 6, 4)]);
+        assert_eq!(v.active_days, 1);
+        assert_eq!(v.commits_per_active_day, 1.0);
+        assert_eq!(v.longest_streak, 1);
+        assert_eq!(v.busiest_weekday, Some(Weekday::Wed));
+    }
+
+    #[test]
+    fn streaks_span_month_ends_and_reset_on_gaps() {
+        let commits = [
+            commit_on(2025, 5, 30),
+            commit_on(2025, 5, 31),
+            commit_on(2025, 6, 1),
+            commit_on(2025, 6, 1),
+            commit_on(2025, 6, 3),
+            commit_on(2025, 6, 4),
+        ];
+        let v = compute(&commits);
+        assert_eq!(v.active_days, 5);
+        assert!((v.commits_per_active_day - 1.2).abs() < 1e-9);
+        assert_eq!(v.longest_streak, 3);
+    }
+
+    #[test]
+    fn busiest_weekday_breaks_ties_by_week_order() {
+        // 2025-06-02 is a Monday, 2025-06-06 a Friday
+        let commits = [
+            commit_on(2025, 6, 6),
+            commit_on(2025, 6, 2),
+            commit_on(2025, 6, 13),
+            commit_on(2025, 6, 9),
+        ];
+        assert_eq!(compute(&commits).busiest_weekday, Some(Weekday::Mon));
+
+        let commits = [
+            commit_on(2025, 6, 6),
+            commit_on(2025, 6, 13),
+            commit_on(2025, 6, 2),
+        ];
+        let v = compute(&commits);
+        assert_eq!(v.busiest_weekday, Some(Weekday::Fri));
+        assert_eq!(weekday_name(v.busiest_weekday.unwrap()), "Friday");
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One scan, as a line of `history.jsonl`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Report name (`user/repo`, or the directory name without a GitHub remote).
+    pub repo: String,
+    pub scanned_at: DateTime<Utc>,
+    pub points: u32,
+    pub grade: String,
+    pub ai_ratio: f64,
+    pub commits: usize,
+}
+
+/// Where scans are recorded: `$XDG_DATA_HOME/vibereport/history.jsonl`,
+/// falling back to `~/.local/share/vibereport` (or `%LOCALAPPDATA%\vibereport`
+/// on Windows).
+pub fn history_file() -> Option<PathBuf> {
+    let non_empty = |key: &str| std::env::var_os(key).filter(|v| !v.is_empty());
+    non_empty("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .or_else(|| non_empty("LOCALAPPDATA").map(PathBuf::from))
+        .map(|dir| dir.join("vibereport").join("history.jsonl"))
+}
+
+/// Every recorded scan, oldest first. A missing file is an empty history;
+/// lines that don't parse (hand edits, a truncated write) are skipped.
+pub fn load(file: &Path) -> Vec<Entry> {
+    std::fs::read_to_string(file)
+        .map(|raw| {
+            raw.lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Most scans kept: past this, `append` drops the oldest ones.
+pub const MAX_ENTRIES: usize = 5_000;
+
+/// Append one scan, creating the file and its directory as needed. Once the
+/// file holds more than `MAX_ENTRIES` scans it is rewritten with the newest.
+pub fn append(file: &Path, entry: &Entry) -> std::io::Result<()> {
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let line = serde_json::to_string(entry)?;
+    let mut out = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file)?;
+    writeln!(out, "{}", line)?;
+    drop(out);
+
+    let entries = load(file);
+    if entries.len() > MAX_ENTRIES {
+        rewrite(file, &entries[entries.len() - MAX_ENTRIES..])?;
+    }
+    Ok(())
+}
+
+/// Replace the file with `entries`, through a temp file so a crash midway
+/// never loses the history.
+fn rewrite(file: &Path, entries: &[Entry]) -> std::io::Result<()> {
+    let tmp = file.with_extension("jsonl.tmp");
+    let mut out = std::io::BufWriter::new(std::fs::File::create(&tmp)?);
+    for entry in entries {
+        writeln!(out, "{}", serde_json::to_string(entry)?)?;
+    }
+    out.flush()?;
+    drop(out);
+    std::fs::rename(&tmp, file)
+}
+
+/// How a score ranks against the latest scan of every other recorded repo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percentile {
+    /// Share of those repos with a strictly lower score, 0-100.
+    pub higher_than: f64,
+    /// How many other repos were compared.
+    pub repos: usize,
+}
+
+/// Rank `points` for `repo` against the other repos in `entries`, or `None`
+/// when nothing else has been scanned yet.
+pub fn percentile(entries: &[Entry], repo: &str, points: u32) -> Option<Percentile> {
+    let mut latest: HashMap<&str, u32> = HashMap::new();
+    for entry in entries.iter().filter(|e| e.repo != repo) {
+        latest.insert(&entry.repo, entry.points);
+    }
+    if latest.is_empty() {
+        return None;
+    }
+    let lower = latest.values().filter(|&&p| p < points).count();
+    Some(Percentile {
+        higher_than: lower as f64 * 100.0 / latest.len() as f64,
+        repos: latest.len(),
+    })
+}
+
+/// Scans of `repo`, oldest first.
+pub fn trend<'a>(entries: &'a [Entry], repo: &str) -> Vec<&'a Entry> {
+    entries.iter().filter(|e| e.repo == repo).collect()
+}
+
+// ━━ Tests ━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(repo: &str, points: u32) -> Entry {
+        Entry {
+            repo: repo.to_string(),
+            scanned_at: "2026-01-01T00:00:00Z".parse().unwrap(),
+            points,
+            grade: "B".to_string(),
+            ai_ratio: 0.5,
+            commits: 10,
+        }
+    }
+
+    #[test]
+    fn append_then_load_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("nested/history.jsonl");
+        assert!(load(&file).is_empty());
+
+        append(&file, &entry("a/one", 40)).unwrap();
+        append(&file, &entry("b/two", 70)).unwrap();
+        assert_eq!(load(&file), vec![entry("a/one", 40), entry("b/two", 70)]);
+    }
+
+    #[test]
+    fn append_keeps_only_the_newest_entries() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("history.jsonl");
+        let old: Vec<Entry> = (0..MAX_ENTRIES).map(|i| entry("old", i as u32)).collect();
+        rewrite(&file, &old).unwrap();
+
+        append(&file, &entry("new", 1)).unwrap();
+        let entries = load(&file);
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0], entry("old", 1));
+        assert_eq!(entries.last(), Some(&entry("new", 1)));
+    }
+
+    #[test]
+    fn load_skips_bad_lines() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("history.jsonl");
+        append(&file, &entry("a/one", 40)).unwrap();
+        let mut raw = std::fs::read_to_string(&file).unwrap();
+        raw.push_str("{\"repo\": \"trunc\n");
+        std::fs::write(&file, raw).unwrap();
+        assert_eq!(load(&file), vec![entry("a/one", 40)]);
+    }
+
+    #[test]
+    fn percentile_uses_the_latest_scan_of_other_repos() {
+        let entries = vec![
+            entry("a", 90),
+            entry("a", 20), // latest for "a" wins
+            entry("b", 50),
+            entry("c", 60),
+            entry("d", 10),
+            entry("me", 99), // our own scans are never compared
+        ];
+        let p = percentile(&entries, "me", 50).unwrap();
+        assert_eq!(p.repos, 4);
+        // Only "a" (20) and "d" (10) are strictly lower
+        assert_eq!(p.higher_than, 50.0);
+    }
+
+    #[test]
+    fn no_percentile_without_other_repos() {
+        assert!(percentile(&[], "me", 50).is_none());
+        assert!(percentile(&[entry("me", 10)], "me", 50).is_none());
+    }
+
+    #[test]
+    fn trend_keeps_one_repo_in_order() {
+        let entries = vec![entry("me", 10), entry("other", 50), entry("me", 30)];
+        let points: Vec<u32> = trend(&entries, "me").iter().map(|e| e.points).collect();
+        assert_eq!(points, vec![10, 30]);
+    }
+}
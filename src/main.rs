@@ -1,12 +1,22 @@
+// serde_json::json! for the full JSON report needs more than the default 128
+#![recursion_limit = "256"]
+
+mod config;
 mod git;
+mod history;
 mod project;
 mod render;
 mod scanner;
 mod score;
 mod share;
 
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use std::io::{IsTerminal, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -23,25 +33,267 @@ struct Cli {
     #[arg(long)]
     scan_all: bool,
 
-    /// Export report as SVG
+    /// Number of repos to analyze in parallel with --scan-all (default: CPU count)
+    #[arg(short, long, value_name = "N")]
+    jobs: Option<usize>,
+
+    /// With --scan-all, order the table by score (default), ai, lines, commits, or name
+    #[arg(
+        long,
+        value_name = "COLUMN",
+        default_value = "score",
+        requires = "scan_all"
+    )]
+    sort: scanner::multi_report::RepoSort,
+
+    /// With --scan-all, flip the --sort order
+    #[arg(long, requires = "scan_all")]
+    reverse: bool,
+
+    /// With --scan-all, weight the second average score by commits (default) or lines
+    #[arg(
+        long,
+        value_name = "WEIGHT",
+        default_value = "commits",
+        requires = "scan_all"
+    )]
+    weight_by: scanner::multi_report::ScoreWeight,
+
+    /// With --scan-all, leave out repos matching a glob (repeatable, e.g. '**/vendor/**')
+    #[arg(long, value_name = "GLOB", requires = "scan_all")]
+    exclude: Vec<String>,
+
+    /// With --scan-all, leave out submodules (repos a parent's .gitmodules declares)
+    #[arg(long, requires = "scan_all")]
+    skip_submodules: bool,
+
+    /// With --scan-all, leave out repos with fewer than N commits
+    #[arg(long, value_name = "N", default_value_t = 0, requires = "scan_all")]
+    min_commits: usize,
+
+    /// Export report as SVG (same as --format svg=PATH)
     #[arg(long)]
     svg: Option<String>,
 
-    /// Export report as JSON
+    /// SVG colors: light, dark (default), or a JSON theme file
+    #[arg(long, value_name = "THEME", default_value = "dark")]
+    svg_theme: String,
+
+    /// Export report as PNG (needs the `png` cargo feature)
+    #[arg(long, value_name = "PATH")]
+    png: Option<String>,
+
+    /// PNG size as a multiple of the SVG's 600px width
+    #[arg(long, value_name = "N", default_value_t = 2.0)]
+    png_scale: f32,
+
+    /// Export report as GitHub-flavored Markdown (same as --format markdown=PATH)
+    #[arg(long, value_name = "PATH")]
+    markdown: Option<String>,
+
+    /// Show a 0-100 code-health score (tests, CI, linting, secrets, deps) instead of the Vibe Score
+    #[arg(long)]
+    quality: bool,
+
+    /// Exit with code 1 when the Vibe Score exceeds N (with --quality: when the quality score is below N)
+    #[arg(long, value_name = "N", conflicts_with_all = ["scan_all", "compare"])]
+    fail_over: Option<u32>,
+
+    /// Exit with code 2 when a .env file, hardcoded secret or private key is found
+    #[arg(long, conflicts_with_all = ["scan_all", "compare"])]
+    fail_on_secrets: bool,
+
+    /// Outputs to write, repeatable: terminal, json, svg=PATH, markdown=PATH, csv=PATH
+    /// (default: terminal; the flags below are shorthands)
+    #[arg(long, value_name = "FORMAT[=PATH]")]
+    format: Vec<render::Format>,
+
+    /// Export report as JSON (same as --format json)
     #[arg(long)]
     json: bool,
 
+    /// Like --json, but minified onto one line (for scripts that parse many reports)
+    #[arg(long, conflicts_with = "print_payload")]
+    json_compact: bool,
+
+    /// Write the JSON report to a file instead of stdout (implies --json)
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<String>,
+
     /// Don't share report to vibereport.dev
     #[arg(long)]
     no_share: bool,
 
+    /// Print a one-line summary (for shell prompts and CI statuses) instead of the full report
+    #[arg(long, conflicts_with_all = ["json", "output", "quality", "scan_all", "compare"])]
+    oneline: bool,
+
+    /// Stay running and re-render the report whenever a commit lands (Ctrl-C to quit)
+    #[arg(
+        long,
+        conflicts_with_all = ["scan_all", "compare", "json", "output", "print_payload", "fail_over", "fail_on_secrets"]
+    )]
+    watch: bool,
+
+    /// Show this repo's score trend from the local scan history instead of the report
+    #[arg(
+        long,
+        conflicts_with_all = ["scan_all", "compare", "watch", "json", "output", "oneline", "quality", "print_payload"]
+    )]
+    history: bool,
+
+    /// Don't record this scan in the local scan history
+    #[arg(long)]
+    no_history: bool,
+
+    /// After the report, list the commits classified as AI (hash, tool, subject)
+    #[arg(long, conflicts_with = "oneline")]
+    verbose: bool,
+
+    /// Print the share payload as JSON instead of uploading it (implies --no-share)
+    #[arg(long, conflicts_with = "json")]
+    print_payload: bool,
+
+    /// Share without the owner/repo names, and a salted hash of the repo fingerprint instead of the raw remote URL / root commit
+    #[arg(long)]
+    anonymous: bool,
+
     /// Only analyze commits since this date (YYYY-MM-DD, "6m", "1y", "2y", or "all")
     #[arg(long, default_value = "all")]
     since: String,
+
+    /// Only analyze commits up to this date (YYYY-MM-DD, "6m", "1y", "2y", or "all")
+    #[arg(long, default_value = "all")]
+    until: String,
+
+    /// Count merge commits in the AI ratio and timeline (skipped by default)
+    #[arg(long)]
+    include_merges: bool,
+
+    /// Count bot commits (dependabot, renovate, ...) as human (excluded by default)
+    #[arg(long)]
+    include_bots: bool,
+
+    /// Timeline chart granularity: day, week, or month (default: picked from the history span)
+    #[arg(long, value_name = "GRANULARITY")]
+    timeline: Option<git::timeline::Granularity>,
+
+    /// Width of the terminal report box (minimum 40)
+    #[arg(long, default_value_t = render::terminal::DEFAULT_WIDTH)]
+    width: usize,
+
+    /// Languages listed in the report; the rest are summed into an "Other" row
+    #[arg(long, value_name = "N", default_value_t = render::terminal::DEFAULT_TOP_LANGUAGES)]
+    top_languages: usize,
+
+    /// Disable colored output (also honors the NO_COLOR env var)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Only count commits from the first AI-authored one on; the lifetime AI ratio is shown alongside
+    #[arg(long, conflicts_with = "compare")]
+    since_first_ai: bool,
+
+    /// Compare commits before vs. since this date (YYYY-MM-DD, "6m", "1y", "2y")
+    #[arg(long, value_name = "DATE")]
+    compare: Option<String>,
+
+    /// Analyze this branch instead of HEAD (e.g. "main", "origin/feature")
+    #[arg(long, value_name = "NAME")]
+    branch: Option<String>,
+
+    /// Count commits from every local branch, not just HEAD (each commit once)
+    #[arg(long, conflicts_with = "branch")]
+    all_branches: bool,
+
+    /// Treat the path as a subdirectory of its repo (monorepo package): only its commits, lines and files
+    #[arg(long, conflicts_with = "scan_all")]
+    subtree: bool,
+
+    /// Skip git history and report the project structure only (automatic when the path isn't in a repo)
+    #[arg(
+        long,
+        conflicts_with_all = ["scan_all", "compare", "watch", "subtree", "branch", "all_branches", "since_first_ai"]
+    )]
+    no_git: bool,
+
+    /// Tally commits as they're walked instead of keeping each one in memory (100k+ commit repos)
+    #[arg(long, conflicts_with_all = ["verbose", "since_first_ai"])]
+    low_memory: bool,
+
+    /// GitHub token for cloning private repos (prefer the GITHUB_TOKEN env var)
+    #[arg(
+        long,
+        env = "GITHUB_TOKEN",
+        hide_env_values = true,
+        value_name = "TOKEN"
+    )]
+    token: Option<String>,
+
+    /// Rescan the working tree instead of reusing .git/vibereport-cache.json, and
+    /// clone github: repos into a temp dir instead of the ~/.cache/vibereport cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Fetch the latest commits even if the cached clone is recent
+    #[arg(long)]
+    refresh: bool,
+
+    /// Seed for picking the roast (default: derived from the repo, so it's stable per repo)
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+
+    /// Map the AI ratio to points: linear (default), quadratic, or logistic
+    #[arg(long, value_name = "CURVE", default_value = "linear")]
+    ai_curve: score::calculator::AiCurve,
+
+    /// Custom roasts file (TOML, or JSON for .json) replacing built-in roasts per condition/score band
+    #[arg(long, value_name = "PATH")]
+    roasts: Option<String>,
+
+    /// Custom AI detection rules (defaults to vibereport.toml at the repo root)
+    #[arg(long, global = true)]
+    config: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Score weights from the config files' `[weights]` table (not a flag)
+    #[arg(skip)]
+    weights: score::calculator::ScoreWeights,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Print which AI tool a commit message classifies as, and which rule matched (JSON)
+    Classify {
+        /// Full commit message, trailers included
+        message: String,
+
+        /// Commit author email (some tools only show up as the author)
+        #[arg(long, value_name = "EMAIL", default_value = "")]
+        author_email: String,
+    },
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let cli = parse_cli();
+    if let Some(Command::Classify {
+        message,
+        author_email,
+    }) = &cli.command
+    {
+        run_classify(&cli, message, author_email);
+        return;
+    }
+    // Validate the roasts and theme files before a potentially long scan
+    score_options(&cli);
+    svg_theme(&cli);
+    if cli.png.is_some() && !cfg!(feature = "png") {
+        eprintln!("Error: PNG export is not available in this build");
+        eprintln!("  Reinstall with: cargo install vibereport --features png");
+        std::process::exit(1);
+    }
 
     // Check if path is a GitHub reference
     if let Some((user, repo)) = scanner::remote::parse_github_ref(&cli.path) {
@@ -52,11 +304,23 @@ fn main() {
     let path = Path::new(&cli.path);
 
     if cli.scan_all {
-        run_scan_all(path);
+        run_scan_all(&cli, path);
         return;
     }
 
-    run_single(&cli, path);
+    if let Some(pivot) = &cli.compare {
+        run_compare(&cli, path, pivot);
+        return;
+    }
+
+    if cli.watch {
+        run_watch(&cli, path);
+        return;
+    }
+
+    if run_single(&cli, path).is_err() {
+        std::process::exit(1);
+    }
 }
 
 /// Extract "user/repo" from a GitHub remote URL.
@@ -80,50 +344,558 @@ fn extract_github_slug(remote_url: Option<&str>) -> Option<String> {
     None
 }
 
+/// Build the commit filters for `analyze_repo` from CLI flags.
+/// Exits with an error if the detection config is malformed.
+fn analyze_options(cli: &Cli, repo_path: &Path) -> git::parser::AnalyzeOptions {
+    git::parser::AnalyzeOptions {
+        since: git::parser::parse_since(&cli.since),
+        until: git::parser::parse_until(&cli.until),
+        include_merges: cli.include_merges,
+        include_bots: cli.include_bots,
+        branch: cli.branch.clone(),
+        all_branches: cli.all_branches,
+        since_first_ai: cli.since_first_ai,
+        low_memory: cli.low_memory,
+        subtree: cli.subtree,
+        rules: load_rules(cli, repo_path).unwrap_or_else(|e| {
+            eprintln!("Error: invalid detection config");
+            eprintln!("  {}", e);
+            std::process::exit(1);
+        }),
+    }
+}
+
+/// Parse CLI flags, filling flags not given on the command line from the
+/// config files (see `config::Defaults` for the precedence).
+fn parse_cli() -> Cli {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let on_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+    let local_dir = Path::new(&cli.path);
+    let is_local = scanner::remote::parse_github_ref(&cli.path).is_none() && local_dir.is_dir();
+    let defaults = config::Defaults::load(is_local.then_some(local_dir));
+
+    if let Some(since) = defaults.since.filter(|_| !on_cli("since")) {
+        cli.since = since;
+    }
+    if let Some(no_share) = defaults.no_share.filter(|_| !on_cli("no_share")) {
+        cli.no_share = no_share;
+    }
+    // A live dashboard never uploads: one share per commit would flood the leaderboard.
+    // Nor does a subtree: its report would replace the whole repo's entry.
+    // --history only looks back at local scans
+    if cli.watch || cli.subtree || cli.history {
+        cli.no_share = true;
+    }
+    if let Some(width) = defaults.width.filter(|_| !on_cli("width")) {
+        cli.width = width;
+    }
+    if let Some(color) = defaults.color.filter(|_| !on_cli("no_color")) {
+        cli.no_color = !color;
+    }
+    if let Some(roasts) = defaults.roasts.filter(|_| !on_cli("roasts")) {
+        cli.roasts = Some(roasts.to_string_lossy().into_owned());
+    }
+    if let Some(curve) = defaults.ai_curve.filter(|_| !on_cli("ai_curve")) {
+        cli.ai_curve = curve.parse().unwrap_or_else(|e| {
+            eprintln!("Error: invalid ai_curve in config");
+            eprintln!("  {}", e);
+            std::process::exit(1);
+        });
+    }
+    if let Some(weights) = defaults.weights {
+        cli.weights = toml::Value::Table(weights).try_into().unwrap_or_else(|e| {
+            eprintln!("Error: invalid [weights] in config");
+            eprintln!("  {}", e);
+            std::process::exit(1);
+        });
+    }
+    cli.format = output_formats(&cli);
+    check_formats(&cli);
+    cli
+}
+
+/// Every output to write, in order: the `--format` values, then the
+/// shorthand flags (`--json`/`--json-compact`/`-o`, `--svg`, `--markdown`). Without
+/// `--format`, the terminal report comes first unless JSON is selected, as
+/// the shorthands always behaved.
+fn output_formats(cli: &Cli) -> Vec<render::Format> {
+    let mut formats: Vec<render::Format> = Vec::new();
+    let shorthands = [
+        (cli.json || cli.json_compact || cli.output.is_some()).then_some(render::Format::Json),
+        cli.svg.clone().map(render::Format::Svg),
+        cli.markdown.clone().map(render::Format::Markdown),
+    ];
+    for format in cli
+        .format
+        .iter()
+        .cloned()
+        .chain(shorthands.into_iter().flatten())
+    {
+        if !formats.contains(&format) {
+            formats.push(format);
+        }
+    }
+    if cli.format.is_empty() && !formats.contains(&render::Format::Json) {
+        formats.insert(0, render::Format::Terminal);
+    }
+    formats
+}
+
+/// Reject output combinations clap can't see: file exports in the modes
+/// that only print terminal/JSON reports, `--format json` against the
+/// terminal-only modes, and two reports sharing stdout.
+fn check_formats(cli: &Cli) {
+    let conflict = |msg: String| -> ! {
+        Cli::command()
+            .error(clap::error::ErrorKind::ArgumentConflict, msg)
+            .exit()
+    };
+    let mode = if cli.scan_all {
+        Some("--scan-all")
+    } else if cli.compare.is_some() {
+        Some("--compare")
+    } else {
+        None
+    };
+    if let Some(mode) = mode {
+        let export = cli.format.iter().find_map(|format| match format {
+            render::Format::Svg(_) => Some("svg"),
+            render::Format::Markdown(_) => Some("markdown"),
+            render::Format::Csv(_) => Some("csv"),
+            render::Format::Terminal | render::Format::Json => None,
+        });
+        if let Some(export) = export.or(cli.png.as_ref().map(|_| "png")) {
+            conflict(format!(
+                "{} only prints the terminal or JSON report; it can't export {}",
+                mode, export
+            ));
+        }
+    }
+    if !json_output(cli) {
+        return;
+    }
+    for (set, flag) in [
+        (cli.oneline, "--oneline"),
+        (cli.watch, "--watch"),
+        (cli.history, "--history"),
+    ] {
+        if set {
+            conflict(format!(
+                "{} only applies to the terminal report, not JSON",
+                flag
+            ));
+        }
+    }
+    if cli.output.is_none() && cli.format.contains(&render::Format::Terminal) {
+        conflict(
+            "the terminal and JSON reports can't both go to stdout; add -o PATH for the JSON"
+                .into(),
+        );
+    }
+}
+
+/// Whether the JSON report is an output (`--json`, `-o` or `--format json`).
+fn json_output(cli: &Cli) -> bool {
+    cli.format.contains(&render::Format::Json)
+}
+
+/// Scoring options from CLI flags. Built once: the roasts file is loaded and
+/// validated a single time even when scoring many repos.
+fn score_options(cli: &Cli) -> &'static score::calculator::ScoreOptions {
+    static OPTIONS: OnceLock<score::calculator::ScoreOptions> = OnceLock::new();
+    OPTIONS.get_or_init(|| score::calculator::ScoreOptions {
+        seed: cli.seed,
+        roasts: match &cli.roasts {
+            Some(path) => score::roast::CustomRoasts::load(Path::new(path)).unwrap_or_else(|e| {
+                eprintln!("Error: invalid roasts file");
+                eprintln!("  {}", e);
+                std::process::exit(1);
+            }),
+            None => Default::default(),
+        },
+        weights: cli.weights.clone(),
+        ai_curve: cli.ai_curve,
+    })
+}
+
+/// SVG theme from `--svg-theme`, resolved once. Exits if the theme file is invalid.
+fn svg_theme(cli: &Cli) -> &'static render::svg::SvgTheme {
+    static THEME: OnceLock<render::svg::SvgTheme> = OnceLock::new();
+    THEME.get_or_init(|| {
+        render::svg::SvgTheme::resolve(&cli.svg_theme).unwrap_or_else(|e| {
+            eprintln!("Error: invalid SVG theme");
+            eprintln!("  {}", e);
+            std::process::exit(1);
+        })
+    })
+}
+
+/// Terminal rendering options from CLI flags and the NO_COLOR convention.
+fn render_options(cli: &Cli) -> render::terminal::RenderOptions {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    render::terminal::RenderOptions {
+        timeline: cli.timeline,
+        color: !cli.no_color && !no_color_env,
+        width: cli.width,
+        top_languages: cli.top_languages,
+        sort: cli.sort,
+        reverse: cli.reverse,
+        weight_by: cli.weight_by,
+    }
+}
+
+/// Load custom detection rules from `--config`, else `vibereport.toml` in the repo.
+fn load_rules(
+    cli: &Cli,
+    repo_path: &Path,
+) -> Result<git::ai_detect::CustomRules, Box<dyn std::error::Error>> {
+    git::ai_detect::CustomRules::discover(repo_path, cli.config.as_deref().map(Path::new))
+}
+
+/// A scan that couldn't produce a report. The reason is already on stderr;
+/// the caller decides whether that ends the process (`--watch` keeps going).
+#[derive(Debug)]
+struct ScanFailed;
+
 /// Analyze a single local repo.
-fn run_single(cli: &Cli, path: &Path) {
+fn run_single(cli: &Cli, path: &Path) -> Result<(), ScanFailed> {
     eprintln!("Scanning {}...", path.display());
 
     // ── Step 1: Analyze git history ──
-    let since = git::parser::parse_since(&cli.since);
-    let git_stats = match git::parser::analyze_repo(path, since) {
+    let analyzed = if cli.no_git {
+        Ok(no_git_stats())
+    } else {
+        git::parser::analyze_repo(path, &analyze_options(cli, path))
+    };
+    let git_stats = match analyzed {
         Ok(s) => s,
+        Err(e) if e.is::<git::parser::BranchNotFound>() => {
+            explain_branch_not_found(&*e);
+            return Err(ScanFailed);
+        }
+        Err(e) if e.is::<git::parser::NoCommits>() => empty_repo_stats(cli, path)?,
+        // A code dump without .git: report what the tree itself shows
+        Err(_) if path.is_dir() && gix::discover(path).is_err() => {
+            eprintln!(
+                "Warning: not a git repository ({}); reporting the project structure only",
+                path.display()
+            );
+            no_git_stats()
+        }
         Err(e) => {
             eprintln!("Error: not a git repository ({})", path.display());
             eprintln!("  {}", e);
+            if !cli.subtree && git::parser::open_subtree(path).is_ok() {
+                eprintln!("  It's inside one; pass --subtree to scan just this directory");
+            }
             eprintln!();
             eprintln!("Usage:");
             eprintln!("  vibereport /path/to/repo       # scan a specific repo");
             eprintln!("  vibereport --scan-all ~/Desktop # scan all repos in a directory");
             eprintln!("  vibereport github:user/repo     # scan a GitHub repo");
-            std::process::exit(1);
+            return Err(ScanFailed);
         }
     };
+    warn_if_commits_capped(&git_stats);
+    note_head_fallback(&git_stats);
+    warn_if_no_ai_era(cli, &git_stats);
 
     // ── Step 2: Analyze project structure ──
-    let project_stats = project::analyze_project_with_ai_ratio(path, git_stats.ai_ratio);
+    let project_stats = project::analyze_project(path, &git_stats, !cli.no_cache);
 
     // ── Step 3: Calculate vibe score ──
-    let vibe_score = score::calculator::calculate(&git_stats, &project_stats);
+    let vibe_score = score::calculator::calculate(&git_stats, &project_stats, score_options(cli));
 
     // ── Repo name ──
-    // Try to extract "user/repo" from git remote URL; fall back to directory name
-    let repo_name = extract_github_slug(git_stats.remote_url.as_deref()).unwrap_or_else(|| {
+    let repo_name = local_repo_name(cli, path, &git_stats);
+
+    // ── Output + export ──
+    output_report(cli, &git_stats, &project_stats, &vibe_score, &repo_name);
+    Ok(())
+}
+
+/// Changes closer together than this are rendered once (a rebase or
+/// `commit --amend` rewrites the reflog several times in a row).
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// `--watch`: render, then re-render each time `.git/logs/HEAD` changes.
+/// Nothing touches the terminal mode, so the default Ctrl-C exit is clean.
+/// Only a failing first scan exits; later failures (a half-written rebase,
+/// a branch deleted under `--branch`) are reported and the watch goes on.
+fn run_watch(cli: &Cli, path: &Path) {
+    let git_dir = match gix::discover(path) {
+        Ok(repo) => repo.git_dir().to_path_buf(),
+        Err(e) => {
+            eprintln!("Error: not a git repository ({})", path.display());
+            eprintln!("  {}", e);
+            std::process::exit(1);
+        }
+    };
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).unwrap_or_else(|e| {
+        eprintln!("Error: can't watch {}", git_dir.display());
+        eprintln!("  {}", e);
+        std::process::exit(1);
+    });
+
+    let render = || {
+        if std::io::stdout().is_terminal() {
+            let _ = crossterm::execute!(
+                std::io::stdout(),
+                crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+                crossterm::cursor::MoveTo(0, 0)
+            );
+        }
+        let scanned = run_single(cli, path);
+        if scanned.is_err() {
+            eprintln!("  Scan failed; retrying on the next commit");
+        }
+        eprintln!("  Watching for new commits (Ctrl-C to quit)");
+        scanned
+    };
+    // First render before checking the reflog: an empty repo gets the usual error
+    if render().is_err() {
+        std::process::exit(1);
+    }
+
+    let logs = git_dir.join("logs");
+    if !logs.is_dir() {
+        eprintln!("Error: no reflog to watch in {}", git_dir.display());
+        eprintln!("  --watch follows logs/HEAD, which git writes on every commit");
+        std::process::exit(1);
+    }
+    notify::Watcher::watch(&mut watcher, &logs, notify::RecursiveMode::NonRecursive)
+        .unwrap_or_else(|e| {
+            eprintln!("Error: can't watch {}", logs.display());
+            eprintln!("  {}", e);
+            std::process::exit(1);
+        });
+
+    while wait_for_head_change(&rx) {
+        let _ = render();
+    }
+    // The watcher is gone: nothing will ever fire again
+    std::process::exit(1);
+}
+
+/// Block until an event touches `logs/HEAD`, then until the changes settle
+/// for `WATCH_DEBOUNCE`. False once the watcher has hung up.
+fn wait_for_head_change(rx: &mpsc::Receiver<notify::Result<notify::Event>>) -> bool {
+    let touches_head = |event: &notify::Event| {
+        event
+            .paths
+            .iter()
+            .any(|p| p.file_name().is_some_and(|n| n == "HEAD"))
+    };
+    loop {
+        match rx.recv() {
+            Ok(Ok(event)) if touches_head(&event) => break,
+            Ok(_) => continue,
+            Err(_) => return false,
+        }
+    }
+    while rx.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+    true
+}
+
+/// `vibereport classify`: run the detector (custom rules included) on one
+/// message, without a repo.
+fn run_classify(cli: &Cli, message: &str, author_email: &str) {
+    let rules = load_rules(cli, Path::new(&cli.path)).unwrap_or_else(|e| {
+        eprintln!("Error: invalid detection config");
+        eprintln!("  {}", e);
+        std::process::exit(1);
+    });
+    let classification = git::ai_detect::classify(message, author_email, &rules);
+    let output = serde_json::json!({
+        "tool": classification.tool.to_string(),
+        "ai": classification.tool != git::ai_detect::AiTool::Human,
+        "reason": classification.reason,
+    });
+    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+}
+
+/// Try to extract "user/repo" from git remote URL; fall back to directory name.
+/// With `--subtree`, the subdirectory is appended: `user/repo/packages/web`.
+fn local_repo_name(cli: &Cli, path: &Path, git_stats: &git::parser::GitStats) -> String {
+    if cli.subtree {
+        if let Ok((repo, subtree)) = git::parser::open_subtree(path) {
+            if let (false, Some(workdir)) = (subtree.is_empty(), repo.workdir()) {
+                return format!("{}/{}", repo_name_at(cli, workdir, git_stats), subtree);
+            }
+        }
+    }
+    repo_name_at(cli, path, git_stats)
+}
+
+fn repo_name_at(cli: &Cli, path: &Path, git_stats: &git::parser::GitStats) -> String {
+    extract_github_slug(git_stats.remote_url.as_deref()).unwrap_or_else(|| {
         path.canonicalize()
             .ok()
             .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
             .unwrap_or_else(|| cli.path.clone())
-    });
+    })
+}
 
-    // ── Output + export ──
-    output_report(cli, &git_stats, &project_stats, &vibe_score, &repo_name);
+/// Analyze a local repo twice — commits before `pivot` and commits since — and
+/// print the deltas. Both windows still honor --since / --until.
+fn run_compare(cli: &Cli, path: &Path, pivot: &str) {
+    let Some(pivot_time) = git::parser::parse_since(pivot) else {
+        eprintln!("Error: invalid --compare date: {}", pivot);
+        eprintln!("  Expected YYYY-MM-DD, \"6m\", \"1y\", or \"2y\"");
+        std::process::exit(1);
+    };
+    eprintln!(
+        "Scanning {} (comparing around {})...",
+        path.display(),
+        pivot
+    );
+
+    let base = analyze_options(cli, path);
+    let before_options = git::parser::AnalyzeOptions {
+        until: Some(
+            base.until.map_or(pivot_time, |u| u.min(pivot_time)) - chrono::Duration::seconds(1),
+        ),
+        ..base.clone()
+    };
+    let after_options = git::parser::AnalyzeOptions {
+        since: Some(base.since.map_or(pivot_time, |s| s.max(pivot_time))),
+        ..base
+    };
+
+    let analyze = |options: &git::parser::AnalyzeOptions| {
+        let stats = git::parser::analyze_repo(path, options).unwrap_or_else(|e| {
+            if e.is::<git::parser::BranchNotFound>() {
+                exit_branch_not_found(&*e);
+            }
+            if e.is::<git::parser::NoCommits>() {
+                eprintln!("Error: {} ({})", e, path.display());
+                std::process::exit(1);
+            }
+            eprintln!("Error: not a git repository ({})", path.display());
+            eprintln!("  {}", e);
+            std::process::exit(1);
+        });
+        warn_if_commits_capped(&stats);
+        note_head_fallback(&stats);
+        stats
+    };
+    let before = analyze(&before_options);
+    let after = analyze(&after_options);
+
+    // The working tree is the same for both windows; only the git side differs
+    let project_stats = project::analyze_project(path, &after, !cli.no_cache);
+    let before_score = score::calculator::calculate(&before, &project_stats, score_options(cli));
+    let after_score = score::calculator::calculate(&after, &project_stats, score_options(cli));
+    let repo_name = local_repo_name(cli, path, &after);
+
+    if json_output(cli) {
+        let output = serde_json::json!({
+            "schema_version": JSON_SCHEMA_VERSION,
+            "repo": repo_name,
+            "compare": pivot,
+            "before": report_json(cli, &before, &project_stats, &before_score, &repo_name),
+            "after": report_json(cli, &after, &project_stats, &after_score, &repo_name),
+        });
+        write_json(cli, &output);
+    } else {
+        render::terminal::render_diff(
+            &render::terminal::DiffSide {
+                git: &before,
+                score: &before_score,
+            },
+            &render::terminal::DiffSide {
+                git: &after,
+                score: &after_score,
+            },
+            &repo_name,
+            pivot,
+            &render_options(cli),
+        );
+    }
+}
+
+/// A repo without commits is an error in the terminal, but `--json` still
+/// gets a valid all-zero report so scripts don't crash.
+fn empty_repo_stats(cli: &Cli, path: &Path) -> Result<git::parser::GitStats, ScanFailed> {
+    if !json_output(cli) {
+        eprintln!("Error: repository has no commits yet ({})", path.display());
+        return Err(ScanFailed);
+    }
+    eprintln!(
+        "Warning: repository has no commits yet ({})",
+        path.display()
+    );
+    Ok(git::parser::GitStats::default())
+}
+
+/// Stats for a `--no-git` scan: no history, so a 0 AI ratio.
+fn no_git_stats() -> git::parser::GitStats {
+    git::parser::GitStats {
+        no_git: true,
+        ..Default::default()
+    }
+}
+
+/// `--all-branches` walked `ALL_BRANCHES_MAX_COMMITS` and dropped the rest.
+fn warn_if_commits_capped(git_stats: &git::parser::GitStats) {
+    if git_stats.commits_capped {
+        eprintln!(
+            "Warning: --all-branches stopped after the newest {} commits; older history is not counted",
+            git::parser::ALL_BRANCHES_MAX_COMMITS
+        );
+    }
+}
+
+/// HEAD wasn't on a branch, so origin's default branch was analyzed instead.
+fn note_head_fallback(git_stats: &git::parser::GitStats) {
+    if let Some(branch) = &git_stats.head_fallback {
+        eprintln!(
+            "Note: HEAD is detached or unborn; analyzing {} (the remote's default branch) instead. Pass --branch to pick another",
+            branch
+        );
+    }
+}
+
+/// `--since-first-ai` found no AI commit to start from, so nothing was cut.
+fn warn_if_no_ai_era(cli: &Cli, git_stats: &git::parser::GitStats) {
+    if cli.since_first_ai && git_stats.ai_era.is_none() && git_stats.total_commits > 0 {
+        eprintln!("Warning: no AI-authored commits; --since-first-ai reports the whole history");
+    }
+}
+
+/// Report an unknown --branch with the branches that do exist, then exit.
+fn exit_branch_not_found(e: &(dyn std::error::Error + 'static)) -> ! {
+    explain_branch_not_found(e);
+    std::process::exit(1);
+}
+
+/// Print a `BranchNotFound` error with the branches that do exist.
+fn explain_branch_not_found(e: &(dyn std::error::Error + 'static)) {
+    eprintln!("Error: {}", e);
+    if let Some(not_found) = e.downcast_ref::<git::parser::BranchNotFound>() {
+        if !not_found.available.is_empty() {
+            eprintln!("Available branches:");
+            for name in &not_found.available {
+                eprintln!("  {}", name);
+            }
+        }
+    }
 }
 
 /// Clone a remote GitHub repo and analyze it.
 fn run_remote(cli: &Cli, user: &str, repo: &str) {
     eprintln!("Cloning {}/{}...", user, repo);
-    let tmp_path = match scanner::remote::clone_for_analysis(user, repo) {
-        Ok(p) => p,
+    let options = scanner::remote::CloneOptions {
+        branch: cli.branch.as_deref(),
+        token: cli.token.as_deref().filter(|t| !t.is_empty()),
+        no_cache: cli.no_cache,
+        refresh: cli.refresh,
+    };
+    let cloned = match scanner::remote::clone_for_analysis(user, repo, &options) {
+        Ok(c) => c,
         Err(e) => {
             eprintln!("Error cloning repo: {}", e);
             std::process::exit(1);
@@ -133,26 +905,37 @@ fn run_remote(cli: &Cli, user: &str, repo: &str) {
     let repo_name = format!("{}/{}", user, repo);
 
     // Run the same analysis pipeline as single-repo
-    let since = git::parser::parse_since(&cli.since);
-    let git_stats = match git::parser::analyze_repo(&tmp_path, since) {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("Error analyzing repo: {}", e);
-            scanner::remote::cleanup(&tmp_path);
-            std::process::exit(1);
-        }
-    };
-    let project_stats = project::analyze_project_with_ai_ratio(&tmp_path, git_stats.ai_ratio);
-    let vibe_score = score::calculator::calculate(&git_stats, &project_stats);
+    let git_stats =
+        match git::parser::analyze_repo(&cloned.path, &analyze_options(cli, &cloned.path)) {
+            Ok(s) => s,
+            Err(e) if e.is::<git::parser::BranchNotFound>() => {
+                cloned.cleanup();
+                exit_branch_not_found(&*e)
+            }
+            Err(e) if e.is::<git::parser::NoCommits>() && json_output(cli) => {
+                empty_repo_stats(cli, Path::new(&repo_name)).unwrap_or_default()
+            }
+            Err(e) => {
+                eprintln!("Error analyzing repo: {}", e);
+                cloned.cleanup();
+                std::process::exit(1);
+            }
+        };
+    warn_if_commits_capped(&git_stats);
+    note_head_fallback(&git_stats);
+    warn_if_no_ai_era(cli, &git_stats);
+    let project_stats = project::analyze_project(&cloned.path, &git_stats, !cli.no_cache);
+    let vibe_score = score::calculator::calculate(&git_stats, &project_stats, score_options(cli));
 
     // Output + export
     output_report(cli, &git_stats, &project_stats, &vibe_score, &repo_name);
 
-    // Cleanup temp dir
-    scanner::remote::cleanup(&tmp_path);
+    // Cleanup temp dir (cached clones are kept)
+    cloned.cleanup();
 }
 
-/// Common output logic: terminal/JSON rendering + SVG export.
+/// Common output logic: every `--format` output in order, then sharing and
+/// the CI gates.
 fn output_report(
     cli: &Cli,
     git_stats: &git::parser::GitStats,
@@ -160,115 +943,460 @@ fn output_report(
     vibe_score: &score::calculator::VibeScore,
     repo_name: &str,
 ) {
-    if cli.json {
-        let languages: std::collections::HashMap<&String, &usize> =
-            project_stats.languages.languages.iter().collect();
+    let history = record_history(cli, git_stats, vibe_score, repo_name);
 
-        let ai_tools: Vec<serde_json::Value> = git_stats
-            .ai_tools
-            .iter()
-            .map(|(tool, count)| {
-                serde_json::json!({
-                    "tool": tool.to_string(),
-                    "commits": count,
-                })
-            })
-            .collect();
+    for format in &cli.format {
+        match format {
+            render::Format::Terminal => {
+                render_terminal(
+                    cli,
+                    git_stats,
+                    project_stats,
+                    vibe_score,
+                    repo_name,
+                    &history,
+                );
+            }
+            render::Format::Json => write_json(
+                cli,
+                &report_json(cli, git_stats, project_stats, vibe_score, repo_name),
+            ),
+            render::Format::Svg(path) => {
+                let svg = render::svg::render_svg(
+                    git_stats,
+                    project_stats,
+                    vibe_score,
+                    repo_name,
+                    svg_theme(cli),
+                );
+                write_export("SVG", path, svg.as_bytes());
+            }
+            render::Format::Markdown(path) => {
+                let md = render::markdown::render_markdown(
+                    git_stats,
+                    project_stats,
+                    vibe_score,
+                    repo_name,
+                );
+                write_export("Markdown", path, md.as_bytes());
+            }
+            render::Format::Csv(path) => {
+                let csv = render::csv::render_csv(git_stats, project_stats, vibe_score, repo_name);
+                write_export("CSV", path, csv.as_bytes());
+            }
+        }
+    }
 
-        let score_breakdown: Vec<serde_json::Value> = vibe_score
-            .breakdown
-            .iter()
-            .map(|f| {
-                serde_json::json!({
-                    "label": f.label,
-                    "points": f.points,
-                })
-            })
-            .collect();
+    // ── PNG export (the rasterized SVG) ──
+    #[cfg(feature = "png")]
+    if let Some(png_path) = &cli.png {
+        let svg = render::svg::render_svg(
+            git_stats,
+            project_stats,
+            vibe_score,
+            repo_name,
+            svg_theme(cli),
+        );
+        let png = render::png::svg_to_png(&svg, cli.png_scale).unwrap_or_else(|e| {
+            eprintln!("Error rendering PNG: {}", e);
+            std::process::exit(1);
+        });
+        write_export("PNG", png_path, &png);
+    }
 
-        let daily_commits: Vec<serde_json::Value> =
-            git::timeline::build_daily_timeline(&git_stats.commits)
-                .iter()
-                .map(|d| {
-                    serde_json::json!({
-                        "date": d.date,
-                        "total": d.total_commits,
-                        "ai": d.ai_commits,
-                    })
-                })
-                .collect();
+    // ── Share to vibereport.dev (default unless --no-share; a report without
+    // git history has nothing to rank on the leaderboard) ──
+    if cli.print_payload {
+        let payload = share_payload(cli, git_stats, project_stats, repo_name);
+        println!("{}", serde_json::to_string_pretty(&payload).unwrap());
+    } else if !cli.no_share && !git_stats.no_git {
+        eprintln!("  Sharing stats to vibereport.dev (use --no-share to disable)");
+        share_report(cli, git_stats, project_stats, repo_name);
+    }
 
-        let output = serde_json::json!({
-            "repo": repo_name,
-            "ai_ratio": vibe_score.ai_ratio,
-            "human_ratio": 1.0 - vibe_score.ai_ratio,
-            "score": vibe_score.points,
-            "vibe_score": vibe_score.points,
-            "grade": vibe_score.grade,
-            "roast": vibe_score.roast,
-            "score_breakdown": score_breakdown,
-            "total_commits": git_stats.total_commits,
-            "ai_commits": git_stats.ai_commits,
-            "human_commits": git_stats.human_commits,
-            "ai_tools": ai_tools,
-            "daily_commits": daily_commits,
-            "deps": {
-                "total": project_stats.deps.total,
-                "manager": project_stats.deps.manager,
-            },
-            "tests": {
-                "has_tests": project_stats.tests.has_tests,
-                "test_files": project_stats.tests.test_files_count,
-                "frameworks": project_stats.tests.frameworks,
-            },
-            "languages": languages,
-            "total_lines": project_stats.languages.total_lines,
-            "security": {
-                "env_in_git": project_stats.security.env_in_git,
-            },
-            "vibe": {
-                "no_linting": project_stats.vibe.no_linting,
-                "no_ci_cd": project_stats.vibe.no_ci_cd,
-                "boomer_ai": project_stats.vibe.boomer_ai,
-                "node_modules_in_git": project_stats.vibe.node_modules_in_git,
-                "no_gitignore": project_stats.vibe.no_gitignore,
-                "no_readme": project_stats.vibe.no_readme,
-                "todo_flood": project_stats.vibe.todo_flood,
-                "todo_count": project_stats.vibe.todo_count,
-                "single_branch": project_stats.vibe.single_branch,
-                "mega_commit": project_stats.vibe.mega_commit,
-            },
-        });
+    // ── CI gates (--fail-on-secrets, --fail-over), once every output is written ──
+    if cli.fail_on_secrets {
+        let findings = project_stats.security.findings();
+        if !findings.is_empty() {
+            eprintln!("Secrets found (--fail-on-secrets):");
+            for finding in findings {
+                eprintln!("  - {}", finding);
+            }
+            std::process::exit(2);
+        }
+    }
+    if let Some(threshold) = cli.fail_over {
+        if cli.quality {
+            let quality = score::calculator::quality(project_stats);
+            if quality.points < threshold {
+                eprintln!(
+                    "Quality score {} is below --fail-over {}",
+                    quality.points, threshold
+                );
+                std::process::exit(1);
+            }
+        } else if vibe_score.points > threshold {
+            eprintln!(
+                "Vibe score {} exceeds --fail-over {}",
+                vibe_score.points, threshold
+            );
+            std::process::exit(1);
+        }
+    }
+}
 
-        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+/// The terminal report: `--history`, `--oneline`, `--quality` or the full
+/// box (with the local percentile), then the `--verbose` commit list.
+fn render_terminal(
+    cli: &Cli,
+    git_stats: &git::parser::GitStats,
+    project_stats: &project::ProjectStats,
+    vibe_score: &score::calculator::VibeScore,
+    repo_name: &str,
+    history: &[history::Entry],
+) {
+    if cli.history {
+        let scans = history::trend(history, repo_name);
+        render::terminal::render_history(repo_name, &scans, &render_options(cli));
+    } else if cli.oneline {
+        // Colors only when printing to a terminal: the line gets pasted into
+        // prompts and commit statuses
+        let options = render::terminal::RenderOptions {
+            color: render_options(cli).color && std::io::stdout().is_terminal(),
+            ..render_options(cli)
+        };
+        render::terminal::render_oneline(project_stats, vibe_score, repo_name, &options);
+    } else if cli.quality {
+        render::terminal::render_quality(
+            git_stats,
+            project_stats,
+            &score::calculator::quality(project_stats),
+            repo_name,
+            &render_options(cli),
+        );
     } else {
-        render::terminal::render_with_name(git_stats, project_stats, vibe_score, repo_name);
+        render::terminal::render_with_name(
+            git_stats,
+            project_stats,
+            vibe_score,
+            repo_name,
+            &render_options(cli),
+        );
+        if let Some(rank) = history::percentile(history, repo_name, vibe_score.points) {
+            eprintln!(
+                "  \u{1f4ca} Scores higher than {:.0}% of your {} other scanned repo{}",
+                rank.higher_than,
+                rank.repos,
+                if rank.repos == 1 { "" } else { "s" }
+            );
+        }
     }
 
-    // ── SVG export ──
-    if let Some(svg_path) = &cli.svg {
-        let svg_content = render::svg::render_svg(git_stats, project_stats, vibe_score, repo_name);
-        std::fs::write(svg_path, &svg_content).unwrap_or_else(|e| {
-            eprintln!("Error writing SVG: {}", e);
-            std::process::exit(1);
-        });
-        eprintln!("SVG saved to {}", svg_path);
+    // ── AI commit list (--verbose), to debug misclassified commits ──
+    if cli.verbose {
+        render::terminal::render_ai_commits(git_stats, &render_options(cli));
     }
+}
 
-    // ── Share to vibereport.dev (default unless --no-share) ──
-    if !cli.no_share {
-        eprintln!("  Sharing stats to vibereport.dev (use --no-share to disable)");
-        share_report(git_stats, project_stats, vibe_score, repo_name);
+/// Write an exported report file, exiting on failure.
+fn write_export(kind: &str, path: &str, content: &[u8]) {
+    std::fs::write(path, content).unwrap_or_else(|e| {
+        eprintln!("Error writing {}: {}", kind, e);
+        std::process::exit(1);
+    });
+    eprintln!("{} saved to {}", kind, path);
+}
+
+/// Append this scan to the local history and return every recorded scan,
+/// this one last. Best effort: an unwritable data dir only costs the
+/// percentile line and `--history`.
+///
+/// Only terminal reports are recorded: `--history` just reads the file, and
+/// `--no-history` or a run without terminal output (`--json`, the VPS
+/// worker, exports-only) leaves it alone.
+fn record_history(
+    cli: &Cli,
+    git_stats: &git::parser::GitStats,
+    vibe_score: &score::calculator::VibeScore,
+    repo_name: &str,
+) -> Vec<history::Entry> {
+    let Some(file) = history::history_file() else {
+        return Vec::new();
+    };
+    let mut entries = history::load(&file);
+    if cli.history || cli.no_history || !cli.format.contains(&render::Format::Terminal) {
+        return entries;
     }
+    let entry = history::Entry {
+        repo: repo_name.to_string(),
+        scanned_at: chrono::Utc::now(),
+        points: vibe_score.points,
+        grade: vibe_score.grade.clone(),
+        ai_ratio: vibe_score.ai_ratio,
+        commits: git_stats.total_commits,
+    };
+    if let Err(e) = history::append(&file, &entry) {
+        eprintln!(
+            "  Couldn't record scan history in {}: {}",
+            file.display(),
+            e
+        );
+    }
+    entries.push(entry);
+    entries
 }
 
-/// Build a ReportPayload from computed stats and upload to vibereport.dev.
-fn share_report(
+/// Print JSON (pretty, or minified with `--json-compact`) to the `--output`
+/// file, or to stdout.
+fn write_json(cli: &Cli, output: &serde_json::Value) {
+    let content = if cli.json_compact {
+        serde_json::to_string(output)
+    } else {
+        serde_json::to_string_pretty(output)
+    }
+    .unwrap();
+    match &cli.output {
+        Some(out_path) => {
+            std::fs::write(out_path, content + "\n").unwrap_or_else(|e| {
+                eprintln!("Error writing JSON: {}", e);
+                std::process::exit(1);
+            });
+            eprintln!("JSON saved to {}", out_path);
+        }
+        None => println!("{}", content),
+    }
+}
+
+/// Version of the `--json` shape, as `schema_version` in every report object
+/// (and the `--compare` / `--scan-all` summary wrappers). Bump it when a field
+/// is removed, renamed or changes meaning; adding fields doesn't need a bump.
+/// The VPS worker refuses reports with a version it doesn't know.
+const JSON_SCHEMA_VERSION: u32 = 4;
+
+/// Report fields read from git history, left out when `no_git` is true
+/// (there's no history to report, and zeros would look like a real repo).
+const GIT_ONLY_FIELDS: &[&str] = &[
+    "first_commit_date",
+    "last_commit_date",
+    "ai_era",
+    "head_fallback",
+    "human_ratio",
+    "ai_line_ratio",
+    "ai_lines_added",
+    "total_lines_added",
+    "total_commits",
+    "ai_commits",
+    "human_commits",
+    "bot_commits",
+    "ai_tools",
+    "daily_commits",
+    "velocity",
+];
+
+/// Build the JSON report (`--json` / `--output`).
+fn report_json(
+    cli: &Cli,
     git_stats: &git::parser::GitStats,
     project_stats: &project::ProjectStats,
     vibe_score: &score::calculator::VibeScore,
     repo_name: &str,
+) -> serde_json::Value {
+    let languages: std::collections::HashMap<&String, &usize> =
+        project_stats.languages.languages.iter().collect();
+    let (top_languages, other_lines) = project_stats.languages.top(cli.top_languages);
+    let top_languages: Vec<serde_json::Value> = top_languages
+        .iter()
+        .map(|(name, lines)| serde_json::json!({ "language": name, "lines": lines }))
+        .collect();
+
+    let ai_tools: Vec<serde_json::Value> = git_stats
+        .ai_tools
+        .iter()
+        .map(|(tool, count)| {
+            serde_json::json!({
+                "tool": tool.to_string(),
+                "commits": count,
+            })
+        })
+        .collect();
+
+    let breakdown: Vec<serde_json::Value> = vibe_score
+        .breakdown
+        .iter()
+        .map(|f| {
+            serde_json::json!({
+                "label": f.label,
+                "points": f.points,
+            })
+        })
+        .collect();
+
+    let quality = score::calculator::quality(project_stats);
+    let quality_breakdown: Vec<serde_json::Value> = quality
+        .breakdown
+        .iter()
+        .map(|f| {
+            serde_json::json!({
+                "label": f.label,
+                "points": f.points,
+            })
+        })
+        .collect();
+
+    let velocity = git::velocity::compute(&git_stats.daily);
+
+    let daily_commits: Vec<serde_json::Value> =
+        git::timeline::build_daily_timeline(&git_stats.daily)
+            .iter()
+            .map(|d| {
+                serde_json::json!({
+                    "date": d.date,
+                    "total": d.total_commits,
+                    "ai": d.ai_commits,
+                })
+            })
+            .collect();
+
+    let mut report = serde_json::json!({
+        "schema_version": JSON_SCHEMA_VERSION,
+        "repo": repo_name,
+        "no_git": git_stats.no_git,
+        "first_commit_date": git_stats.first_commit_date,
+        "last_commit_date": git_stats.last_commit_date,
+        "ai_era": git_stats.ai_era.as_ref().map(|era| serde_json::json!({
+            "since": era.since,
+            "lifetime_commits": era.lifetime_commits,
+            "lifetime_ai_ratio": era.lifetime_ai_ratio,
+        })),
+        "head_fallback": git_stats.head_fallback,
+        "ai_ratio": vibe_score.ai_ratio,
+        "human_ratio": 1.0 - vibe_score.ai_ratio,
+        "ai_line_ratio": git_stats.ai_line_ratio,
+        "ai_lines_added": git_stats.ai_lines_added,
+        "total_lines_added": git_stats.total_lines_added,
+        "score": vibe_score.points,
+        "vibe_score": vibe_score.points,
+        "grade": vibe_score.grade,
+        "roast": vibe_score.roast,
+        "breakdown": breakdown,
+        "quality_score": quality.points,
+        "quality_grade": quality.grade,
+        "quality_roast": quality.roast,
+        "quality_breakdown": quality_breakdown,
+        "total_commits": git_stats.total_commits,
+        "ai_commits": git_stats.ai_commits,
+        "human_commits": git_stats.human_commits,
+        "bot_commits": git_stats.bot_commits,
+        "ai_tools": ai_tools,
+        "daily_commits": daily_commits,
+        "velocity": {
+            "active_days": velocity.active_days,
+            "commits_per_active_day": velocity.commits_per_active_day,
+            "longest_streak_days": velocity.longest_streak,
+            "busiest_weekday": velocity.busiest_weekday.map(git::velocity::weekday_name),
+        },
+        "deps": {
+            "total": project_stats.deps.total,
+            "direct": project_stats.deps.direct,
+            "manager": project_stats.deps.manager,
+        },
+        "monorepo": project_stats.monorepo,
+        "license": project_stats.license,
+        "tests": {
+            "has_tests": project_stats.tests.has_tests,
+            "test_files": project_stats.tests.test_files_count,
+            "frameworks": project_stats.tests.frameworks,
+            "test_ratio": project_stats.tests.test_ratio,
+        },
+        "languages": languages,
+        "top_languages": top_languages,
+        "other_languages_lines": other_lines,
+        "total_lines": project_stats.languages.total_lines,
+        "code_lines": project_stats.languages.code_lines,
+        "config_docs_lines": project_stats.languages.config_docs_lines,
+        "generated_lines": project_stats.languages.generated_lines,
+        "security": {
+            "env_in_git": project_stats.security.env_in_git,
+            "env_in_history": project_stats.security.env_in_history,
+            "env_history_count": project_stats.security.env_history_count,
+            "hardcoded_secrets": project_stats.security.hardcoded_secrets_hints,
+            "secret_files": project_stats.security.secret_files,
+            "private_keys": project_stats.security.private_keys_count,
+        },
+        "vibe": {
+            "no_linting": project_stats.vibe.no_linting,
+            "no_ci_cd": project_stats.vibe.no_ci_cd,
+            "boomer_ai": project_stats.vibe.boomer_ai,
+            "node_modules_in_git": project_stats.vibe.node_modules_in_git,
+            "no_gitignore": project_stats.vibe.no_gitignore,
+            "no_readme": project_stats.vibe.no_readme,
+            "no_license": project_stats.license.is_none(),
+            "todo_flood": project_stats.vibe.todo_flood,
+            "todo_count": project_stats.vibe.todo_count,
+            "single_branch": project_stats.vibe.single_branch,
+            "mega_commit": project_stats.vibe.mega_commit,
+            "low_effort_commits": project_stats.vibe.low_effort_commits,
+            "low_effort_ratio": project_stats.vibe.low_effort_ratio,
+        },
+    });
+    if git_stats.no_git {
+        if let Some(fields) = report.as_object_mut() {
+            fields.retain(|key, _| !GIT_ONLY_FIELDS.contains(&key.as_str()));
+        }
+    }
+    report
+}
+
+/// Upload the report's share payload to vibereport.dev.
+fn share_report(
+    cli: &Cli,
+    git_stats: &git::parser::GitStats,
+    project_stats: &project::ProjectStats,
+    repo_name: &str,
 ) {
+    let payload = share_payload(cli, git_stats, project_stats, repo_name);
+
+    eprintln!("\n  Uploading report...");
+
+    match share::upload::upload_report(&payload) {
+        Ok(resp) => {
+            eprintln!("  \u{1f517} Shared! {}", resp.url);
+            if let (Some(rank), Some(percentile)) = (resp.rank, resp.percentile) {
+                eprintln!(
+                    "  \u{1f3c6} Rank #{} \u{2014} More AI-dependent than {:.0}% of devs",
+                    rank, percentile
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("  Failed to share report: {}", e);
+            eprintln!("  (The report was still rendered locally above.)");
+        }
+    }
+}
+
+/// Build a ReportPayload from computed stats: what `share_report` uploads
+/// and what --print-payload prints.
+///
+/// The leaderboard compares repos, so the payload is scored with the stock
+/// weights, linear AI curve and built-in roasts: `[weights]`, `--ai-curve`
+/// and `--roasts` (which a repo's own `.vibereport.toml` can set) only
+/// change the local report.
+fn share_payload(
+    cli: &Cli,
+    git_stats: &git::parser::GitStats,
+    project_stats: &project::ProjectStats,
+    repo_name: &str,
+) -> share::upload::ReportPayload {
+    let vibe_score = score::calculator::calculate(
+        git_stats,
+        project_stats,
+        &score::calculator::ScoreOptions {
+            seed: cli.seed,
+            ..Default::default()
+        },
+    );
+
     // Determine the most common AI tool, or "Human" if no AI commits
     let ai_tool = git_stats
         .ai_tools
@@ -292,7 +1420,7 @@ fn share_report(
 
     // Build chaos badges from detected patterns
     let mut badges: Vec<&str> = Vec::new();
-    if !project_stats.tests.has_tests {
+    if !project_stats.tests.has_test_files() {
         badges.push("no-tests");
     }
     if project_stats.security.env_in_git {
@@ -301,6 +1429,9 @@ fn share_report(
     if project_stats.security.hardcoded_secrets_hints > 0 {
         badges.push("hardcoded-secrets");
     }
+    if project_stats.security.private_keys_count > 0 {
+        badges.push("private-keys");
+    }
     if project_stats.vibe.no_linting {
         badges.push("no-linting");
     }
@@ -319,6 +1450,9 @@ fn share_report(
     if project_stats.vibe.no_readme {
         badges.push("no-readme");
     }
+    if project_stats.license.is_none() {
+        badges.push("no-license");
+    }
     if project_stats.vibe.todo_flood {
         badges.push("todo-flood");
     }
@@ -328,17 +1462,21 @@ fn share_report(
     if project_stats.vibe.mega_commit {
         badges.push("mega-commit");
     }
+    if project_stats.vibe.low_effort_commits {
+        badges.push("low-effort-commits");
+    }
     let chaos_badges_json = serde_json::to_string(&badges).unwrap_or_else(|_| "[]".into());
 
-    let payload = share::upload::ReportPayload {
+    let mut payload = share::upload::ReportPayload {
         github_username,
         repo_name: short_repo_name,
         ai_ratio: vibe_score.ai_ratio,
         ai_tool,
         score_points: vibe_score.points,
-        score_grade: vibe_score.grade.clone(),
-        roast: vibe_score.roast.clone(),
+        score_grade: vibe_score.grade,
+        roast: vibe_score.roast,
         deps_count: project_stats.deps.total,
+        monorepo: project_stats.monorepo,
         has_tests: project_stats.tests.has_tests,
         total_lines: project_stats.languages.total_lines,
         total_commits: git_stats.total_commits,
@@ -347,80 +1485,288 @@ fn share_report(
         repo_fingerprint: git_stats.repo_fingerprint.clone(),
         chaos_badges: chaos_badges_json,
     };
-
-    eprintln!("\n  Uploading report...");
-
-    match share::upload::upload_report(&payload) {
-        Ok(resp) => {
-            eprintln!("  \u{1f517} Shared! {}", resp.url);
-            if let (Some(rank), Some(percentile)) = (resp.rank, resp.percentile) {
-                eprintln!(
-                    "  \u{1f3c6} Rank #{} \u{2014} More AI-dependent than {:.0}% of devs",
-                    rank, percentile
-                );
-            }
-        }
-        Err(e) => {
-            eprintln!("  Failed to share report: {}", e);
-            eprintln!("  (The report was still rendered locally above.)");
-        }
+    if cli.anonymous {
+        payload.anonymize();
     }
+    payload
 }
 
 /// Scan all git repos under the given directory and produce a multi-repo report.
-fn run_scan_all(path: &Path) {
+fn run_scan_all(cli: &Cli, path: &Path) {
     eprintln!("Discovering git repos in {}...", path.display());
 
-    let repo_paths = scanner::discover::find_git_repos(path, 5);
+    let found = scanner::discover::find_git_repos(path, 5, cli.skip_submodules);
+    let found_count = found.len();
+    let repo_paths =
+        scanner::discover::exclude_repos(path, found, &cli.exclude).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+    let excluded = found_count - repo_paths.len();
 
     if repo_paths.is_empty() {
-        eprintln!("No git repos found under {}", path.display());
+        if excluded > 0 {
+            eprintln!(
+                "All {} repos under {} matched --exclude",
+                excluded,
+                path.display()
+            );
+        } else {
+            eprintln!("No git repos found under {}", path.display());
+        }
         std::process::exit(1);
     }
 
-    eprintln!("Found {} repos. Analyzing...", repo_paths.len());
+    if excluded > 0 {
+        eprintln!(
+            "Found {} repos ({} excluded by --exclude). Analyzing...",
+            repo_paths.len(),
+            excluded
+        );
+    } else {
+        eprintln!("Found {} repos. Analyzing...", repo_paths.len());
+    }
+
+    // --json / --output: one JSON object per line (NDJSON), then a summary line
+    let mut ndjson: Option<Box<dyn std::io::Write>> = match &cli.output {
+        Some(out_path) => match std::fs::File::create(out_path) {
+            Ok(f) => Some(Box::new(f)),
+            Err(e) => {
+                eprintln!("Error writing JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None if json_output(cli) => Some(Box::new(std::io::stdout().lock())),
+        None => None,
+    };
+    let mut emit = |line: serde_json::Value| {
+        if let Some(out) = ndjson.as_mut() {
+            // Flush per line so consumers see each repo as soon as it's done
+            writeln!(out, "{}", line)
+                .and_then(|_| out.flush())
+                .unwrap_or_else(|e| {
+                    eprintln!("Error writing JSON: {}", e);
+                    std::process::exit(1);
+                });
+        }
+    };
 
+    // Repos are independent: workers pull the next index and send results back,
+    // while this thread does all the printing so progress lines never interleave.
+    let jobs = cli
+        .jobs
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, repo_paths.len());
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    let fancy = render_options(cli).color && std::io::stderr().is_terminal();
+    let progress = scanner::progress::ScanProgress::new(repo_paths.len(), fancy);
     let mut reports = Vec::new();
+    let mut too_small = 0;
 
-    for repo_path in &repo_paths {
-        let name = repo_path
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| repo_path.display().to_string());
+    std::thread::scope(|s| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let (next, repo_paths) = (&next, &repo_paths);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(repo_path) = repo_paths.get(i) else {
+                    break;
+                };
+                let result = analyze_scan_repo(cli, repo_path, repo_dir_name(repo_path));
+                if tx.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
 
-        eprint!("  {} ... ", name);
+        // Results arrive in completion order
+        for (i, result) in rx {
+            let repo_path = &repo_paths[i];
+            let report = match result {
+                Ok(r) => r,
+                Err(e) => {
+                    progress.skipped(&repo_dir_name(repo_path), &e);
+                    emit(serde_json::json!({
+                        "error": e,
+                        "path": repo_path.display().to_string(),
+                    }));
+                    continue;
+                }
+            };
 
-        // Analyze git history
-        let git_stats = match git::parser::analyze_repo(repo_path, None) {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("skipped ({})", e);
+            let commits = report.git_stats.total_commits;
+            if commits < cli.min_commits {
+                progress.below_min_commits(&report.name, commits);
+                too_small += 1;
                 continue;
             }
-        };
+            progress.ok(&report.name, commits);
 
-        // Analyze project structure
-        let project_stats = project::analyze_project(repo_path);
+            let mut line = report_json(
+                cli,
+                &report.git_stats,
+                &report.project_stats,
+                &report.score,
+                &report.name,
+            );
+            line["path"] = serde_json::json!(repo_path.display().to_string());
+            emit(line);
 
-        // Calculate vibe score
-        let vibe_score = score::calculator::calculate(&git_stats, &project_stats);
+            reports.push((i, report));
+        }
+    });
+    progress.finish();
+    if too_small > 0 {
+        eprintln!(
+            "Skipped {} repo{} with fewer than {} commits (--min-commits)",
+            too_small,
+            if too_small == 1 { "" } else { "s" },
+            cli.min_commits
+        );
+    }
 
-        eprintln!("OK ({} commits)", git_stats.total_commits);
+    // Back to discovery order so the multi-repo table is deterministic
+    reports.sort_by_key(|(i, _)| *i);
+    let reports = reports.into_iter().map(|(_, r)| r).collect();
+    let multi = scanner::multi_report::aggregate(reports);
 
-        reports.push(scanner::multi_report::RepoReport {
-            path: repo_path.clone(),
-            name,
-            git_stats,
-            project_stats,
-            score: vibe_score,
-        });
+    if json_output(cli) {
+        emit(serde_json::json!({
+            "schema_version": JSON_SCHEMA_VERSION,
+            "summary": {
+                "repos": multi.repos.len(),
+                "failed": repo_paths.len() - multi.repos.len() - too_small,
+                "below_min_commits": too_small,
+                "excluded": excluded,
+                "total_commits": multi.total_commits,
+                "ai_commits": multi.total_ai_commits,
+                "ai_ratio": multi.global_ai_ratio,
+                "total_lines": multi.total_lines,
+                "average_score": multi.average_score,
+                "weighted_score": multi.weighted_score(cli.weight_by),
+                "weighted_by": cli.weight_by.as_str(),
+                "languages": multi.languages,
+                "ai_tools": multi
+                    .ai_tools
+                    .iter()
+                    .map(|(tool, commits)| {
+                        serde_json::json!({ "tool": tool.to_string(), "commits": commits })
+                    })
+                    .collect::<Vec<_>>(),
+            }
+        }));
+        if let Some(out_path) = &cli.output {
+            eprintln!("JSON saved to {}", out_path);
+        }
     }
 
-    if reports.is_empty() {
-        eprintln!("All repos failed to parse.");
+    if multi.repos.is_empty() {
+        if too_small > 0 {
+            eprintln!("No repos with at least {} commits.", cli.min_commits);
+        } else {
+            eprintln!("All repos failed to parse.");
+        }
         std::process::exit(1);
     }
 
-    let multi = scanner::multi_report::aggregate(reports);
-    render::terminal::render_multi(&multi);
+    if !json_output(cli) {
+        render::terminal::render_multi(&multi, &render_options(cli));
+    }
+}
+
+/// Display name of a discovered repo: its directory name.
+fn repo_dir_name(repo_path: &Path) -> String {
+    repo_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo_path.display().to_string())
+}
+
+/// Analyze one repo found by --scan-all. Errors are reported per repo, not fatal.
+fn analyze_scan_repo(
+    cli: &Cli,
+    repo_path: &Path,
+    name: String,
+) -> Result<scanner::multi_report::RepoReport, String> {
+    let rules = load_rules(cli, repo_path).map_err(|e| e.to_string())?;
+    let options = git::parser::AnalyzeOptions {
+        include_merges: cli.include_merges,
+        include_bots: cli.include_bots,
+        all_branches: cli.all_branches,
+        since_first_ai: cli.since_first_ai,
+        low_memory: cli.low_memory,
+        rules,
+        ..Default::default()
+    };
+
+    // Analyze git history
+    let git_stats = git::parser::analyze_repo(repo_path, &options).map_err(|e| e.to_string())?;
+
+    // Analyze project structure
+    let project_stats = project::analyze_project(repo_path, &git_stats, !cli.no_cache);
+
+    // Calculate vibe score
+    let vibe_score = score::calculator::calculate(&git_stats, &project_stats, score_options(cli));
+
+    Ok(scanner::multi_report::RepoReport {
+        path: repo_path.to_path_buf(),
+        name,
+        git_stats,
+        project_stats,
+        score: vibe_score,
+    })
+}
+
+// ━━ Tests ━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touched(path: &str) -> notify::Result<notify::Event> {
+        Ok(notify::Event::new(notify::EventKind::Any).add_path(std::path::PathBuf::from(path)))
+    }
+
+    #[test]
+    fn head_change_waits_for_head_and_swallows_the_burst() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(touched(".git/logs/refs/heads/main")).unwrap();
+        tx.send(touched(".git/logs/HEAD")).unwrap();
+        tx.send(touched(".git/logs/HEAD")).unwrap();
+        tx.send(touched(".git/logs/refs/heads/main")).unwrap();
+
+        assert!(wait_for_head_change(&rx));
+        assert!(
+            rx.try_recv().is_err(),
+            "the debounce drains the rest of the burst"
+        );
+    }
+
+    #[test]
+    fn shared_score_ignores_local_scoring_tweaks() {
+        let git_stats = git::parser::GitStats {
+            total_commits: 10,
+            ai_commits: 5,
+            ..Default::default()
+        };
+        let project_stats = project::ProjectStats::default();
+        let stock = Cli::try_parse_from(["vibereport"]).unwrap();
+        let mut tweaked = Cli::try_parse_from(["vibereport", "--ai-curve", "quadratic"]).unwrap();
+        tweaked.weights = toml::from_str("ai_ratio = 0\nno_readme = 200\n").unwrap();
+
+        let expected = share_payload(&stock, &git_stats, &project_stats, "me/repo");
+        let shared = share_payload(&tweaked, &git_stats, &project_stats, "me/repo");
+        assert_eq!(shared.score_points, expected.score_points);
+        assert_eq!(shared.roast, expected.roast);
+    }
+
+    #[test]
+    fn head_change_stops_when_the_watcher_hangs_up() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(touched(".git/logs/refs/heads/main")).unwrap();
+        drop(tx);
+        assert!(!wait_for_head_change(&rx));
+    }
 }
@@ -0,0 +1,222 @@
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use super::vibe_detect::SKIP_DIRS;
+use super::ProjectStats;
+
+/// Written inside the git dir so project analysis never sees it, and each
+/// linked worktree gets its own.
+const CACHE_FILE: &str = "vibereport-cache.json";
+
+/// Identifies one state of a repo: the HEAD commit plus a fingerprint of the
+/// working tree. Any edit, new or deleted file changes it.
+pub struct CacheKey {
+    file: PathBuf,
+    key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    stats: ProjectStats,
+}
+
+/// Cache key for the repo at `path` (or the `--subtree` it's in), or `None`
+/// when it can't be opened or has no commits yet.
+///
+/// The tree fingerprint hashes the path, size and mtime of every file
+/// outside `SKIP_DIRS` — the same dirs the scans skip. Only metadata is
+/// read, which is what makes a hit much cheaper than rescanning.
+/// The crate version is part of the key, so an upgrade never reuses stats
+/// computed by older detection rules.
+pub fn key(path: &Path) -> Option<CacheKey> {
+    let (repo, subtree) = crate::git::parser::open_subtree(path).ok()?;
+    let head = repo.head_id().ok()?;
+
+    let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update(head.as_bytes());
+    hasher.update(subtree.as_bytes());
+    fingerprint_tree(path, Path::new(""), &mut hasher);
+    let key = hasher.try_finalize().ok()?.to_string();
+
+    Some(CacheKey {
+        file: repo.git_dir().join(CACHE_FILE),
+        key,
+    })
+}
+
+/// Feed every entry below `dir` into `hasher`, in a stable (sorted) order.
+/// Symlinks are hashed as entries, never followed.
+fn fingerprint_tree(root: &Path, dir: &Path, hasher: &mut gix::hash::Hasher) {
+    let Ok(entries) = std::fs::read_dir(root.join(dir)) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let name = entry.file_name();
+        let Ok(meta) = std::fs::symlink_metadata(entry.path()) else {
+            continue;
+        };
+        let rel = dir.join(&name);
+        if meta.is_dir() {
+            if SKIP_DIRS.contains(&name.to_string_lossy().as_ref()) {
+                continue;
+            }
+            hasher.update(format!("d {}\n", rel.display()).as_bytes());
+            fingerprint_tree(root, &rel, hasher);
+        } else {
+            let mtime = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos());
+            hasher.update(format!("f {} {} {}\n", rel.display(), meta.len(), mtime).as_bytes());
+        }
+    }
+}
+
+/// The stats stored for `key`, if the cache file holds that exact state.
+pub fn load(key: &CacheKey) -> Option<ProjectStats> {
+    let raw = std::fs::read_to_string(&key.file).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&raw).ok()?;
+    (entry.key == key.key).then_some(entry.stats)
+}
+
+/// Replace the cached stats. Best effort: a read-only `.git` just means no
+/// cache. Written to a temp file first so a concurrent run never reads a
+/// half-written entry.
+pub fn store(key: &CacheKey, stats: ProjectStats) -> ProjectStats {
+    let entry = CacheEntry {
+        key: key.key.clone(),
+        stats,
+    };
+    if let Ok(json) = serde_json::to_string(&entry) {
+        let tmp = key.file.with_extension("json.tmp");
+        if std::fs::write(&tmp, json).is_ok() && std::fs::rename(&tmp, &key.file).is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+    }
+    entry.stats
+}
+
+// ━━ Tests ━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parser::GitStats;
+    use crate::project::analyze_project;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commit(repo: &gix::Repository, parents: &[gix::ObjectId]) -> gix::ObjectId {
+        let sig = gix::actor::SignatureRef {
+            name: "dev".into(),
+            email: "dev@example.com".into(),
+            time: "1735689600 +0000",
+        };
+        let tree = gix::ObjectId::empty_tree(repo.object_hash());
+        repo.commit_as(sig, sig, "HEAD", "wip", tree, parents.iter().copied())
+            .unwrap()
+            .detach()
+    }
+
+    fn repo_with_commit() -> (TempDir, gix::Repository, gix::ObjectId) {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let head = commit(&repo, &[]);
+        fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        (dir, repo, head)
+    }
+
+    #[test]
+    fn no_key_without_commits() {
+        let dir = TempDir::new().unwrap();
+        gix::init(dir.path()).unwrap();
+        assert!(key(dir.path()).is_none());
+        assert!(key(&dir.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn key_is_stable_for_an_unchanged_repo() {
+        let (dir, _repo, _) = repo_with_commit();
+        let a = key(dir.path()).unwrap();
+        let b = key(dir.path()).unwrap();
+        assert_eq!(a.key, b.key);
+        assert_eq!(a.file, dir.path().join(".git").join(CACHE_FILE));
+    }
+
+    #[test]
+    fn key_changes_with_head_and_tree() {
+        let (dir, repo, head) = repo_with_commit();
+        let before = key(dir.path()).unwrap().key;
+
+        fs::write(dir.path().join("main.rs"), "fn main() { todo!() }\n").unwrap();
+        let edited = key(dir.path()).unwrap().key;
+        assert_ne!(before, edited);
+
+        fs::write(dir.path().join(".env"), "KEY=1\n").unwrap();
+        let added = key(dir.path()).unwrap().key;
+        assert_ne!(edited, added);
+
+        commit(&repo, &[head]);
+        assert_ne!(added, key(dir.path()).unwrap().key);
+    }
+
+    #[test]
+    fn skipped_dirs_do_not_change_the_key() {
+        let (dir, _repo, _) = repo_with_commit();
+        let before = key(dir.path()).unwrap().key;
+        fs::create_dir_all(dir.path().join("node_modules/left-pad")).unwrap();
+        fs::write(dir.path().join("node_modules/left-pad/index.js"), "x").unwrap();
+        assert_eq!(before, key(dir.path()).unwrap().key);
+    }
+
+    #[test]
+    fn load_only_matches_the_stored_key() {
+        let (dir, repo, head) = repo_with_commit();
+        let cache_key = key(dir.path()).unwrap();
+        assert!(load(&cache_key).is_none());
+
+        let mut stats = analyze_project(dir.path(), &GitStats::default(), false);
+        stats.vibe.todo_count = 42;
+        store(&cache_key, stats);
+        assert_eq!(load(&cache_key).unwrap().vibe.todo_count, 42);
+
+        commit(&repo, &[head]);
+        assert!(load(&key(dir.path()).unwrap()).is_none());
+    }
+
+    #[test]
+    fn analyze_project_uses_and_bypasses_the_cache() {
+        let (dir, _repo, _) = repo_with_commit();
+        let git = GitStats::default();
+
+        let fresh = analyze_project(dir.path(), &git, true);
+        assert_eq!(fresh.languages.total_lines, 1);
+
+        // Plant a recognisable entry for the current state
+        let cache_key = key(dir.path()).unwrap();
+        let mut planted = load(&cache_key).unwrap();
+        planted.languages.total_lines = 999;
+        planted.vibe.boomer_ai = false;
+        store(&cache_key, planted);
+
+        let ai = GitStats {
+            ai_ratio: 0.5,
+            ..Default::default()
+        };
+        let hit = analyze_project(dir.path(), &ai, true);
+        assert_eq!(hit.languages.total_lines, 999);
+        // History-based checks are recomputed, not read from the cache
+        assert!(hit.vibe.boomer_ai);
+
+        let bypassed = analyze_project(dir.path(), &git, false);
+        assert_eq!(bypassed.languages.total_lines, 1);
+    }
+}
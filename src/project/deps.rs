@@ -1,33 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::Path;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DepsInfo {
+    /// Every package the project pulls in: a lockfile's resolved packages
+    /// (transitive ones included) when there is one, else `direct`.
     pub total: usize,
+    /// Dependencies the project declares itself (manifest entries, go.mod
+    /// requires not marked `// indirect`). Shown next to `total`, which is
+    /// the count the deps-bloat factors judge.
+    pub direct: usize,
     pub manager: String,
 }
 
-/// Count dependencies by looking for package.json, Cargo.toml, requirements.txt, etc.
+impl DepsInfo {
+    /// Counts from a manifest alone, where every dependency is a direct one.
+    fn declared(count: usize, manager: &str) -> Self {
+        Self {
+            total: count,
+            direct: count,
+            manager: manager.to_string(),
+        }
+    }
+
+    /// "12", or "12 direct, 840 resolved" when a lockfile adds transitive ones.
+    pub fn counts(&self) -> String {
+        if self.total > self.direct {
+            format!("{} direct, {} resolved", self.direct, self.total)
+        } else {
+            self.direct.to_string()
+        }
+    }
+}
+
+/// Workspace tool configs (Nx, Turborepo, Lerna, pnpm) that mark a monorepo.
+const MONOREPO_MARKERS: &[&str] = &["nx.json", "turbo.json", "lerna.json", "pnpm-workspace.yaml"];
+
+/// Whether the repo root configures a monorepo tool. Its one lockfile holds
+/// every package's dependencies, so a high count is expected there.
+pub fn is_monorepo(path: &Path) -> bool {
+    MONOREPO_MARKERS.iter().any(|m| path.join(m).is_file())
+}
+
+/// Count dependencies by looking for package.json, Cargo.toml, requirements.txt,
+/// pyproject.toml, go.mod, Gemfile, etc.
 pub fn count_deps(path: &Path) -> DepsInfo {
     // Try package.json (npm/yarn/pnpm)
     let pkg_json = path.join("package.json");
     if pkg_json.exists() {
-        if let Ok(content) = std::fs::read_to_string(&pkg_json) {
-            if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&content) {
-                let deps = parsed
-                    .get("dependencies")
-                    .and_then(|d| d.as_object())
-                    .map(|d| d.len())
-                    .unwrap_or(0);
-                let dev_deps = parsed
-                    .get("devDependencies")
-                    .and_then(|d| d.as_object())
-                    .map(|d| d.len())
-                    .unwrap_or(0);
-                return DepsInfo {
-                    total: deps + dev_deps,
-                    manager: "npm".to_string(),
-                };
-            }
+        let declared = count_package_json_deps(&pkg_json);
+        // A lockfile lists every resolved package, transitive ones included
+        if let Some((total, manager)) = count_js_lockfile(path) {
+            return DepsInfo {
+                total,
+                direct: declared.unwrap_or(total),
+                manager: manager.to_string(),
+            };
+        }
+        if let Some(count) = declared {
+            return DepsInfo::declared(count, "npm");
         }
     }
 
@@ -36,6 +68,9 @@ pub fn count_deps(path: &Path) -> DepsInfo {
     if cargo_toml.exists() {
         if let Ok(content) = std::fs::read_to_string(&cargo_toml) {
             if let Ok(parsed) = content.parse::<toml::Table>() {
+                if let Some(total) = count_cargo_workspace_deps(path, &parsed) {
+                    return DepsInfo::declared(total, "cargo");
+                }
                 let deps = parsed
                     .get("dependencies")
                     .and_then(|d| d.as_table())
@@ -46,10 +81,7 @@ pub fn count_deps(path: &Path) -> DepsInfo {
                     .and_then(|d| d.as_table())
                     .map(|d| d.len())
                     .unwrap_or(0);
-                return DepsInfo {
-                    total: deps + dev_deps,
-                    manager: "cargo".to_string(),
-                };
+                return DepsInfo::declared(deps + dev_deps, "cargo");
             }
         }
     }
@@ -62,16 +94,275 @@ pub fn count_deps(path: &Path) -> DepsInfo {
                 .lines()
                 .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
                 .count();
+            return DepsInfo::declared(count, "pip");
+        }
+    }
+
+    // Try pyproject.toml (Poetry or PEP 621)
+    let pyproject = path.join("pyproject.toml");
+    if pyproject.exists() {
+        if let Ok(content) = std::fs::read_to_string(&pyproject) {
+            if let Ok(parsed) = content.parse::<toml::Table>() {
+                if let Some(info) = count_pyproject_deps(&parsed) {
+                    return info;
+                }
+            }
+        }
+    }
+
+    // Try go.mod (Go modules)
+    let go_mod = path.join("go.mod");
+    if go_mod.exists() {
+        if let Ok(content) = std::fs::read_to_string(&go_mod) {
+            let (total, direct) = count_go_requires(&content);
             return DepsInfo {
-                total: count,
-                manager: "pip".to_string(),
+                total,
+                direct,
+                manager: "go".to_string(),
             };
         }
     }
 
+    // Try Gemfile.lock, then Gemfile (Ruby)
+    if let Ok(content) = std::fs::read_to_string(path.join("Gemfile.lock")) {
+        return DepsInfo {
+            total: count_gemfile_lock_specs(&content),
+            direct: count_gemfile_lock_dependencies(&content),
+            manager: "bundler".to_string(),
+        };
+    }
+    if let Ok(content) = std::fs::read_to_string(path.join("Gemfile")) {
+        let count = content
+            .lines()
+            .filter(|l| l.trim_start().starts_with("gem "))
+            .count();
+        return DepsInfo::declared(count, "bundler");
+    }
+
     DepsInfo::default()
 }
 
+/// `dependencies` + `devDependencies` of a package.json; `None` if it
+/// can't be read or parsed.
+fn count_package_json_deps(pkg_json: &Path) -> Option<usize> {
+    let content = std::fs::read_to_string(pkg_json).ok()?;
+    let parsed = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    let section_len = |key: &str| {
+        parsed
+            .get(key)
+            .and_then(|d| d.as_object())
+            .map_or(0, |d| d.len())
+    };
+    Some(section_len("dependencies") + section_len("devDependencies"))
+}
+
+/// For a `[workspace]` root, count the distinct dependency names across the
+/// root package and every member's `Cargo.toml`. Supports `dir/*` member globs.
+/// `None` if the manifest has no `[workspace] members`.
+fn count_cargo_workspace_deps(root: &Path, manifest: &toml::Table) -> Option<usize> {
+    let members = manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())?;
+
+    let mut names: HashSet<String> = HashSet::new();
+    let mut add_deps = |manifest: &toml::Table| {
+        for section in ["dependencies", "dev-dependencies"] {
+            if let Some(deps) = manifest.get(section).and_then(|d| d.as_table()) {
+                names.extend(deps.keys().cloned());
+            }
+        }
+    };
+    add_deps(manifest);
+
+    let mut member_dirs = Vec::new();
+    for member in members.iter().filter_map(|m| m.as_str()) {
+        match member.strip_suffix("/*") {
+            Some(parent) => {
+                if let Ok(entries) = std::fs::read_dir(root.join(parent)) {
+                    member_dirs.extend(entries.flatten().map(|e| e.path()));
+                }
+            }
+            None if member != "." => member_dirs.push(root.join(member)),
+            None => {}
+        }
+    }
+    for dir in member_dirs {
+        let parsed = std::fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| c.parse::<toml::Table>().ok());
+        if let Some(parsed) = parsed {
+            add_deps(&parsed);
+        }
+    }
+
+    Some(names.len())
+}
+
+/// Poetry `[tool.poetry.*dependencies]` (minus the `python` pin), else
+/// PEP 621 `[project] dependencies`. `None` if neither is declared.
+fn count_pyproject_deps(parsed: &toml::Table) -> Option<DepsInfo> {
+    let table_len = |t: Option<&toml::Value>| {
+        t.and_then(|d| d.as_table())
+            .map(|d| d.keys().filter(|k| *k != "python").count())
+    };
+
+    if let Some(poetry) = parsed
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.as_table())
+    {
+        let main = table_len(poetry.get("dependencies"));
+        let dev = table_len(poetry.get("dev-dependencies"));
+        let groups: usize = poetry
+            .get("group")
+            .and_then(|g| g.as_table())
+            .map(|groups| {
+                groups
+                    .values()
+                    .filter_map(|g| table_len(g.get("dependencies")))
+                    .sum()
+            })
+            .unwrap_or(0);
+        if main.is_some() || dev.is_some() || groups > 0 {
+            return Some(DepsInfo::declared(
+                main.unwrap_or(0) + dev.unwrap_or(0) + groups,
+                "poetry",
+            ));
+        }
+    }
+
+    let deps = parsed
+        .get("project")
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_array())?;
+    Some(DepsInfo::declared(deps.len(), "pip"))
+}
+
+/// Count module paths in `require` directives (single-line and block form):
+/// all of them, and those not marked `// indirect`.
+fn count_go_requires(content: &str) -> (usize, usize) {
+    let mut in_block = false;
+    let mut requires = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() && !line.starts_with("//") {
+                requires.push(line);
+            }
+        } else if let Some(rest) = line.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest.starts_with('(') {
+                in_block = true;
+            } else if !rest.is_empty() {
+                requires.push(rest);
+            }
+        }
+    }
+    let direct = requires
+        .iter()
+        .filter(|r| !r.ends_with("// indirect"))
+        .count();
+    (requires.len(), direct)
+}
+
+/// Count gems under the `specs:` lists of Gemfile.lock (4-space-indented entries;
+/// deeper lines are a gem's own dependency constraints).
+fn count_gemfile_lock_specs(content: &str) -> usize {
+    let mut in_specs = false;
+    let mut count = 0;
+    for line in content.lines() {
+        if line.trim_end() == "  specs:" {
+            in_specs = true;
+        } else if !line.starts_with("    ") {
+            in_specs = false;
+        } else if in_specs && !line.starts_with("     ") {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Count the gems a Gemfile.lock's `DEPENDENCIES` section lists: the ones
+/// the Gemfile asks for.
+fn count_gemfile_lock_dependencies(content: &str) -> usize {
+    content
+        .lines()
+        .skip_while(|line| line.trim_end() != "DEPENDENCIES")
+        .skip(1)
+        .take_while(|line| line.starts_with("  "))
+        .filter(|line| !line.starts_with("   "))
+        .count()
+}
+
+/// Count resolved packages in pnpm-lock.yaml, yarn.lock, or package-lock.json
+/// (first found wins), with the package manager it belongs to.
+fn count_js_lockfile(path: &Path) -> Option<(usize, &'static str)> {
+    if let Ok(content) = std::fs::read_to_string(path.join("pnpm-lock.yaml")) {
+        return Some((count_pnpm_packages(&content), "pnpm"));
+    }
+    if let Ok(content) = std::fs::read_to_string(path.join("yarn.lock")) {
+        return Some((count_yarn_packages(&content), "yarn"));
+    }
+    let content = std::fs::read_to_string(path.join("package-lock.json")).ok()?;
+    let parsed = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    Some((count_npm_lock_packages(&parsed), "npm"))
+}
+
+/// pnpm-lock.yaml: one 2-space-indented key per package under `packages:`.
+fn count_pnpm_packages(content: &str) -> usize {
+    let mut in_packages = false;
+    let mut count = 0;
+    for line in content.lines() {
+        if !line.starts_with(' ') && !line.trim().is_empty() {
+            in_packages = line.trim_end() == "packages:";
+            continue;
+        }
+        if in_packages
+            && line.starts_with("  ")
+            && !line.starts_with("   ")
+            && line.trim_end().ends_with(':')
+        {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// yarn.lock (v1 and berry): one unindented `"pkg@range", ...:` key per resolved package.
+fn count_yarn_packages(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|l| {
+            !l.starts_with(' ')
+                && !l.starts_with('#')
+                && !l.starts_with("__metadata")
+                && l.trim_end().ends_with(':')
+        })
+        .count()
+}
+
+/// package-lock.json: keys of `packages` minus the root (v2/v3), else the
+/// nested `dependencies` tree (v1).
+fn count_npm_lock_packages(lock: &serde_json::Value) -> usize {
+    if let Some(packages) = lock.get("packages").and_then(|p| p.as_object()) {
+        return packages.keys().filter(|k| !k.is_empty()).count();
+    }
+
+    fn count_nested(deps: &serde_json::Value) -> usize {
+        deps.as_object()
+            .map(|d| {
+                d.values()
+                    .map(|dep| 1 + dep.get("dependencies").map(count_nested).unwrap_or(0))
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+    lock.get("dependencies").map(count_nested).unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,9 +382,135 @@ mod tests {
         .unwrap();
         let info = count_deps(dir.path());
         assert_eq!(info.total, 3);
+        assert_eq!(info.direct, 3);
+        assert_eq!(info.counts(), "3");
+        assert_eq!(info.manager, "npm");
+    }
+
+    const PACKAGE_JSON: &str = r#"{ "dependencies": { "react": "^18" } }"#;
+
+    #[test]
+    fn counts_pnpm_lock_packages() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), PACKAGE_JSON).unwrap();
+        fs::write(
+            dir.path().join("pnpm-lock.yaml"),
+            r#"lockfileVersion: '9.0'
+
+importers:
+  .:
+    dependencies:
+      react:
+        specifier: ^18
+        version: 18.3.1
+
+packages:
+  js-tokens@4.0.0:
+    resolution: {integrity: sha512-abc}
+
+  loose-envify@1.4.0:
+    resolution: {integrity: sha512-def}
+    hasBin: true
+
+  react@18.3.1:
+    resolution: {integrity: sha512-ghi}
+
+snapshots:
+  react@18.3.1:
+    dependencies:
+      loose-envify: 1.4.0
+"#,
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 3);
+        assert_eq!(info.direct, 1);
+        assert_eq!(info.counts(), "1 direct, 3 resolved");
+        assert_eq!(info.manager, "pnpm");
+    }
+
+    #[test]
+    fn counts_yarn_lock_packages() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), PACKAGE_JSON).unwrap();
+        fs::write(
+            dir.path().join("yarn.lock"),
+            r#"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+# yarn lockfile v1
+
+
+"js-tokens@^3.0.0 || ^4.0.0":
+  version "4.0.0"
+
+loose-envify@^1.1.0:
+  version "1.4.0"
+  dependencies:
+    js-tokens "^3.0.0 || ^4.0.0"
+
+react@^18, react@^18.2.0:
+  version "18.3.1"
+"#,
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 3);
+        assert_eq!(info.manager, "yarn");
+    }
+
+    #[test]
+    fn counts_package_lock_packages() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), PACKAGE_JSON).unwrap();
+        fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": { "dependencies": { "react": "^18" } },
+                "node_modules/js-tokens": { "version": "4.0.0" },
+                "node_modules/loose-envify": { "version": "1.4.0" },
+                "node_modules/react": { "version": "18.3.1" }
+            }
+        }"#,
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 3);
         assert_eq!(info.manager, "npm");
     }
 
+    #[test]
+    fn counts_package_lock_v1_nested() {
+        let lock: serde_json::Value = serde_json::from_str(
+            r#"{
+            "lockfileVersion": 1,
+            "dependencies": {
+                "react": { "version": "18.3.1" },
+                "loose-envify": {
+                    "version": "1.4.0",
+                    "dependencies": { "js-tokens": { "version": "4.0.0" } }
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(count_npm_lock_packages(&lock), 3);
+    }
+
+    #[test]
+    fn detects_monorepo_tooling() {
+        let dir = TempDir::new().unwrap();
+        assert!(!is_monorepo(dir.path()));
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        assert!(!is_monorepo(dir.path()));
+
+        for marker in MONOREPO_MARKERS {
+            let dir = TempDir::new().unwrap();
+            fs::write(dir.path().join(marker), "{}").unwrap();
+            assert!(is_monorepo(dir.path()), "{}", marker);
+        }
+    }
+
     #[test]
     fn counts_cargo_deps() {
         let dir = TempDir::new().unwrap();
@@ -118,6 +535,56 @@ tempfile = "3"
         assert_eq!(info.manager, "cargo");
     }
 
+    #[test]
+    fn counts_cargo_workspace_deps() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/core\", \"crates/cli\"]\n",
+        )
+        .unwrap();
+        for (name, deps) in [
+            ("core", "serde = \"1\"\nthiserror = \"1\"\n"),
+            ("cli", "serde = \"1\"\nclap = \"4\"\n"),
+        ] {
+            let member = dir.path().join("crates").join(name);
+            fs::create_dir_all(&member).unwrap();
+            fs::write(
+                member.join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{}\"\n\n[dependencies]\n{}\n[dev-dependencies]\ntempfile = \"3\"\n",
+                    name, deps
+                ),
+            )
+            .unwrap();
+        }
+
+        let info = count_deps(dir.path());
+        // serde, thiserror, clap, tempfile — shared names counted once
+        assert_eq!(info.total, 4);
+        assert_eq!(info.manager, "cargo");
+    }
+
+    #[test]
+    fn counts_cargo_workspace_glob_and_root_package() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\".\", \"crates/*\"]\n\n[package]\nname = \"root\"\n\n[dependencies]\nanyhow = \"1\"\n",
+        )
+        .unwrap();
+        let member = dir.path().join("crates").join("util");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"util\"\n\n[dependencies]\nanyhow = \"1\"\nregex = \"1\"\n",
+        )
+        .unwrap();
+
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 2);
+    }
+
     #[test]
     fn counts_pip_deps() {
         let dir = TempDir::new().unwrap();
@@ -131,6 +598,122 @@ tempfile = "3"
         assert_eq!(info.manager, "pip");
     }
 
+    #[test]
+    fn counts_go_mod_requires() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("go.mod"),
+            r#"module example.com/app
+
+go 1.22
+
+require github.com/spf13/cobra v1.8.0
+
+require (
+	github.com/inconshreveable/mousetrap v1.1.0 // indirect
+	// tooling
+	github.com/spf13/pflag v1.0.5 // indirect
+)
+"#,
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 3);
+        assert_eq!(info.direct, 1);
+        assert_eq!(info.manager, "go");
+    }
+
+    #[test]
+    fn counts_gemfile_lock_specs() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Gemfile"), "gem \"rails\"\n").unwrap();
+        fs::write(
+            dir.path().join("Gemfile.lock"),
+            r#"GEM
+  remote: https://rubygems.org/
+  specs:
+    actionpack (7.1.0)
+      rack (>= 2.2.4)
+    rack (3.0.8)
+    rails (7.1.0)
+      actionpack (= 7.1.0)
+
+PLATFORMS
+  ruby
+
+DEPENDENCIES
+  rails
+"#,
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 3);
+        assert_eq!(info.direct, 1);
+        assert_eq!(info.manager, "bundler");
+    }
+
+    #[test]
+    fn counts_gemfile_without_lock() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Gemfile"),
+            "source \"https://rubygems.org\"\n\ngem \"rails\"\ngem \"pg\"\ngroup :test do\n  gem \"rspec\"\nend\n",
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 3);
+        assert_eq!(info.manager, "bundler");
+    }
+
+    #[test]
+    fn counts_poetry_deps() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            r#"
+[tool.poetry.dependencies]
+python = "^3.11"
+fastapi = "^0.110"
+httpx = "^0.27"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8"
+"#,
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 3);
+        assert_eq!(info.manager, "poetry");
+    }
+
+    #[test]
+    fn counts_pep621_deps() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            r#"
+[project]
+name = "app"
+dependencies = ["fastapi>=0.110", "httpx"]
+"#,
+        )
+        .unwrap();
+        let info = count_deps(dir.path());
+        assert_eq!(info.total, 2);
+        assert_eq!(info.manager, "pip");
+    }
+
+    #[test]
+    fn pyproject_without_deps_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[tool.ruff]\nline-length = 100\n",
+        )
+        .unwrap();
+        assert_eq!(count_deps(dir.path()).total, 0);
+    }
+
     #[test]
     fn returns_default_for_empty_dir() {
         let dir = TempDir::new().unwrap();
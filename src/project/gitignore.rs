@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+/// Matcher for the repo's `.gitignore` files, used by the source-tree walks
+/// (language counts, TODO counting, security scanning) on top of their
+/// hardcoded skip lists. Without a `.gitignore`, nothing extra is skipped.
+/// `--scan-all` discovery reuses it for `.vibereportignore` via `from_file`.
+pub struct GitignoreFilter {
+    /// The directory being walked; paths are matched relative to it.
+    base: PathBuf,
+    /// Root-most first, each with the path from its directory to `base`.
+    matchers: Vec<(Gitignore, PathBuf)>,
+}
+
+impl GitignoreFilter {
+    /// Load the `.gitignore` files that apply to a walk of `path`: its own,
+    /// and for a `--subtree` path the repo root's and every one in between.
+    /// A missing or unparsable file is skipped.
+    pub fn new(path: &Path) -> Self {
+        let rel = path.canonicalize().ok().and_then(|full| {
+            let root = crate::git::parser::workdir_root(path)?;
+            let rel = full.strip_prefix(&root).ok()?.to_path_buf();
+            Some((root, rel))
+        });
+        let Some((root, rel)) = rel else {
+            return Self::from_file(path, ".gitignore");
+        };
+        let parts: Vec<_> = rel.components().collect();
+        let matchers = (0..=parts.len())
+            .filter_map(|depth| {
+                let dir: PathBuf = std::iter::once(root.as_path())
+                    .chain(parts[..depth].iter().map(|c| Path::new(c.as_os_str())))
+                    .collect();
+                let below: PathBuf = parts[depth..].iter().collect();
+                load(&dir, ".gitignore").map(|m| (m, below))
+            })
+            .collect();
+        Self {
+            base: path.to_path_buf(),
+            matchers,
+        }
+    }
+
+    /// Load gitignore-style patterns from `<root>/<name>` only, same
+    /// fallbacks as `new`.
+    pub fn from_file(root: &Path, name: &str) -> Self {
+        Self {
+            base: root.to_path_buf(),
+            matchers: load(root, name)
+                .map(|m| (m, PathBuf::new()))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Whether `path` (under the walked directory) is gitignored, directly or
+    /// via a parent dir. A deeper `.gitignore` overrides the ones above it.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(rel) = path.strip_prefix(&self.base) else {
+            return false;
+        };
+        for (matcher, below) in self.matchers.iter().rev() {
+            match matcher.matched_path_or_any_parents(below.join(rel), is_dir) {
+                Match::None => continue,
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+            }
+        }
+        false
+    }
+}
+
+/// Matcher for `<dir>/<name>`, `None` when the file is missing or unusable.
+fn load(dir: &Path, name: &str) -> Option<Gitignore> {
+    let file = dir.join(name);
+    if !file.is_file() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    // Bad lines are skipped by the builder; only an unreadable file errors
+    let _ = builder.add(&file);
+    builder.build().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn no_gitignore_ignores_nothing() {
+        let dir = TempDir::new().unwrap();
+        let filter = GitignoreFilter::new(dir.path());
+        assert!(!filter.is_ignored(&dir.path().join("out"), true));
+    }
+
+    #[test]
+    fn matches_dirs_globs_and_negations() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(".gitignore"),
+            "# build output\nout/\n*.min.js\n.env*\n!.env.example\n",
+        )
+        .unwrap();
+        let filter = GitignoreFilter::new(dir.path());
+
+        assert!(filter.is_ignored(&dir.path().join("out"), true));
+        assert!(filter.is_ignored(&dir.path().join("out/bundle.js"), false));
+        assert!(filter.is_ignored(&dir.path().join("src/app.min.js"), false));
+        assert!(filter.is_ignored(&dir.path().join(".env.local"), false));
+        assert!(!filter.is_ignored(&dir.path().join(".env.example"), false));
+        assert!(!filter.is_ignored(&dir.path().join("src/app.js"), false));
+        // "out/" only matches directories
+        assert!(!filter.is_ignored(&dir.path().join("out"), false));
+    }
+
+    #[test]
+    fn subtree_applies_root_and_nested_gitignores() {
+        let dir = TempDir::new().unwrap();
+        gix::init(dir.path()).unwrap();
+        fs::write(dir.path().join(".gitignore"), "*.log\ndist/\n").unwrap();
+        let pkg = dir.path().join("packages");
+        let web = pkg.join("web");
+        fs::create_dir_all(&web).unwrap();
+        fs::write(pkg.join(".gitignore"), "!keep.log\n").unwrap();
+
+        let filter = GitignoreFilter::new(&web);
+        assert!(filter.is_ignored(&web.join("debug.log"), false));
+        assert!(filter.is_ignored(&web.join("dist"), true));
+        // A deeper .gitignore overrides the root one
+        assert!(!filter.is_ignored(&web.join("keep.log"), false));
+        assert!(!filter.is_ignored(&web.join("src/app.ts"), false));
+        // Outside the walked directory nothing is ignored
+        assert!(!filter.is_ignored(&dir.path().join("debug.log"), false));
+    }
+}
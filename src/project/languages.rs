@@ -1,35 +1,56 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
-#[derive(Debug, Default)]
+use super::gitignore::GitignoreFilter;
+use super::vibe_detect::{is_regular_dir, is_regular_file};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct LanguageStats {
     /// Map of language name -> lines of code
     pub languages: HashMap<String, usize>,
     pub total_lines: usize,
+    /// Lines that are neither blank nor a single-line comment, in code
+    /// languages only (config/docs files are excluded)
+    pub code_lines: usize,
+    /// Lines in config and docs languages (YAML, TOML, Markdown), part of
+    /// `total_lines` but not `code_lines`
+    pub config_docs_lines: usize,
+    /// Lines in files marked as generated (`@generated`, `DO NOT EDIT`),
+    /// kept out of every other count
+    pub generated_lines: usize,
 }
 
-/// Check if a path is a regular file (not a symlink) to prevent symlink attacks.
-fn is_regular_file(path: &Path) -> bool {
-    std::fs::symlink_metadata(path)
-        .map(|m| m.file_type().is_file())
-        .unwrap_or(false)
+impl LanguageStats {
+    /// The `n` languages with the most lines (ties by name), plus the total
+    /// lines of all the others.
+    pub fn top(&self, n: usize) -> (Vec<(&str, usize)>, usize) {
+        top_languages(&self.languages, n)
+    }
 }
 
-/// Check if a path is a regular directory (not a symlink) to prevent symlink attacks.
-fn is_regular_dir(path: &Path) -> bool {
-    std::fs::symlink_metadata(path)
-        .map(|m| m.file_type().is_dir())
-        .unwrap_or(false)
+/// `LanguageStats::top` for any language -> lines map (e.g. the `--scan-all`
+/// totals in `MultiReport`).
+pub fn top_languages(languages: &HashMap<String, usize>, n: usize) -> (Vec<(&str, usize)>, usize) {
+    let mut langs: Vec<(&str, usize)> = languages
+        .iter()
+        .map(|(name, lines)| (name.as_str(), *lines))
+        .collect();
+    langs.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let other = langs.iter().skip(n).map(|(_, lines)| lines).sum();
+    langs.truncate(n);
+    (langs, other)
 }
 
 /// Count lines of code by language by walking the source tree.
+/// Skips the usual build/vendor dirs plus anything matched by the root `.gitignore`.
 pub fn count_languages(path: &Path) -> LanguageStats {
     let mut stats = LanguageStats::default();
-    walk_dir(path, &mut stats);
+    walk_dir(path, &GitignoreFilter::new(path), &mut stats);
     stats
 }
 
-fn walk_dir(dir: &Path, stats: &mut LanguageStats) {
+fn walk_dir(dir: &Path, gitignore: &GitignoreFilter, stats: &mut LanguageStats) {
     let skip_dirs = [
         "node_modules",
         "target",
@@ -54,20 +75,53 @@ fn walk_dir(dir: &Path, stats: &mut LanguageStats) {
         let name = entry.file_name().to_string_lossy().to_string();
 
         if is_regular_dir(&path) {
-            if !skip_dirs.contains(&name.as_str()) && !name.starts_with('.') {
-                walk_dir(&path, stats);
+            if !skip_dirs.contains(&name.as_str())
+                && !name.starts_with('.')
+                && !gitignore.is_ignored(&path, true)
+            {
+                walk_dir(&path, gitignore, stats);
             }
-        } else if is_regular_file(&path) {
-            if let Some(lang) = detect_language(&name) {
-                let lines = count_lines(&path);
-                *stats.languages.entry(lang).or_insert(0) += lines;
+        } else if is_regular_file(&path) && !gitignore.is_ignored(&path, false) {
+            let lang = detect_language(&name).or_else(|| {
+                // Extensionless scripts: trust the shebang
+                (!name.contains('.'))
+                    .then(|| sniff_shebang(&path))
+                    .flatten()
+            });
+            if let Some(lang) = lang {
+                let (lines, code, generated) = count_lines(&path, comment_prefixes(&lang, &name));
+                if generated {
+                    stats.generated_lines += lines;
+                    continue;
+                }
                 stats.total_lines += lines;
+                if CONFIG_DOCS_LANGUAGES.contains(&lang.as_str()) {
+                    stats.config_docs_lines += lines;
+                } else {
+                    stats.code_lines += code;
+                }
+                *stats.languages.entry(lang).or_insert(0) += lines;
             }
         }
     }
 }
 
+/// Languages counted in `total_lines` but kept out of `code_lines`.
+const CONFIG_DOCS_LANGUAGES: &[&str] = &["YAML", "TOML", "Markdown"];
+
 fn detect_language(filename: &str) -> Option<String> {
+    // Well-known build files have no (meaningful) extension
+    match filename {
+        "Makefile" | "makefile" | "GNUmakefile" => return Some("Makefile".to_string()),
+        "CMakeLists.txt" => return Some("CMake".to_string()),
+        "Rakefile" | "Gemfile" => return Some("Ruby".to_string()),
+        // Generated lockfile
+        "pnpm-lock.yaml" => return None,
+        _ if filename.starts_with("Dockerfile") || filename.starts_with("Containerfile") => {
+            return Some("Dockerfile".to_string())
+        }
+        _ => {}
+    }
     let ext = filename.rsplit('.').next()?;
     match ext {
         "ts" | "tsx" => Some("TypeScript".to_string()),
@@ -87,24 +141,109 @@ fn detect_language(filename: &str) -> Option<String> {
         "c" | "h" => Some("C".to_string()),
         "cpp" | "cc" | "hpp" => Some("C++".to_string()),
         "cs" => Some("C#".to_string()),
+        "lua" => Some("Lua".to_string()),
+        "sh" | "bash" | "zsh" => Some("Shell".to_string()),
+        "dockerfile" => Some("Dockerfile".to_string()),
+        "mk" => Some("Makefile".to_string()),
+        "cmake" => Some("CMake".to_string()),
+        "sql" => Some("SQL".to_string()),
+        "yaml" | "yml" => Some("YAML".to_string()),
+        "toml" => Some("TOML".to_string()),
+        "md" | "markdown" => Some("Markdown".to_string()),
         _ => None,
     }
 }
 
+/// Language from a shebang line such as `#!/usr/bin/env python3` or `#!/bin/bash`.
+fn language_from_shebang(line: &str) -> Option<String> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        // Skip env's own flags (`env -S node --flag`)
+        interpreter = words.find(|w| !w.starts_with('-'))?;
+    }
+    // python3.12 -> python
+    let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let lang = match name {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => "Shell",
+        "python" => "Python",
+        "node" | "nodejs" | "bun" => "JavaScript",
+        "deno" | "ts-node" | "tsx" => "TypeScript",
+        "ruby" => "Ruby",
+        "php" => "PHP",
+        "lua" => "Lua",
+        _ => return None,
+    };
+    Some(lang.to_string())
+}
+
+/// Read just the first line of `path` and map its shebang to a language.
+/// Files over the read cap are skipped like in `count_lines`.
+fn sniff_shebang(path: &Path) -> Option<String> {
+    use std::io::Read;
+    if std::fs::metadata(path).ok()?.len() > MAX_FILE_SIZE {
+        return None;
+    }
+    let mut head = [0u8; 128];
+    let n = std::fs::File::open(path).ok()?.read(&mut head).ok()?;
+    let head = String::from_utf8_lossy(&head[..n]);
+    language_from_shebang(head.lines().next()?)
+}
+
+/// Single-line comment markers per language (block comments are not tracked).
+fn comment_prefixes(lang: &str, filename: &str) -> &'static [&'static str] {
+    match lang {
+        "TypeScript" | "JavaScript" | "Rust" | "Go" | "Java" | "Swift" | "Kotlin" | "C" | "C++"
+        | "C#" => &["//"],
+        // Plain CSS only has block comments
+        "CSS" if !filename.ends_with(".css") => &["//"],
+        "PHP" => &["//", "#"],
+        "Python" | "Ruby" | "Shell" | "Makefile" | "Dockerfile" | "CMake" | "YAML" | "TOML" => {
+            &["#"]
+        }
+        "Lua" | "SQL" => &["--"],
+        _ => &[],
+    }
+}
+
 /// Maximum file size to read (1 MB). Files larger than this are skipped
 /// to prevent out-of-memory conditions on huge generated/vendored files.
 const MAX_FILE_SIZE: u64 = 1_048_576;
 
-fn count_lines(path: &Path) -> usize {
+/// Markers that codegen tools (Go, protobuf, ORMs, C#) put in a file header.
+const GENERATED_MARKERS: &[&str] = &["@generated", "DO NOT EDIT", "<auto-generated"];
+
+/// How many leading lines are searched for a `GENERATED_MARKERS` entry.
+const GENERATED_HEADER_LINES: usize = 5;
+
+/// Whether a file's header marks it as generated, e.g. Go's
+/// `// Code generated by protoc-gen-go. DO NOT EDIT.`
+fn is_generated(content: &str) -> bool {
+    content
+        .lines()
+        .take(GENERATED_HEADER_LINES)
+        .any(|line| GENERATED_MARKERS.iter().any(|m| line.contains(m)))
+}
+
+/// Count `(all lines, code lines, generated)` in a file, where code lines
+/// exclude blanks and lines starting with one of `comments`.
+fn count_lines(path: &Path, comments: &[&str]) -> (usize, usize, bool) {
     // Skip files larger than 1 MB to avoid OOM
     if let Ok(meta) = std::fs::metadata(path) {
         if meta.len() > MAX_FILE_SIZE {
-            return 0;
+            return (0, 0, false);
         }
     }
-    std::fs::read_to_string(path)
-        .map(|content| content.lines().count())
-        .unwrap_or(0)
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return (0, 0, false),
+    };
+    let code = content
+        .lines()
+        .map(str::trim_start)
+        .filter(|l| !l.is_empty() && !comments.iter().any(|c| l.starts_with(c)))
+        .count();
+    (content.lines().count(), code, is_generated(&content))
 }
 
 #[cfg(test)]
@@ -113,6 +252,23 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn top_rolls_up_the_rest() {
+        let stats = LanguageStats {
+            languages: [("Rust", 500), ("Go", 100), ("C", 100), ("Lua", 10)]
+                .into_iter()
+                .map(|(name, lines)| (name.to_string(), lines))
+                .collect(),
+            total_lines: 710,
+            code_lines: 710,
+            config_docs_lines: 0,
+            generated_lines: 0,
+        };
+        assert_eq!(stats.top(2), (vec![("Rust", 500), ("C", 100)], 110));
+        assert_eq!(stats.top(10).1, 0);
+        assert_eq!(stats.top(0), (vec![], 710));
+    }
+
     #[test]
     fn counts_rust_lines() {
         let dir = TempDir::new().unwrap();
@@ -134,6 +290,40 @@ mod tests {
         assert_eq!(stats.total_lines, 6);
     }
 
+    #[test]
+    fn generated_files_are_counted_apart() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("main.go"),
+            "package main\n\nfunc main() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("api.pb.go"),
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\n// source: api.proto\n\npackage api\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("schema.ts"),
+            "/**\n * @generated by the ORM, do not modify\n */\nexport type User = {};\n",
+        )
+        .unwrap();
+
+        let stats = count_languages(dir.path());
+        assert_eq!(stats.total_lines, 3);
+        assert_eq!(stats.languages.get("Go"), Some(&3));
+        assert_eq!(stats.languages.get("TypeScript"), None);
+        assert_eq!(stats.generated_lines, 8);
+    }
+
+    #[test]
+    fn generated_marker_only_counts_in_the_header() {
+        let body = "fn main() {}\n".repeat(GENERATED_HEADER_LINES);
+        assert!(!is_generated(&format!("{}// DO NOT EDIT below\n", body)));
+        assert!(is_generated("#!/bin/sh\n# DO NOT EDIT: written by make\n"));
+        assert!(!is_generated("fn main() {}\n"));
+    }
+
     #[test]
     fn counts_multiple_languages() {
         let dir = TempDir::new().unwrap();
@@ -146,6 +336,42 @@ mod tests {
         assert_eq!(stats.total_lines, 3);
     }
 
+    #[test]
+    fn code_lines_exclude_blanks_and_comments() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("main.rs"),
+            "// entry point\nfn main() {\n\n    // greet\n    println!(\"hi\"); // inline stays\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("app.py"),
+            "#!/usr/bin/env python\n# comment\n\n\nprint('hi')\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("init.lua"), "-- config\nlocal x = 1\n").unwrap();
+
+        let stats = count_languages(dir.path());
+        assert_eq!(stats.total_lines, 6 + 5 + 2);
+        assert_eq!(stats.code_lines, 3 + 1 + 1);
+        // Per-language breakdown still reports raw lines
+        assert_eq!(stats.languages.get("Rust"), Some(&6));
+    }
+
+    #[test]
+    fn code_lines_keep_everything_without_comment_syntax() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("index.html"),
+            "<p>\n\n# not a comment\n</p>\n",
+        )
+        .unwrap();
+
+        let stats = count_languages(dir.path());
+        assert_eq!(stats.total_lines, 4);
+        assert_eq!(stats.code_lines, 3);
+    }
+
     #[test]
     fn skips_node_modules() {
         let dir = TempDir::new().unwrap();
@@ -172,6 +398,33 @@ mod tests {
         assert_eq!(stats.total_lines, 1);
     }
 
+    #[test]
+    fn skips_gitignored_paths() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".gitignore"), "out/\n*.min.js\n").unwrap();
+        fs::write(dir.path().join("app.js"), "run();\n").unwrap();
+        fs::write(dir.path().join("app.min.js"), "a;\nb;\nc;\n").unwrap();
+        let out = dir.path().join("out");
+        fs::create_dir_all(&out).unwrap();
+        fs::write(out.join("bundle.js"), "x;\ny;\n").unwrap();
+
+        let stats = count_languages(dir.path());
+        assert_eq!(stats.languages.get("JavaScript"), Some(&1));
+        assert_eq!(stats.total_lines, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_follow_symlink_loops() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("src/loop")).unwrap();
+
+        let stats = count_languages(dir.path());
+        assert_eq!(stats.languages.get("Rust"), Some(&1));
+    }
+
     #[test]
     fn empty_dir_returns_empty_stats() {
         let dir = TempDir::new().unwrap();
@@ -185,7 +438,89 @@ mod tests {
         assert_eq!(detect_language("app.tsx"), Some("TypeScript".to_string()));
         assert_eq!(detect_language("main.py"), Some("Python".to_string()));
         assert_eq!(detect_language("server.go"), Some("Go".to_string()));
-        assert_eq!(detect_language("readme.md"), None);
-        assert_eq!(detect_language("Makefile"), None);
+        assert_eq!(detect_language("readme.md"), Some("Markdown".to_string()));
+        assert_eq!(detect_language("deploy.sh"), Some("Shell".to_string()));
+        assert_eq!(detect_language("schema.sql"), Some("SQL".to_string()));
+        assert_eq!(detect_language("ci.yml"), Some("YAML".to_string()));
+        assert_eq!(detect_language("Cargo.toml"), Some("TOML".to_string()));
+        assert_eq!(detect_language("pnpm-lock.yaml"), None);
+    }
+
+    #[test]
+    fn detects_language_from_special_filenames() {
+        assert_eq!(detect_language("Makefile"), Some("Makefile".to_string()));
+        assert_eq!(
+            detect_language("Dockerfile"),
+            Some("Dockerfile".to_string())
+        );
+        assert_eq!(
+            detect_language("Dockerfile.prod"),
+            Some("Dockerfile".to_string())
+        );
+        assert_eq!(detect_language("CMakeLists.txt"), Some("CMake".to_string()));
+        assert_eq!(detect_language("notes.txt"), None);
+        assert_eq!(detect_language("LICENSE"), None);
+    }
+
+    #[test]
+    fn detects_language_from_shebang() {
+        let shebang = |line: &str| language_from_shebang(line);
+        assert_eq!(shebang("#!/usr/bin/env python3"), Some("Python".into()));
+        assert_eq!(shebang("#!/usr/bin/python3.12 -u"), Some("Python".into()));
+        assert_eq!(shebang("#!/bin/bash"), Some("Shell".into()));
+        assert_eq!(
+            shebang("#!/usr/bin/env -S node --no-warnings"),
+            Some("JavaScript".into())
+        );
+        assert_eq!(shebang("#!/usr/bin/env"), None);
+        assert_eq!(shebang("#!/usr/bin/awk -f"), None);
+        assert_eq!(shebang("import sys"), None);
+    }
+
+    #[test]
+    fn config_and_docs_lines_stay_out_of_code_lines() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.path().join("README.md"), "# App\n\nDocs.\n").unwrap();
+        fs::write(dir.path().join("config.toml"), "# settings\nport = 80\n").unwrap();
+        fs::write(
+            dir.path().join("schema.sql"),
+            "-- users\nCREATE TABLE u (id INT);\n",
+        )
+        .unwrap();
+
+        let stats = count_languages(dir.path());
+        assert_eq!(stats.languages.get("Markdown"), Some(&3));
+        assert_eq!(stats.languages.get("TOML"), Some(&2));
+        assert_eq!(stats.total_lines, 8);
+        assert_eq!(stats.config_docs_lines, 5);
+        // main.rs + the SQL statement
+        assert_eq!(stats.code_lines, 2);
+    }
+
+    #[test]
+    fn counts_extensionless_scripts_by_shebang() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("bin")).unwrap();
+        fs::write(
+            dir.path().join("bin/release"),
+            "#!/usr/bin/env bash
+# cut a release
+set -e
+echo done
+",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("LICENSE"),
+            "MIT License
+",
+        )
+        .unwrap();
+
+        let stats = count_languages(dir.path());
+        assert_eq!(stats.languages.get("Shell"), Some(&4));
+        assert_eq!(stats.languages.len(), 1);
+        assert_eq!(stats.code_lines, 2);
     }
 }
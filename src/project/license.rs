@@ -0,0 +1,168 @@
+use std::io::Read;
+use std::path::Path;
+
+use super::vibe_detect::is_regular_file;
+
+/// License files looked for at the repo root, in order. Dual-licensed repos
+/// (Rust's `LICENSE-MIT` + `LICENSE-APACHE`) list one license per file.
+const LICENSE_FILES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENCE",
+    "LICENCE.md",
+    "LICENCE.txt",
+    "LICENSE-MIT",
+    "LICENSE-APACHE",
+    "COPYING",
+    "COPYING.md",
+    "COPYING.txt",
+    "UNLICENSE",
+];
+
+/// Only the start of a license file is read: every marker below sits in
+/// the title or the first paragraph.
+const MAX_READ: usize = 8 * 1024;
+
+/// SPDX identifier for a license text, most specific first (the LGPL and
+/// AGPL texts mention the GPL, BSD-3 contains all of BSD-2).
+fn spdx_id(text: &str) -> &'static str {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let upper = text.to_uppercase();
+    let has = |marker: &str| upper.contains(marker);
+
+    if has("GNU AFFERO GENERAL PUBLIC LICENSE") {
+        "AGPL-3.0"
+    } else if has("GNU LESSER GENERAL PUBLIC LICENSE") {
+        if !has("VERSION 3") && has("VERSION 2.1") {
+            "LGPL-2.1"
+        } else {
+            "LGPL-3.0"
+        }
+    } else if has("GNU GENERAL PUBLIC LICENSE") {
+        // v3 text may refer back to version 2, never the other way round
+        if !has("VERSION 3") && has("VERSION 2") {
+            "GPL-2.0"
+        } else {
+            "GPL-3.0"
+        }
+    } else if has("APACHE LICENSE") && has("VERSION 2.0") {
+        "Apache-2.0"
+    } else if has("MOZILLA PUBLIC LICENSE") && has("2.0") {
+        "MPL-2.0"
+    } else if has("MIT LICENSE") || has("PERMISSION IS HEREBY GRANTED, FREE OF CHARGE") {
+        "MIT"
+    } else if has("ISC LICENSE") || has("PERMISSION TO USE, COPY, MODIFY, AND/OR DISTRIBUTE") {
+        "ISC"
+    } else if has("REDISTRIBUTION AND USE IN SOURCE AND BINARY FORMS") {
+        if has("NEITHER THE NAME") || has("THE NAME OF THE COPYRIGHT HOLDER") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        }
+    } else if has("FREE AND UNENCUMBERED SOFTWARE RELEASED INTO THE PUBLIC DOMAIN") {
+        "Unlicense"
+    } else {
+        "Other"
+    }
+}
+
+/// The repo's license as an SPDX expression (`"MIT"`, `"MIT OR Apache-2.0"`
+/// for dual-licensed repos), `"Other"` for a license file we don't
+/// recognize, or `None` when there is no license file at all.
+pub fn detect_license(path: &Path) -> Option<String> {
+    let mut ids: Vec<&str> = Vec::new();
+    for name in LICENSE_FILES {
+        let file = path.join(name);
+        if !is_regular_file(&file) {
+            continue;
+        }
+        let mut raw = Vec::new();
+        let read =
+            std::fs::File::open(&file).and_then(|f| f.take(MAX_READ as u64).read_to_end(&mut raw));
+        if read.is_err() {
+            continue;
+        }
+        let head = String::from_utf8_lossy(&raw);
+        let id = spdx_id(&head);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    // An unrecognized file next to a known one adds nothing
+    if ids.len() > 1 {
+        ids.retain(|id| *id != "Other");
+    }
+    (!ids.is_empty()).then(|| ids.join(" OR "))
+}
+
+// ━━ Tests ━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn no_license_file() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("README.md"), "# hi").unwrap();
+        assert_eq!(detect_license(dir.path()), None);
+    }
+
+    #[test]
+    fn recognizes_common_licenses() {
+        let cases = [
+            ("MIT License\n\nCopyright (c) 2025 me\n\nPermission is hereby granted, free of charge, to any person", "MIT"),
+            ("                                 Apache License\n                           Version 2.0, January 2004", "Apache-2.0"),
+            ("                    GNU GENERAL PUBLIC LICENSE\n                       Version 3, 29 June 2007", "GPL-3.0"),
+            ("                    GNU GENERAL PUBLIC LICENSE\n                       Version 2, June 1991", "GPL-2.0"),
+            ("                   GNU LESSER GENERAL PUBLIC LICENSE\n                       Version 3, 29 June 2007", "LGPL-3.0"),
+            ("                    GNU AFFERO GENERAL PUBLIC LICENSE\n                       Version 3, 19 November 2007", "AGPL-3.0"),
+            ("Mozilla Public License Version 2.0\n==================================", "MPL-2.0"),
+            ("Redistribution and use in source and binary forms, with or without\nmodification, are permitted. Neither the name of the copyright holder", "BSD-3-Clause"),
+            ("This is free and unencumbered software released into the public domain.", "Unlicense"),
+            ("All rights reserved. Do not copy.", "Other"),
+        ];
+        for (text, id) in cases {
+            assert_eq!(spdx_id(text), id, "{}", text);
+        }
+    }
+
+    #[test]
+    fn reads_any_license_file_name() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("COPYING"),
+            "GNU GENERAL PUBLIC LICENSE\nVersion 3",
+        )
+        .unwrap();
+        assert_eq!(detect_license(dir.path()).as_deref(), Some("GPL-3.0"));
+    }
+
+    #[test]
+    fn dual_licenses_are_joined() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("LICENSE-MIT"), "MIT License").unwrap();
+        fs::write(
+            dir.path().join("LICENSE-APACHE"),
+            "Apache License\nVersion 2.0, January 2004",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_license(dir.path()).as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_license_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("MIT"), "MIT License").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("MIT"), dir.path().join("LICENSE")).unwrap();
+        assert_eq!(detect_license(dir.path()), None);
+    }
+}
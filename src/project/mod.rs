@@ -1,30 +1,72 @@
+pub mod cache;
 pub mod deps;
+pub mod gitignore;
 pub mod languages;
+pub mod license;
 pub mod security;
 pub mod tests_detect;
 pub mod vibe_detect;
 
+use crate::git::parser::GitStats;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ProjectStats {
     pub deps: deps::DepsInfo,
     pub tests: tests_detect::TestsInfo,
     pub languages: languages::LanguageStats,
     pub security: security::SecurityInfo,
     pub vibe: vibe_detect::VibeInfo,
+    /// Nx, Turborepo, Lerna or pnpm workspace config at the root.
+    pub monorepo: bool,
+    /// SPDX id of the root license file(s), `None` without one.
+    pub license: Option<String>,
 }
 
-pub fn analyze_project(path: &Path) -> ProjectStats {
-    analyze_project_with_ai_ratio(path, 0.0)
-}
+/// Analyze the working tree at `path`; `git` feeds the history-based vibe
+/// checks (boomer AI, low-effort commit messages).
+///
+/// With `use_cache`, results are reused from `.git/vibereport-cache.json`
+/// while HEAD and the working tree are unchanged (see `cache::key`). A
+/// `no_git` scan never touches a repo, so it isn't cached either.
+pub fn analyze_project(path: &Path, git: &GitStats, use_cache: bool) -> ProjectStats {
+    let cache_key = if use_cache && !git.no_git {
+        cache::key(path)
+    } else {
+        None
+    };
+    // Repo-level files may sit above a --subtree path
+    let repo_root = if git.no_git {
+        None
+    } else {
+        crate::git::parser::workdir_root(path)
+    };
+    let vibe_options = vibe_detect::VibeOptions {
+        repo_root: repo_root.clone(),
+        ..vibe_detect::VibeOptions::from_git(git)
+    };
+    if let Some(mut stats) = cache_key.as_ref().and_then(cache::load) {
+        stats.vibe.detect_history(path, &vibe_options);
+        return stats;
+    }
 
-pub fn analyze_project_with_ai_ratio(path: &Path, ai_ratio: f64) -> ProjectStats {
-    ProjectStats {
+    let stats = ProjectStats {
         deps: deps::count_deps(path),
         tests: tests_detect::detect_tests(path),
         languages: languages::count_languages(path),
-        security: security::check_security(path),
-        vibe: vibe_detect::detect_vibe(path, ai_ratio),
+        security: if git.no_git {
+            security::check_working_tree(path)
+        } else {
+            security::check_security(path)
+        },
+        vibe: vibe_detect::detect_vibe(path, &vibe_options),
+        monorepo: deps::is_monorepo(path),
+        license: license::detect_license(path)
+            .or_else(|| repo_root.as_deref().and_then(license::detect_license)),
+    };
+    match cache_key {
+        Some(cache_key) => cache::store(&cache_key, stats),
+        None => stats,
     }
 }
@@ -1,10 +1,24 @@
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-#[derive(Debug, Default)]
+use super::gitignore::GitignoreFilter;
+use super::vibe_detect::{is_regular_dir, SKIP_DIRS, SOURCE_EXTENSIONS};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TestsInfo {
     pub has_tests: bool,
     pub test_files_count: usize,
     pub frameworks: Vec<String>,
+    /// Test files per source file (0.0 to 1.0)
+    pub test_ratio: f64,
+}
+
+impl TestsInfo {
+    /// Tests were detected and at least one test file was counted; a test
+    /// framework config on its own doesn't count for scoring.
+    pub fn has_test_files(&self) -> bool {
+        self.has_tests && self.test_files_count > 0
+    }
 }
 
 /// Detect presence of tests by looking for common test directories and config files.
@@ -30,36 +44,139 @@ pub fn detect_tests(path: &Path) -> TestsInfo {
         ("pytest.ini", "pytest"),
         ("pyproject.toml", "pytest"),
         (".mocharc.yml", "Mocha"),
+        ("phpunit.xml", "PHPUnit"),
+        ("phpunit.xml.dist", "PHPUnit"),
     ];
     for (file, framework) in test_configs {
         if path.join(file).exists() {
             info.has_tests = true;
-            if !info.frameworks.contains(&framework.to_string()) {
-                info.frameworks.push(framework.to_string());
-            }
+            add_framework(&mut info, framework);
         }
     }
 
+    // Ruby: .rspec config, or *_spec.rb files under spec/
+    if path.join(".rspec").exists() || has_rspec_specs(&path.join("spec")) {
+        info.has_tests = true;
+        add_framework(&mut info, "RSpec");
+    }
+
+    // Java/Kotlin: Maven/Gradle layout keeps JUnit tests in src/test/java
+    if path.join("src/test/java").is_dir() {
+        add_framework(&mut info, "JUnit");
+    }
+
+    // Go: tests live next to the code as *_test.go
+    let mut go_tests = 0;
+    count_go_test_files(path, &mut go_tests, 0);
+    if go_tests > 0 {
+        info.has_tests = true;
+        info.test_files_count += go_tests;
+        add_framework(&mut info, "go test");
+    }
+
     // For Rust: check if tests/ dir exists or if Cargo.toml present with tests/
     if path.join("Cargo.toml").exists() && path.join("tests").is_dir() {
         info.has_tests = true;
-        if !info.frameworks.contains(&"cargo test".to_string()) {
-            info.frameworks.push("cargo test".to_string());
-        }
+        add_framework(&mut info, "cargo test");
     }
 
     // For Rust: check for inline #[test] or #[cfg(test)] in .rs files
     if path.join("Cargo.toml").exists() && !info.has_tests && has_rust_inline_tests(path) {
         info.has_tests = true;
         info.test_files_count = info.test_files_count.max(count_rs_test_files(path));
-        if !info.frameworks.contains(&"cargo test".to_string()) {
-            info.frameworks.push("cargo test".to_string());
-        }
+        add_framework(&mut info, "cargo test");
     }
 
+    let mut source_files = 0;
+    let gitignore = GitignoreFilter::new(path);
+    count_source_files(path, &gitignore, &mut source_files, 0);
+    info.test_ratio = test_ratio(info.test_files_count, source_files);
+
     info
 }
 
+/// Share of test files among source files, capped at 1.0. Zero without sources.
+fn test_ratio(test_files: usize, source_files: usize) -> f64 {
+    if source_files == 0 {
+        0.0
+    } else {
+        (test_files as f64 / source_files as f64).min(1.0)
+    }
+}
+
+/// Count source files (by extension), skipping vendored and gitignored paths.
+fn count_source_files(path: &Path, gitignore: &GitignoreFilter, count: &mut usize, depth: usize) {
+    if depth > 10 {
+        return;
+    }
+    let entries = match std::fs::read_dir(path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let p = entry.path();
+        let is_dir = is_regular_dir(&p);
+        if gitignore.is_ignored(&p, is_dir) {
+            continue;
+        }
+        if is_dir {
+            let name = entry.file_name();
+            if !SKIP_DIRS.contains(&name.to_string_lossy().as_ref()) {
+                count_source_files(&p, gitignore, count, depth + 1);
+            }
+        } else if p
+            .extension()
+            .is_some_and(|e| SOURCE_EXTENSIONS.contains(&e.to_string_lossy().as_ref()))
+        {
+            *count += 1;
+        }
+    }
+}
+
+fn add_framework(info: &mut TestsInfo, framework: &str) {
+    if !info.frameworks.iter().any(|f| f == framework) {
+        info.frameworks.push(framework.to_string());
+    }
+}
+
+/// Check if spec/ contains any RSpec file (*_spec.rb), searching subdirectories.
+fn has_rspec_specs(path: &Path) -> bool {
+    let entries = match std::fs::read_dir(path) {
+        Ok(e) => e,
+        Err(_) => return false,
+    };
+    entries.flatten().any(|entry| {
+        let p = entry.path();
+        if is_regular_dir(&p) {
+            has_rspec_specs(&p)
+        } else {
+            entry.file_name().to_string_lossy().ends_with("_spec.rb")
+        }
+    })
+}
+
+/// Count *_test.go files, skipping vendored / generated dirs.
+fn count_go_test_files(path: &Path, count: &mut usize, depth: usize) {
+    if depth > 10 {
+        return;
+    }
+    let entries = match std::fs::read_dir(path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let p = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_regular_dir(&p) {
+            if !SKIP_DIRS.contains(&name.as_str()) {
+                count_go_test_files(&p, count, depth + 1);
+            }
+        } else if name.ends_with("_test.go") {
+            *count += 1;
+        }
+    }
+}
+
 /// Check if any .rs file contains #[test] or #[cfg(test)] (scan src/ up to 50 files).
 fn has_rust_inline_tests(path: &Path) -> bool {
     let src_dir = path.join("src");
@@ -84,7 +201,7 @@ fn scan_rs_for_tests(path: &Path, found: &mut bool, depth: usize) {
             return;
         }
         let p = entry.path();
-        if p.is_dir() {
+        if is_regular_dir(&p) {
             scan_rs_for_tests(&p, found, depth + 1);
         } else if p.extension().is_some_and(|e| e == "rs") {
             if let Ok(content) = std::fs::read_to_string(&p) {
@@ -118,7 +235,7 @@ fn count_rs_test_files_recursive(path: &Path, count: &mut usize, depth: usize) {
     };
     for entry in entries.flatten() {
         let p = entry.path();
-        if p.is_dir() {
+        if is_regular_dir(&p) {
             count_rs_test_files_recursive(&p, count, depth + 1);
         } else if p.extension().is_some_and(|e| e == "rs") {
             if let Ok(content) = std::fs::read_to_string(&p) {
@@ -137,7 +254,7 @@ fn count_files_recursive(path: &Path) -> usize {
             let p = entry.path();
             if p.is_file() {
                 count += 1;
-            } else if p.is_dir() {
+            } else if is_regular_dir(&p) {
                 count += count_files_recursive(&p);
             }
         }
@@ -151,6 +268,22 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[cfg(unix)]
+    #[test]
+    fn does_not_follow_symlink_loops() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("tests/unit")).unwrap();
+        fs::write(dir.path().join("tests/unit/a_test.py"), "").unwrap();
+        fs::create_dir_all(dir.path().join("spec")).unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("tests/unit/loop")).unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("spec/loop")).unwrap();
+
+        let info = detect_tests(dir.path());
+        assert!(info.has_tests);
+        // The symlinks are neither walked nor counted
+        assert_eq!(info.test_files_count, 1);
+    }
+
     #[test]
     fn detects_tests_directory() {
         let dir = TempDir::new().unwrap();
@@ -201,6 +334,97 @@ mod tests {
         assert!(info.frameworks.contains(&"cargo test".to_string()));
     }
 
+    #[test]
+    fn detects_rspec() {
+        let dir = TempDir::new().unwrap();
+        let models = dir.path().join("spec").join("models");
+        fs::create_dir_all(&models).unwrap();
+        fs::write(models.join("user_spec.rb"), "describe User do; end").unwrap();
+
+        let info = detect_tests(dir.path());
+        assert!(info.has_tests);
+        assert_eq!(info.test_files_count, 1);
+        assert!(info.frameworks.contains(&"RSpec".to_string()));
+    }
+
+    #[test]
+    fn detects_rspec_config() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".rspec"), "--require spec_helper").unwrap();
+
+        let info = detect_tests(dir.path());
+        assert!(info.has_tests);
+        assert_eq!(info.frameworks, ["RSpec"]);
+    }
+
+    #[test]
+    fn detects_phpunit() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("phpunit.xml"), "<phpunit/>").unwrap();
+
+        let info = detect_tests(dir.path());
+        assert!(info.has_tests);
+        assert_eq!(info.frameworks, ["PHPUnit"]);
+    }
+
+    #[test]
+    fn detects_go_tests_next_to_code() {
+        let dir = TempDir::new().unwrap();
+        let pkg = dir.path().join("internal").join("server");
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(pkg.join("server.go"), "package server").unwrap();
+        fs::write(pkg.join("server_test.go"), "package server").unwrap();
+        fs::write(dir.path().join("main_test.go"), "package main").unwrap();
+        // Vendored tests don't count
+        let vendor = dir.path().join("vendor").join("lib");
+        fs::create_dir_all(&vendor).unwrap();
+        fs::write(vendor.join("lib_test.go"), "package lib").unwrap();
+
+        let info = detect_tests(dir.path());
+        assert!(info.has_tests);
+        assert_eq!(info.test_files_count, 2);
+        assert_eq!(info.frameworks, ["go test"]);
+    }
+
+    #[test]
+    fn detects_junit_layout() {
+        let dir = TempDir::new().unwrap();
+        let java = dir.path().join("src/test/java/com/example");
+        fs::create_dir_all(&java).unwrap();
+        fs::write(java.join("AppTest.java"), "class AppTest {}").unwrap();
+
+        let info = detect_tests(dir.path());
+        assert!(info.has_tests);
+        assert_eq!(info.test_files_count, 1);
+        assert_eq!(info.frameworks, ["JUnit"]);
+    }
+
+    #[test]
+    fn test_ratio_counts_test_files_per_source_file() {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        let tests = dir.path().join("tests");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&tests).unwrap();
+        for name in ["a.py", "b.py", "c.py"] {
+            fs::write(src.join(name), "x = 1").unwrap();
+        }
+        fs::write(tests.join("test_a.py"), "def test_a(): pass").unwrap();
+
+        let info = detect_tests(dir.path());
+        // 1 test file out of 4 source files (the test file is source too)
+        assert!((info.test_ratio - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ratio_edge_cases() {
+        assert_eq!(test_ratio(0, 0), 0.0);
+        assert_eq!(test_ratio(5, 0), 0.0);
+        assert_eq!(test_ratio(0, 10), 0.0);
+        // Fixtures can make test files outnumber sources
+        assert_eq!(test_ratio(12, 10), 1.0);
+    }
+
     #[test]
     fn no_tests_in_empty_dir() {
         let dir = TempDir::new().unwrap();
@@ -208,5 +432,6 @@ mod tests {
         assert!(!info.has_tests);
         assert_eq!(info.test_files_count, 0);
         assert!(info.frameworks.is_empty());
+        assert_eq!(info.test_ratio, 0.0);
     }
 }
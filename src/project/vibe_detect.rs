@@ -1,22 +1,29 @@
-use std::path::Path;
+use crate::git::parser::GitStats;
+use gix::refs::Category;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::gitignore::GitignoreFilter;
 
 /// Check if a path is a regular file (not a symlink) to prevent symlink attacks.
-fn is_regular_file(path: &Path) -> bool {
+pub(crate) fn is_regular_file(path: &Path) -> bool {
     std::fs::symlink_metadata(path)
         .map(|m| m.file_type().is_file())
         .unwrap_or(false)
 }
 
-/// Check if a path is a regular directory (not a symlink) to prevent symlink attacks.
-fn is_regular_dir(path: &Path) -> bool {
+/// Check if a path is a regular directory (not a symlink), so a walk can't
+/// follow a symlink out of the repo or into a loop.
+pub(crate) fn is_regular_dir(path: &Path) -> bool {
     std::fs::symlink_metadata(path)
         .map(|m| m.file_type().is_dir())
         .unwrap_or(false)
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct VibeInfo {
-    /// No .eslintrc*, .prettierrc*, biome.json, deno.json, oxlint
+    /// No .eslintrc*, .prettierrc*, biome.json, deno.json, oxlint, pre-commit or husky hooks
     pub no_linting: bool,
     /// No .github/workflows/, .gitlab-ci.yml, Jenkinsfile, .circleci/
     pub no_ci_cd: bool,
@@ -35,6 +42,10 @@ pub struct VibeInfo {
     pub single_branch: bool,
     /// A single commit contains > 50% of total commits' files changed
     pub mega_commit: bool,
+    /// >= 30% of commit subjects are under 10 chars or filler ("minor changes"), over 5+ commits
+    pub low_effort_commits: bool,
+    /// Share of commits with a low-effort subject (0.0 to 1.0)
+    pub low_effort_ratio: f64,
 }
 
 const LINT_CONFIGS: &[&str] = &[
@@ -63,11 +74,16 @@ const LINT_CONFIGS: &[&str] = &[
     "pylintrc",
     ".pylintrc",
     ".flake8",
-    "pyproject.toml", // pyproject checked for [tool.ruff] later
     ".golangci.yml",
     ".golangci.yaml",
+    // Hooks that run linters/formatters before every commit
+    ".pre-commit-config.yaml",
+    ".husky",
 ];
 
+/// `[tool.*]` tables in pyproject.toml that configure a linter or formatter.
+const PYPROJECT_LINT_TOOLS: &[&str] = &["ruff", "black", "flake8"];
+
 const CI_CONFIGS: &[&str] = &[
     ".github/workflows",
     ".gitlab-ci.yml",
@@ -92,38 +108,103 @@ const AI_CONFIGS: &[&str] = &[
     ".github/copilot-instructions.md",
 ];
 
-pub fn detect_vibe(path: &Path, ai_ratio: f64) -> VibeInfo {
-    let has_lint_config = LINT_CONFIGS.iter().any(|f| path.join(f).exists());
-    let no_linting = !has_lint_config && !has_clippy_in_ci(path);
-    let no_ci_cd = !CI_CONFIGS.iter().any(|f| path.join(f).exists());
-    let boomer_ai = ai_ratio > 0.0 && !AI_CONFIGS.iter().any(|f| path.join(f).exists());
+/// Share of low-effort subjects that flags the repo.
+const LOW_EFFORT_THRESHOLD: f64 = 0.3;
+
+/// Below this many commits there's too little history to judge.
+const LOW_EFFORT_MIN_COMMITS: usize = 5;
+
+/// Inputs to the vibe checks beyond the scanned path. Extend this rather
+/// than `detect_vibe`'s signature when a check needs more from the scan.
+#[derive(Debug, Clone, Default)]
+pub struct VibeOptions {
+    /// Blended AI ratio of the counted commits (boomer AI).
+    pub ai_ratio: f64,
+    /// Counted commits, and how many of them have a low-effort subject.
+    pub total_commits: usize,
+    pub low_effort_commits: usize,
+    /// The scan has no git history (`GitStats::no_git`): skip ref checks.
+    pub no_git: bool,
+    /// Working-tree root of the repo, when it may differ from the scanned
+    /// path (`--subtree`): repo-level files (.gitignore, README, CI, lint
+    /// and AI configs) count wherever they sit, in the subtree or at the root.
+    pub repo_root: Option<PathBuf>,
+}
+
+impl VibeOptions {
+    pub fn from_git(git: &GitStats) -> Self {
+        Self {
+            ai_ratio: git.ai_ratio,
+            total_commits: git.total_commits,
+            low_effort_commits: git.low_effort_commits,
+            no_git: git.no_git,
+            repo_root: None,
+        }
+    }
+
+    /// The scanned path, then the repo root when there is one.
+    fn config_dirs<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a Path> {
+        std::iter::once(path).chain(self.repo_root.as_deref())
+    }
+}
+
+pub fn detect_vibe(path: &Path, options: &VibeOptions) -> VibeInfo {
+    let no_linting = !options.config_dirs(path).any(|dir| {
+        LINT_CONFIGS.iter().any(|f| dir.join(f).exists())
+            || has_pyproject_lint_config(dir)
+            || has_clippy_in_ci(dir)
+    });
+    let no_ci_cd = !options
+        .config_dirs(path)
+        .any(|dir| CI_CONFIGS.iter().any(|f| dir.join(f).exists()));
 
     // node_modules in git (heuristic: if node_modules has content, it's tracked)
     let node_modules_in_git = path.join("node_modules").is_dir()
         && path.join("node_modules").join("package.json").exists();
 
-    let no_gitignore = check_gitignore(path);
+    let no_gitignore = options.config_dirs(path).all(check_gitignore);
 
-    let no_readme = !path.join("README.md").exists()
-        && !path.join("readme.md").exists()
-        && !path.join("README").exists()
-        && !path.join("README.rst").exists();
+    let no_readme = !options.config_dirs(path).any(|dir| {
+        ["README.md", "readme.md", "README", "README.rst"]
+            .iter()
+            .any(|f| dir.join(f).exists())
+    });
 
     let todo_count = count_todos(path);
     let todo_flood = todo_count > 20;
-    let single_branch = check_single_branch(path);
 
-    VibeInfo {
+    let mut vibe = VibeInfo {
         no_linting,
         no_ci_cd,
-        boomer_ai,
         node_modules_in_git,
         no_gitignore,
         no_readme,
         todo_flood,
         todo_count,
-        single_branch,
-        mega_commit: false,
+        ..Default::default()
+    };
+    vibe.detect_history(path, options);
+    vibe
+}
+
+impl VibeInfo {
+    /// (Re)compute the checks that depend on git history and refs rather
+    /// than on the tree at HEAD: boomer AI, single branch and low-effort
+    /// commit messages. Run on every cache hit, since those change with
+    /// `--since`/`--author` and new branches without HEAD moving.
+    pub fn detect_history(&mut self, path: &Path, options: &VibeOptions) {
+        self.boomer_ai = options.ai_ratio > 0.0
+            && !options
+                .config_dirs(path)
+                .any(|dir| AI_CONFIGS.iter().any(|f| dir.join(f).exists()));
+        self.single_branch = !options.no_git && check_single_branch(path);
+        self.low_effort_ratio = if options.total_commits == 0 {
+            0.0
+        } else {
+            options.low_effort_commits as f64 / options.total_commits as f64
+        };
+        self.low_effort_commits = options.total_commits >= LOW_EFFORT_MIN_COMMITS
+            && self.low_effort_ratio >= LOW_EFFORT_THRESHOLD;
     }
 }
 
@@ -145,32 +226,45 @@ fn check_gitignore(path: &Path) -> bool {
     }
 }
 
+/// Generated / vendored dirs never worth scanning (also used by the secrets scan).
+pub(crate) const SKIP_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    ".git",
+    "dist",
+    "build",
+    ".next",
+    "vendor",
+    "__pycache__",
+    ".venv",
+    "venv",
+];
+
+/// Source file extensions scanned for TODOs and hardcoded secrets.
+pub(crate) const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "js", "py", "go", "rb", "java", "tsx", "jsx", "vue", "svelte", "php", "swift",
+    "kt", "c", "cpp", "cs", "h",
+];
+
+/// Count TODO/FIXME/HACK lines across the whole repo. Not capped, so large
+/// monorepos get the real figure; only files over the size limit are skipped.
 fn count_todos(path: &Path) -> usize {
     let mut count = 0;
-    let skip_dirs = [
-        "node_modules",
-        "target",
-        ".git",
-        "dist",
-        "build",
-        ".next",
-        "vendor",
-        "__pycache__",
-        ".venv",
-        "venv",
-    ];
-    count_todos_recursive(path, &skip_dirs, &mut count, 0);
+    let gitignore = GitignoreFilter::new(path);
+    count_todos_recursive(path, SKIP_DIRS, &gitignore, &mut count);
     count
 }
 
 /// Maximum file size to read (1 MB). Files larger than this are skipped
 /// to prevent out-of-memory conditions on huge generated/vendored files.
-const MAX_FILE_SIZE: u64 = 1_048_576;
-
-fn count_todos_recursive(path: &Path, skip_dirs: &[&str], count: &mut usize, depth: usize) {
-    if depth > 10 || *count > 100 {
-        return;
-    } // early exit
+pub(crate) const MAX_FILE_SIZE: u64 = 1_048_576;
+
+fn count_todos_recursive(
+    path: &Path,
+    skip_dirs: &[&str],
+    gitignore: &GitignoreFilter,
+    count: &mut usize,
+) {
     let entries = match std::fs::read_dir(path) {
         Ok(e) => e,
         Err(_) => return,
@@ -179,32 +273,13 @@ fn count_todos_recursive(path: &Path, skip_dirs: &[&str], count: &mut usize, dep
         let p = entry.path();
         let name = entry.file_name().to_string_lossy().to_string();
         if is_regular_dir(&p) {
-            if !skip_dirs.contains(&name.as_str()) {
-                count_todos_recursive(&p, skip_dirs, count, depth + 1);
+            if !skip_dirs.contains(&name.as_str()) && !gitignore.is_ignored(&p, true) {
+                count_todos_recursive(&p, skip_dirs, gitignore, count);
             }
-        } else if is_regular_file(&p) {
+        } else if is_regular_file(&p) && !gitignore.is_ignored(&p, false) {
             if let Some(ext) = p.extension() {
                 let ext = ext.to_string_lossy();
-                if matches!(
-                    ext.as_ref(),
-                    "rs" | "ts"
-                        | "js"
-                        | "py"
-                        | "go"
-                        | "rb"
-                        | "java"
-                        | "tsx"
-                        | "jsx"
-                        | "vue"
-                        | "svelte"
-                        | "php"
-                        | "swift"
-                        | "kt"
-                        | "c"
-                        | "cpp"
-                        | "cs"
-                        | "h"
-                ) {
+                if SOURCE_EXTENSIONS.contains(&ext.as_ref()) {
                     // Skip files larger than 1 MB to avoid OOM
                     if let Ok(meta) = std::fs::metadata(&p) {
                         if meta.len() > MAX_FILE_SIZE {
@@ -212,11 +287,7 @@ fn count_todos_recursive(path: &Path, skip_dirs: &[&str], count: &mut usize, dep
                         }
                     }
                     if let Ok(content) = std::fs::read_to_string(&p) {
-                        for line in content.lines() {
-                            if has_todo_keyword(line) {
-                                *count += 1;
-                            }
-                        }
+                        *count += count_todo_comments(&content, &comment_syntax(&ext));
                     }
                 }
             }
@@ -224,6 +295,86 @@ fn count_todos_recursive(path: &Path, skip_dirs: &[&str], count: &mut usize, dep
     }
 }
 
+/// How comments and strings are written in a source file, so a TODO only
+/// counts inside a comment and never in a string like `"TODO list"`.
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: &'static [(&'static str, &'static str)],
+    /// String delimiters; a comment marker between them is just text.
+    quotes: &'static [char],
+}
+
+const C_BLOCK: &[(&str, &str)] = &[("/*", "*/")];
+
+fn comment_syntax(ext: &str) -> CommentSyntax {
+    let (line, block, quotes): (&[&str], &[(&str, &str)], &[char]) = match ext {
+        // Only `"`: a quote also starts a lifetime (`'a`)
+        "rs" => (&["//"], C_BLOCK, &['"']),
+        "ts" | "tsx" | "js" | "jsx" | "go" => (&["//"], C_BLOCK, &['"', '\'', '`']),
+        "vue" | "svelte" => (&["//"], &[("/*", "*/"), ("<!--", "-->")], &['"', '\'', '`']),
+        "java" | "kt" | "swift" | "c" | "cpp" | "h" | "cs" => (&["//"], C_BLOCK, &['"', '\'']),
+        "php" => (&["//", "#"], C_BLOCK, &['"', '\'']),
+        "py" | "rb" => (&["#"], &[], &['"', '\'']),
+        _ => (&["//", "#", "--"], C_BLOCK, &['"', '\'']),
+    };
+    CommentSyntax {
+        line,
+        block,
+        quotes,
+    }
+}
+
+/// Count lines with a TODO/FIXME/HACK inside a comment. Block comments are
+/// followed across lines; strings are assumed to end on the line they start.
+fn count_todo_comments(content: &str, syntax: &CommentSyntax) -> usize {
+    let mut open_block: Option<&str> = None;
+    let mut count = 0;
+    for line in content.lines() {
+        let mut comment = String::new();
+        let mut quote = None;
+        let mut escaped = false;
+        let mut i = 0;
+        while let Some(c) = line[i..].chars().next() {
+            let rest = &line[i..];
+            if let Some(end) = open_block {
+                if rest.starts_with(end) {
+                    open_block = None;
+                    comment.push(' ');
+                    i += end.len();
+                } else {
+                    comment.push(c);
+                    i += c.len_utf8();
+                }
+                continue;
+            }
+            i += c.len_utf8();
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            } else if syntax.quotes.contains(&c) {
+                quote = Some(c);
+            } else if let Some(marker) = syntax.line.iter().find(|m| rest.starts_with(*m)) {
+                comment.push_str(&rest[marker.len()..]);
+                break;
+            } else if let Some((start, end)) =
+                syntax.block.iter().find(|(s, _)| rest.starts_with(s))
+            {
+                open_block = Some(end);
+                i += start.len() - c.len_utf8();
+            }
+        }
+        if has_todo_keyword(&comment) {
+            count += 1;
+        }
+    }
+    count
+}
+
 /// Check if a line contains TODO, FIXME, or HACK as a standalone word
 /// (not part of an identifier like `todo_flood` or `count_todos`).
 fn has_todo_keyword(line: &str) -> bool {
@@ -256,6 +407,24 @@ fn has_todo_keyword(line: &str) -> bool {
     false
 }
 
+/// Check if pyproject.toml has a `[tool.ruff]`, `[tool.black]` or `[tool.flake8]`
+/// table. A pyproject without one (or that doesn't parse) is just packaging.
+fn has_pyproject_lint_config(path: &Path) -> bool {
+    let file = path.join("pyproject.toml");
+    if !is_regular_file(&file) {
+        return false;
+    }
+    let Ok(content) = std::fs::read_to_string(&file) else {
+        return false;
+    };
+    let Ok(doc) = content.parse::<toml::Table>() else {
+        return false;
+    };
+    doc.get("tool")
+        .and_then(|t| t.as_table())
+        .is_some_and(|tool| PYPROJECT_LINT_TOOLS.iter().any(|k| tool.contains_key(*k)))
+}
+
 /// Check if any CI workflow file mentions clippy (Rust linter).
 fn has_clippy_in_ci(path: &Path) -> bool {
     let workflows_dir = path.join(".github/workflows");
@@ -279,8 +448,18 @@ fn has_clippy_in_ci(path: &Path) -> bool {
     false
 }
 
+/// Whether the repo only ever had one branch. Branches are counted by name
+/// across local heads and remote-tracking refs, so a fresh clone (`main` +
+/// `origin/main` + the symbolic `origin/HEAD`) is one branch. gix reads refs
+/// from the common dir, which covers bare repos and linked worktrees alike;
+/// a `--subtree` path finds its repo by searching upwards.
 fn check_single_branch(path: &Path) -> bool {
-    let repo = match gix::open(path) {
+    // Canonical first, as in `open_subtree`: discovery walks a relative
+    // path's parents lexically
+    let Ok(path) = path.canonicalize() else {
+        return false;
+    };
+    let repo = match gix::discover(&path) {
         Ok(r) => r,
         Err(_) => return false,
     };
@@ -292,38 +471,66 @@ fn check_single_branch(path: &Path) -> bool {
         Ok(r) => r,
         Err(_) => return false,
     };
-    // Count local branches
-    let local_count = refs.local_branches().map(|iter| iter.count()).unwrap_or(0);
-    if local_count > 1 {
-        return false;
-    }
-    // Also check remote branches — clones only create one local branch
-    // but remote-tracking refs reveal the true branch count
-    let refs2 = match repo.references() {
-        Ok(r) => r,
-        Err(_) => return local_count <= 1,
+    let all = match refs.all() {
+        Ok(iter) => iter,
+        Err(_) => return false,
     };
-    let remote_count = refs2
-        .remote_branches()
-        .map(|iter| iter.count())
-        .unwrap_or(0);
-    // If there are remote branches, use those to judge (subtract HEAD-like refs)
-    if remote_count > 1 {
-        return false;
+    let mut branches = HashSet::new();
+    for reference in all.flatten() {
+        // `origin/HEAD` only points at another remote branch
+        if matches!(reference.target(), gix::refs::TargetRef::Symbolic(_)) {
+            continue;
+        }
+        match reference.name().category_and_short_name() {
+            Some((Category::LocalBranch, name)) => {
+                branches.insert(name.to_string());
+            }
+            Some((Category::RemoteBranch, name)) => {
+                let name = name.to_string();
+                let branch = name.split_once('/').map_or(name.as_str(), |(_, b)| b);
+                branches.insert(branch.to_string());
+            }
+            _ => {}
+        }
     }
-    local_count <= 1
+    branches.len() <= 1
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::low_effort::is_low_effort_message;
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn subtree_finds_repo_level_files_at_the_root() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.path().join("README.md"), "# mono").unwrap();
+        fs::write(dir.path().join(".eslintrc.json"), "{}").unwrap();
+        fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
+        let web = dir.path().join("packages/web");
+        fs::create_dir_all(&web).unwrap();
+
+        let scoped = detect_vibe(&web, &VibeOptions::default());
+        assert!(scoped.no_gitignore && scoped.no_readme && scoped.no_linting && scoped.no_ci_cd);
+
+        let options = VibeOptions {
+            repo_root: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let info = detect_vibe(&web, &options);
+        assert!(!info.no_gitignore);
+        assert!(!info.no_readme);
+        assert!(!info.no_linting);
+        assert!(!info.no_ci_cd);
+    }
+
     #[test]
     fn detects_no_linting_in_empty_dir() {
         let dir = TempDir::new().unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(info.no_linting);
     }
 
@@ -331,14 +538,192 @@ mod tests {
     fn detects_eslint_config() {
         let dir = TempDir::new().unwrap();
         fs::write(dir.path().join(".eslintrc.json"), "{}").unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(!info.no_linting);
     }
 
+    #[test]
+    fn pyproject_with_ruff_counts_as_linting() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"app\"\n\n[tool.ruff.lint]\nselect = [\"E\"]\n",
+        )
+        .unwrap();
+        assert!(!detect_vibe(dir.path(), &VibeOptions::default()).no_linting);
+
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[tool.black]\nline-length = 100\n",
+        )
+        .unwrap();
+        assert!(!detect_vibe(dir.path(), &VibeOptions::default()).no_linting);
+    }
+
+    #[test]
+    fn pre_commit_hooks_count_as_linting() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(".pre-commit-config.yaml"),
+            "repos:\n  - repo: https://github.com/astral-sh/ruff-pre-commit\n",
+        )
+        .unwrap();
+        assert!(!detect_vibe(dir.path(), &VibeOptions::default()).no_linting);
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".husky")).unwrap();
+        fs::write(dir.path().join(".husky/pre-commit"), "npx lint-staged\n").unwrap();
+        assert!(!detect_vibe(dir.path(), &VibeOptions::default()).no_linting);
+    }
+
+    #[test]
+    fn pyproject_without_lint_tool_is_not_linting() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"app\"\n\n[tool.pytest.ini_options]\naddopts = \"-q\"\n",
+        )
+        .unwrap();
+        assert!(detect_vibe(dir.path(), &VibeOptions::default()).no_linting);
+
+        fs::write(dir.path().join("pyproject.toml"), "[tool.ruff\nbroken").unwrap();
+        assert!(detect_vibe(dir.path(), &VibeOptions::default()).no_linting);
+    }
+
+    fn commit(repo: &gix::Repository, reference: &str, parents: &[gix::ObjectId]) -> gix::ObjectId {
+        let sig = gix::actor::SignatureRef {
+            name: "dev".into(),
+            email: "dev@example.com".into(),
+            time: "1735689600 +0000",
+        };
+        let tree = gix::ObjectId::empty_tree(repo.object_hash());
+        repo.commit_as(sig, sig, reference, "wip", tree, parents.iter().copied())
+            .unwrap()
+            .detach()
+    }
+
+    #[test]
+    fn single_branch_repo() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        commit(&repo, "HEAD", &[]);
+        assert!(check_single_branch(dir.path()));
+    }
+
+    #[test]
+    fn two_branches_are_not_single_branch() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let root = commit(&repo, "HEAD", &[]);
+        commit(&repo, "refs/heads/feature", &[root]);
+        assert!(!check_single_branch(dir.path()));
+    }
+
+    #[test]
+    fn fresh_clone_tracking_refs_are_one_branch() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+        let root = commit(&repo, "HEAD", &[]);
+        let remotes = dir.path().join(".git/refs/remotes/origin");
+        fs::create_dir_all(&remotes).unwrap();
+        fs::write(remotes.join("main"), format!("{}\n", root)).unwrap();
+        fs::write(remotes.join("HEAD"), "ref: refs/remotes/origin/main\n").unwrap();
+        assert!(check_single_branch(dir.path()));
+
+        // A second branch that only exists on the remote still counts
+        fs::write(remotes.join("feature"), format!("{}\n", root)).unwrap();
+        assert!(!check_single_branch(dir.path()));
+    }
+
+    #[test]
+    fn bare_repo_with_two_branches() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init_bare(dir.path()).unwrap();
+        let root = commit(&repo, "refs/heads/main", &[]);
+        commit(&repo, "refs/heads/feature", &[root]);
+        assert!(!check_single_branch(dir.path()));
+    }
+
+    #[test]
+    fn linked_worktree_sees_the_main_repo_branches() {
+        let dir = TempDir::new().unwrap();
+        let main = dir.path().join("main");
+        let repo = gix::init(&main).unwrap();
+        let root = commit(&repo, "HEAD", &[]);
+        commit(&repo, "refs/heads/feature", &[root]);
+
+        // What `git worktree add ../wt feature` leaves on disk
+        let wt = dir.path().join("wt");
+        let admin = main.join(".git/worktrees/wt");
+        fs::create_dir_all(&admin).unwrap();
+        fs::create_dir_all(&wt).unwrap();
+        fs::write(admin.join("HEAD"), "ref: refs/heads/feature\n").unwrap();
+        fs::write(admin.join("commondir"), "../..\n").unwrap();
+        fs::write(
+            admin.join("gitdir"),
+            format!("{}\n", wt.join(".git").display()),
+        )
+        .unwrap();
+        fs::write(wt.join(".git"), format!("gitdir: {}\n", admin.display())).unwrap();
+
+        assert!(!check_single_branch(&wt));
+    }
+
+    /// Options for a history with these commit subjects.
+    fn history_with(messages: &[&str]) -> VibeOptions {
+        VibeOptions {
+            total_commits: messages.len(),
+            low_effort_commits: messages.iter().filter(|m| is_low_effort_message(m)).count(),
+            ..Default::default()
+        }
+    }
+
+    fn with_ai_ratio(ai_ratio: f64) -> VibeOptions {
+        VibeOptions {
+            ai_ratio,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flags_mostly_low_effort_history() {
+        let dir = TempDir::new().unwrap();
+        let commits = history_with(&[
+            "wip",
+            "fix",
+            "asdf",
+            "Add login form validation",
+            "Cache parsed config between runs",
+        ]);
+        let info = detect_vibe(dir.path(), &commits);
+        assert!(info.low_effort_commits);
+        assert!((info.low_effort_ratio - 0.6).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn low_effort_needs_enough_commits_and_share() {
+        let dir = TempDir::new().unwrap();
+        // 100% low effort, but only 2 commits
+        let info = detect_vibe(dir.path(), &history_with(&["wip", "fix"]));
+        assert!(!info.low_effort_commits);
+
+        // 1 in 5 is under the threshold
+        let commits = history_with(&[
+            "wip",
+            "Add login form validation",
+            "Cache parsed config between runs",
+            "Split the renderer into modules",
+            "Document the config precedence",
+        ]);
+        let info = detect_vibe(dir.path(), &commits);
+        assert!(!info.low_effort_commits);
+        assert!((info.low_effort_ratio - 0.2).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn detects_no_ci_cd() {
         let dir = TempDir::new().unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(info.no_ci_cd);
     }
 
@@ -346,14 +731,14 @@ mod tests {
     fn detects_github_actions() {
         let dir = TempDir::new().unwrap();
         fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(!info.no_ci_cd);
     }
 
     #[test]
     fn detects_boomer_ai() {
         let dir = TempDir::new().unwrap();
-        let info = detect_vibe(dir.path(), 0.5);
+        let info = detect_vibe(dir.path(), &with_ai_ratio(0.5));
         assert!(info.boomer_ai);
     }
 
@@ -361,21 +746,21 @@ mod tests {
     fn no_boomer_ai_with_claude_config() {
         let dir = TempDir::new().unwrap();
         fs::create_dir_all(dir.path().join(".claude")).unwrap();
-        let info = detect_vibe(dir.path(), 0.5);
+        let info = detect_vibe(dir.path(), &with_ai_ratio(0.5));
         assert!(!info.boomer_ai);
     }
 
     #[test]
     fn no_boomer_ai_when_zero_ai() {
         let dir = TempDir::new().unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(!info.boomer_ai);
     }
 
     #[test]
     fn detects_no_gitignore() {
         let dir = TempDir::new().unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(info.no_gitignore);
     }
 
@@ -383,7 +768,7 @@ mod tests {
     fn single_line_gitignore_passes() {
         let dir = TempDir::new().unwrap();
         fs::write(dir.path().join(".gitignore"), "node_modules\n").unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(!info.no_gitignore); // 1 line is enough
     }
 
@@ -395,14 +780,14 @@ mod tests {
             "node_modules\ntarget\n.env\ndist\n",
         )
         .unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(!info.no_gitignore);
     }
 
     #[test]
     fn detects_no_readme() {
         let dir = TempDir::new().unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(info.no_readme);
     }
 
@@ -410,10 +795,33 @@ mod tests {
     fn detects_readme_present() {
         let dir = TempDir::new().unwrap();
         fs::write(dir.path().join("README.md"), "# Hello").unwrap();
-        let info = detect_vibe(dir.path(), 0.0);
+        let info = detect_vibe(dir.path(), &VibeOptions::default());
         assert!(!info.no_readme);
     }
 
+    #[test]
+    fn todo_count_is_not_capped() {
+        let dir = TempDir::new().unwrap();
+        let deep = dir.path().join("a/b/c/d/e/f/g/h/i/j/k/l");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(dir.path().join("main.rs"), "// TODO\n".repeat(150)).unwrap();
+        fs::write(deep.join("lib.rs"), "// FIXME\n").unwrap();
+
+        assert_eq!(count_todos(dir.path()), 151);
+    }
+
+    #[test]
+    fn todo_count_skips_gitignored_dirs() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".gitignore"), "generated/\n").unwrap();
+        fs::write(dir.path().join("main.rs"), "// TODO: one\n").unwrap();
+        let gen = dir.path().join("generated");
+        fs::create_dir_all(&gen).unwrap();
+        fs::write(gen.join("api.rs"), "// TODO\n// TODO\n// FIXME\n").unwrap();
+
+        assert_eq!(count_todos(dir.path()), 1);
+    }
+
     #[test]
     fn todo_keyword_word_boundary() {
         // Real TODO comments
@@ -426,4 +834,37 @@ mod tests {
         assert!(!has_todo_keyword("count_todos_recursive(path)"));
         assert!(!has_todo_keyword("pub todo_flood: bool"));
     }
+
+    #[test]
+    fn todos_only_count_inside_comments() {
+        let count = |ext: &str, src: &str| count_todo_comments(src, &comment_syntax(ext));
+
+        assert_eq!(
+            count("ts", "const title = \"TODO list\"; // TODO: i18n\n"),
+            1
+        );
+        assert_eq!(
+            count("tsx", "<h1>{'Todo app'}</h1>\nlabel(`FIXME later`)\n"),
+            0
+        );
+        assert_eq!(
+            count("py", "print(\"TODO list feature\")\nx = 1  # HACK\n"),
+            1
+        );
+        assert_eq!(count("py", "// TODO is not a Python comment\n"), 0);
+        // A marker inside a string doesn't open a comment
+        assert_eq!(count("js", "fetch(\"http://todo.dev\") // ok\n"), 0);
+        assert_eq!(count("rs", "let s = \"a \\\" // TODO\";\n"), 0);
+        assert_eq!(count("rs", "fn f<'a>(s: &'a str) {} // TODO\n"), 1);
+    }
+
+    #[test]
+    fn todos_in_block_comments_span_lines() {
+        let src =
+            "/**\n * TODO: document\n * FIXME too\n */\nlet todo = \"TODO\";\n/* HACK */ done();\n";
+        assert_eq!(count_todo_comments(src, &comment_syntax("ts")), 3);
+
+        let vue = "<!-- TODO: split -->\n<button>Add todo</button>\n<!--\n  HACK\n-->\n";
+        assert_eq!(count_todo_comments(vue, &comment_syntax("vue")), 2);
+    }
 }
@@ -0,0 +1,96 @@
+use crate::git::parser::GitStats;
+use crate::project::ProjectStats;
+use crate::score::calculator::{quality, VibeScore};
+
+/// Column names, in the order `render_csv` writes them.
+const HEADER: &[&str] = &[
+    "repo",
+    "score",
+    "grade",
+    "ai_ratio",
+    "total_commits",
+    "ai_commits",
+    "human_commits",
+    "bot_commits",
+    "code_lines",
+    "total_lines",
+    "deps",
+    "test_files",
+    "quality_score",
+    "roast",
+];
+
+/// Render the report as CSV: a header row and one row for the repo, so
+/// scans can be appended to a spreadsheet.
+pub fn render_csv(
+    git: &GitStats,
+    project: &ProjectStats,
+    score: &VibeScore,
+    repo_name: &str,
+) -> String {
+    let row = [
+        repo_name.to_string(),
+        score.points.to_string(),
+        score.grade.clone(),
+        format!("{:.4}", score.ai_ratio),
+        git.total_commits.to_string(),
+        git.ai_commits.to_string(),
+        git.human_commits.to_string(),
+        git.bot_commits.to_string(),
+        project.languages.code_lines.to_string(),
+        project.languages.total_lines.to_string(),
+        project.deps.total.to_string(),
+        project.tests.test_files_count.to_string(),
+        quality(project).points.to_string(),
+        score.roast.clone(),
+    ];
+    let row: Vec<String> = row.iter().map(|field| csv_escape(field)).collect();
+    format!("{}\n{}\n", HEADER.join(","), row.join(","))
+}
+
+/// Quote a field (doubling inner quotes) when it holds a comma, quote or newline.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// ━━ Tests ━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_row_under_the_header() {
+        let git = GitStats {
+            total_commits: 10,
+            ai_commits: 4,
+            human_commits: 6,
+            ..Default::default()
+        };
+        let score = VibeScore {
+            grade: "B+".to_string(),
+            points: 65,
+            roast: "Vibes, \"mostly\"".to_string(),
+            ai_ratio: 0.4,
+            breakdown: vec![],
+        };
+        let csv = render_csv(&git, &ProjectStats::default(), &score, "me/app");
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], HEADER.join(","));
+        assert!(lines[1].starts_with("me/app,65,B+,0.4000,10,4,6,0,"));
+        assert!(lines[1].ends_with(",\"Vibes, \"\"mostly\"\"\""));
+    }
+
+    #[test]
+    fn escapes_only_when_needed() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("line\nbreak"), "\"line\nbreak\"");
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt::Write;
+
+use crate::git::parser::GitStats;
+use crate::git::timeline::build_timeline;
+use crate::project::ProjectStats;
+use crate::score::calculator::VibeScore;
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Render the vibe report as GitHub-flavored Markdown (for PR descriptions, READMEs).
+pub fn render_markdown(
+    git: &GitStats,
+    project: &ProjectStats,
+    score: &VibeScore,
+    repo_name: &str,
+) -> String {
+    let mut md = String::new();
+
+    // ── Title + grade ──
+    if repo_name.is_empty() {
+        md.push_str("# Vibe Report\n\n");
+    } else {
+        let _ = writeln!(md, "# Vibe Report — {}\n", md_escape(repo_name));
+    }
+    let _ = writeln!(
+        md,
+        "**Vibe Score: {} ({}pts)**\n",
+        score.grade, score.points
+    );
+    let _ = writeln!(md, "> \"{}\"\n", score.roast);
+
+    // ── Summary ──
+    md.push_str("| | |\n|---|---:|\n");
+    if git.no_git {
+        md.push_str("| Git history | none (project only) |\n");
+    } else {
+        let _ = writeln!(md, "| AI-authored | {:.0}% |", score.ai_ratio * 100.0);
+        let _ = writeln!(
+            md,
+            "| Human-authored | {:.0}% |",
+            (1.0 - score.ai_ratio) * 100.0
+        );
+        let _ = writeln!(md, "| Total commits | {} |", git.total_commits);
+    }
+    let deps_str = if project.deps.total > 0 {
+        format!("{} ({})", project.deps.counts(), project.deps.manager)
+    } else {
+        "0".to_string()
+    };
+    let _ = writeln!(md, "| Dependencies | {} |", deps_str);
+    let test_str = if project.tests.has_tests {
+        format!("{} files", project.tests.test_files_count)
+    } else {
+        "none".to_string()
+    };
+    let _ = writeln!(md, "| Tests | {} |", test_str);
+    let _ = writeln!(
+        md,
+        "| License | {} |",
+        project.license.as_deref().unwrap_or("none")
+    );
+    let _ = writeln!(
+        md,
+        "| Lines of code | {} |",
+        fmt_num(project.languages.code_lines)
+    );
+
+    // ── AI Tools ──
+    if !git.ai_tools.is_empty() {
+        md.push_str("\n## AI Tools\n\n| Tool | Commits | Share |\n|---|---:|---:|\n");
+        let mut tools: Vec<_> = git.ai_tools.iter().collect();
+        tools.sort_by_key(|t| std::cmp::Reverse(t.1));
+        for (tool, count) in &tools {
+            let pct = (*count as f64 / git.total_commits.max(1) as f64) * 100.0;
+            let _ = writeln!(
+                md,
+                "| {} | {} | {:.0}% |",
+                md_escape(&tool.to_string()),
+                count,
+                pct
+            );
+        }
+    }
+
+    // ── Languages ──
+    let mut langs: Vec<_> = project.languages.languages.iter().collect();
+    langs.sort_by(|a, b| b.1.cmp(a.1));
+    if !langs.is_empty() {
+        md.push_str("\n## Languages\n\n| Language | Lines | Share |\n|---|---:|---:|\n");
+        for (lang, lines) in &langs {
+            let pct = (**lines as f64 / project.languages.total_lines.max(1) as f64) * 100.0;
+            let _ = writeln!(md, "| {} | {} | {:.1}% |", lang, fmt_num(**lines), pct);
+        }
+    }
+
+    // ── Timeline (last 12 months) ──
+    let timeline = build_timeline(&git.daily);
+    if timeline.len() >= 2 {
+        md.push_str("\n## Timeline\n\n| Month | Commits | AI% |\n|---|---:|---:|\n");
+        for m in timeline.iter().skip(timeline.len().saturating_sub(12)) {
+            let _ = writeln!(
+                md,
+                "| {} {} | {} | {:.0}% |",
+                MONTH_NAMES[(m.month as usize).saturating_sub(1).min(11)],
+                m.year,
+                m.total_commits,
+                m.ai_ratio * 100.0
+            );
+        }
+    }
+
+    // ── Score Breakdown ──
+    if !score.breakdown.is_empty() {
+        md.push_str("\n## Score Breakdown\n\n| Factor | Points |\n|---|---:|\n");
+        let mut factors: Vec<_> = score.breakdown.iter().collect();
+        factors.sort_by_key(|f| std::cmp::Reverse(f.points));
+        for factor in factors {
+            let _ = writeln!(md, "| {} | +{} |", md_escape(&factor.label), factor.points);
+        }
+    }
+
+    md.push_str("\n---\n\nGenerated by [vibereport](https://vibereport.dev)\n");
+    md
+}
+
+/// Escape characters that would break a Markdown table cell.
+fn md_escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+fn fmt_num(n: usize) -> String {
+    if n >= 1_000_000 {
+        format!("{:.1}M", n as f64 / 1_000_000.0)
+    } else if n >= 1_000 {
+        format!("{:.1}K", n as f64 / 1_000.0)
+    } else {
+        n.to_string()
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//  Tests
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::ai_detect::AiTool;
+    use crate::git::parser::CommitInfo;
+    use crate::score::calculator::ScoreFactor;
+    use chrono::{TimeZone, Utc};
+
+    fn mock_git_stats(ai_ratio: f64) -> GitStats {
+        let ai_commits = (100.0 * ai_ratio) as usize;
+        GitStats {
+            total_commits: 100,
+            ai_commits,
+            human_commits: 100 - ai_commits,
+            ai_ratio,
+            ai_tools: if ai_ratio > 0.0 {
+                vec![(AiTool::ClaudeCode, ai_commits)]
+            } else {
+                vec![]
+            },
+            ..Default::default()
+        }
+    }
+
+    fn mock_project_stats() -> ProjectStats {
+        let mut languages = std::collections::HashMap::new();
+        languages.insert("Rust".to_string(), 3000);
+        languages.insert("TypeScript".to_string(), 1000);
+
+        ProjectStats {
+            deps: crate::project::deps::DepsInfo {
+                total: 42,
+                direct: 42,
+                manager: "cargo".into(),
+            },
+            tests: crate::project::tests_detect::TestsInfo {
+                has_tests: true,
+                test_files_count: 8,
+                frameworks: vec!["cargo test".to_string()],
+                test_ratio: 0.2,
+            },
+            languages: crate::project::languages::LanguageStats {
+                languages,
+                total_lines: 4000,
+                code_lines: 3500,
+                config_docs_lines: 0,
+                generated_lines: 0,
+            },
+            security: crate::project::security::SecurityInfo::default(),
+            vibe: crate::project::vibe_detect::VibeInfo::default(),
+            monorepo: false,
+            license: None,
+        }
+    }
+
+    fn mock_vibe_score(ai_ratio: f64) -> VibeScore {
+        VibeScore {
+            grade: "B+".to_string(),
+            points: 65,
+            roast: "Your code writes itself... literally.".to_string(),
+            ai_ratio,
+            breakdown: vec![
+                ScoreFactor {
+                    label: "No Linting".to_string(),
+                    points: 10,
+                },
+                ScoreFactor {
+                    label: "AI Ratio".to_string(),
+                    points: 36,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn markdown_contains_header_tables_and_roast() {
+        let md = render_markdown(
+            &mock_git_stats(0.6),
+            &mock_project_stats(),
+            &mock_vibe_score(0.6),
+            "my-project",
+        );
+
+        assert!(md.starts_with("# Vibe Report — my-project\n"));
+        assert!(md.contains("**Vibe Score: B+ (65pts)**"));
+        assert!(md.contains("> \"Your code writes itself... literally.\""));
+        assert!(md.contains("| Tool | Commits | Share |"));
+        assert!(md.contains("| Claude Code | 60 | 60% |"));
+        assert!(md.contains("| Rust | 3.0K | 75.0% |"));
+        assert!(md.contains("| TypeScript | 1.0K | 25.0% |"));
+        assert!(md.contains("| Lines of code | 3.5K |"));
+    }
+
+    #[test]
+    fn markdown_breakdown_sorted_by_points() {
+        let md = render_markdown(
+            &mock_git_stats(0.6),
+            &mock_project_stats(),
+            &mock_vibe_score(0.6),
+            "repo",
+        );
+        let ai = md.find("| AI Ratio | +36 |").unwrap();
+        let lint = md.find("| No Linting | +10 |").unwrap();
+        assert!(ai < lint);
+    }
+
+    #[test]
+    fn markdown_without_ai_has_no_tools_table() {
+        let md = render_markdown(
+            &mock_git_stats(0.0),
+            &mock_project_stats(),
+            &mock_vibe_score(0.0),
+            "human-repo",
+        );
+        assert!(!md.contains("## AI Tools"));
+        assert!(md.contains("| AI-authored | 0% |"));
+    }
+
+    #[test]
+    fn markdown_timeline_lists_months() {
+        let mut git = mock_git_stats(0.5);
+        let commit = |month, ai_tool| CommitInfo {
+            hash: "abcd1234".to_string(),
+            message: "test".to_string(),
+            author: "dev".to_string(),
+            timestamp: Utc
+                .with_ymd_and_hms(2025, month, 10, 12, 0, 0)
+                .unwrap()
+                .fixed_offset(),
+            ai_tool,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+        git.daily = [commit(1, AiTool::ClaudeCode), commit(3, AiTool::Human)]
+            .iter()
+            .collect();
+
+        let md = render_markdown(&git, &mock_project_stats(), &mock_vibe_score(0.5), "r");
+        assert!(md.contains("| Month | Commits | AI% |"));
+        assert!(md.contains("| Jan 2025 | 1 | 100% |"));
+        assert!(md.contains("| Feb 2025 | 0 | 0% |"));
+        assert!(md.contains("| Mar 2025 | 1 | 0% |"));
+    }
+
+    #[test]
+    fn md_escape_pipes() {
+        assert_eq!(md_escape("a|b"), "a\\|b");
+    }
+}
@@ -1,2 +1,81 @@
+pub mod csv;
+pub mod markdown;
+#[cfg(feature = "png")]
+pub mod png;
 pub mod svg;
 pub mod terminal;
+
+/// One output picked with `--format` (repeatable). Terminal and JSON print
+/// to stdout (JSON goes to `-o` when given); the file formats carry their
+/// path, as in `svg=report.svg`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Format {
+    Terminal,
+    Json,
+    Svg(String),
+    Markdown(String),
+    Csv(String),
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, path) = match s.split_once('=') {
+            Some((name, path)) => (name, Some(path).filter(|p| !p.is_empty())),
+            None => (s, None),
+        };
+        let name = name.to_lowercase();
+        match (name.as_str(), path) {
+            ("terminal", None) => Ok(Format::Terminal),
+            ("json", None) => Ok(Format::Json),
+            ("terminal" | "json", Some(_)) => Err(format!(
+                "{} prints to stdout and takes no path (use -o PATH to write the JSON to a file)",
+                name
+            )),
+            ("svg", Some(path)) => Ok(Format::Svg(path.to_string())),
+            ("markdown", Some(path)) => Ok(Format::Markdown(path.to_string())),
+            ("csv", Some(path)) => Ok(Format::Csv(path.to_string())),
+            ("svg" | "markdown" | "csv", None) => {
+                Err(format!("{} needs a path: --format {}=PATH", name, name))
+            }
+            _ => Err(format!(
+                "expected terminal, json, svg=PATH, markdown=PATH, or csv=PATH (got \"{}\")",
+                s
+            )),
+        }
+    }
+}
+
+// ━━ Tests ━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_formats_and_paths() {
+        assert_eq!("terminal".parse(), Ok(Format::Terminal));
+        assert_eq!("JSON".parse(), Ok(Format::Json));
+        assert_eq!(
+            "svg=out/card.svg".parse(),
+            Ok(Format::Svg("out/card.svg".into()))
+        );
+        assert_eq!(
+            "markdown=a=b.md".parse(),
+            Ok(Format::Markdown("a=b.md".into()))
+        );
+        assert_eq!("csv=scan.csv".parse(), Ok(Format::Csv("scan.csv".into())));
+    }
+
+    #[test]
+    fn rejects_missing_or_extra_paths() {
+        assert!("svg".parse::<Format>().unwrap_err().contains("svg=PATH"));
+        assert!("csv=".parse::<Format>().is_err());
+        assert!("json=out.json"
+            .parse::<Format>()
+            .unwrap_err()
+            .contains("-o PATH"));
+        assert!("html".parse::<Format>().is_err());
+    }
+}
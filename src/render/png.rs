@@ -0,0 +1,74 @@
+use resvg::{tiny_skia, usvg};
+
+/// Rasterize an SVG from `render::svg` into PNG bytes. `scale` multiplies the
+/// SVG's own size (600px wide), so 2.0 gives a crisp 1200px image.
+pub fn svg_to_png(svg: &str, scale: f32) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(format!("invalid PNG scale {}: must be greater than 0", scale).into());
+    }
+
+    let mut options = usvg::Options::default();
+    let fonts = options.fontdb_mut();
+    fonts.load_system_fonts();
+    // The SVG falls back to generic `monospace`, which fontdb maps to a fixed
+    // family name; point it at whatever monospaced font is installed instead.
+    let installed_mono = fonts
+        .faces()
+        .find(|face| face.monospaced)
+        .and_then(|face| face.families.first())
+        .map(|(family, _)| family.clone());
+    if let Some(family) = installed_mono {
+        fonts.set_monospace_family(family);
+    }
+    let tree = usvg::Tree::from_str(svg, &options)?;
+
+    let size = tree
+        .size()
+        .to_int_size()
+        .scale_by(scale)
+        .ok_or_else(|| format!("PNG scale {} gives an empty image", scale))?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| format!("PNG scale {} gives an image too large to render", scale))?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    Ok(pixmap.encode_png()?)
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//  Tests
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="600" height="300" viewBox="0 0 600 300"><rect width="600" height="300" fill="#1a1b26"/></svg>"##;
+
+    /// Width and height from the PNG IHDR chunk.
+    fn png_size(png: &[u8]) -> (u32, u32) {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let be = |at: usize| u32::from_be_bytes(png[at..at + 4].try_into().unwrap());
+        (be(16), be(20))
+    }
+
+    #[test]
+    fn scales_the_svg_size() {
+        assert_eq!(png_size(&svg_to_png(SVG, 1.0).unwrap()), (600, 300));
+        assert_eq!(png_size(&svg_to_png(SVG, 2.0).unwrap()), (1200, 600));
+    }
+
+    #[test]
+    fn rejects_bad_scale() {
+        assert!(svg_to_png(SVG, 0.0).is_err());
+        assert!(svg_to_png(SVG, -1.0).is_err());
+        assert!(svg_to_png(SVG, f32::NAN).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_svg() {
+        assert!(svg_to_png("not an svg", 1.0).is_err());
+    }
+}
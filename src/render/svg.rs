@@ -2,17 +2,121 @@ use crate::git::parser::GitStats;
 use crate::git::timeline::build_timeline;
 use crate::project::ProjectStats;
 use crate::score::calculator::VibeScore;
+use std::path::Path;
+
+/// Colors used by the SVG card. `dark` (Tokyo Night) is the default; `light`
+/// suits READMEs rendered on a white background.
+///
+/// A JSON theme file may override any subset of the fields, e.g.
+/// `{"background": "#ffffff", "bar": "#2ea043"}`; missing fields keep the
+/// dark palette.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SvgTheme {
+    pub background: String,
+    /// Card border, separators and section headings.
+    pub border: String,
+    /// Titles, values and language names.
+    pub text: String,
+    /// Labels, percentages and the roast.
+    pub dimmed: String,
+    /// Leader dots, gridlines and the footer.
+    pub dots: String,
+    /// The score line.
+    pub score: String,
+    /// Filled language and timeline bars.
+    pub bar: String,
+    pub bar_empty: String,
+    /// Security warnings.
+    pub warning: String,
+}
+
+impl Default for SvgTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl SvgTheme {
+    /// Tokyo Night.
+    pub fn dark() -> Self {
+        Self {
+            background: "#1a1b26".into(),
+            border: "#7aa2f7".into(),
+            text: "#c0caf5".into(),
+            dimmed: "#565f89".into(),
+            dots: "#3b3e53".into(),
+            score: "#e0af68".into(),
+            bar: "#9ece6a".into(),
+            bar_empty: "#3b3e53".into(),
+            warning: "#f7768e".into(),
+        }
+    }
+
+    /// Tokyo Night Day.
+    pub fn light() -> Self {
+        Self {
+            background: "#e1e2e7".into(),
+            border: "#2e7de9".into(),
+            text: "#3760bf".into(),
+            dimmed: "#6172b0".into(),
+            dots: "#a8aecb".into(),
+            score: "#8c6c3e".into(),
+            bar: "#587539".into(),
+            bar_empty: "#c4c8da".into(),
+            warning: "#f52a65".into(),
+        }
+    }
+
+    /// Resolve `--svg-theme`: `light`, `dark`, or a path to a JSON theme file.
+    pub fn resolve(name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        match name {
+            "dark" => Ok(Self::dark()),
+            "light" => Ok(Self::light()),
+            path => Self::load(Path::new(path)),
+        }
+    }
 
-// ── Tokyo Night Color Palette ──
-const BG: &str = "#1a1b26";
-const BORDER: &str = "#7aa2f7";
-const WHITE: &str = "#c0caf5";
-const DIMMED: &str = "#565f89";
-const DOTS: &str = "#3b3e53";
-const YELLOW: &str = "#e0af68";
-const GREEN: &str = "#9ece6a";
-const BAR_EMPTY: &str = "#3b3e53";
-const RED: &str = "#f7768e";
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str::<Self>(&content)
+            .map_err(|e| e.to_string())
+            .and_then(Self::validate)
+            .map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// Colors are written into SVG attributes verbatim, so only accept hex
+    /// colors (`#rgb`, `#rrggbb`, `#rrggbbaa`) and named colors.
+    fn validate(self) -> Result<Self, String> {
+        let colors = [
+            ("background", &self.background),
+            ("border", &self.border),
+            ("text", &self.text),
+            ("dimmed", &self.dimmed),
+            ("dots", &self.dots),
+            ("score", &self.score),
+            ("bar", &self.bar),
+            ("bar_empty", &self.bar_empty),
+            ("warning", &self.warning),
+        ];
+        for (key, color) in colors {
+            if !is_valid_color(color) {
+                return Err(format!("invalid color for `{}`: {:?}", key, color));
+            }
+        }
+        Ok(self)
+    }
+}
+
+fn is_valid_color(color: &str) -> bool {
+    match color.strip_prefix('#') {
+        Some(hex) => {
+            matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => !color.is_empty() && color.chars().all(|c| c.is_ascii_alphabetic()),
+    }
+}
 
 const WIDTH: usize = 600;
 const PADDING: usize = 32;
@@ -26,6 +130,7 @@ pub fn render_svg(
     project: &ProjectStats,
     score: &VibeScore,
     repo_name: &str,
+    theme: &SvgTheme,
 ) -> String {
     // Pre-compute content to determine dynamic height
     let mut y: usize = PADDING + 10;
@@ -35,13 +140,13 @@ pub fn render_svg(
     lines.push(SvgLine::CenteredBold {
         text: "VIBE REPORT".to_string(),
         y,
-        color: WHITE.to_string(),
+        color: theme.text.clone(),
     });
     y += LINE_HEIGHT;
     lines.push(SvgLine::CenteredNormal {
         text: repo_name.to_string(),
         y,
-        color: DIMMED.to_string(),
+        color: theme.dimmed.clone(),
     });
     y += LINE_HEIGHT + 8;
 
@@ -50,19 +155,23 @@ pub fn render_svg(
     y += LINE_HEIGHT;
 
     // ── AI vs Human ──
-    lines.push(kv_line(
-        "AI-authored",
-        &format!("{:.0}%", score.ai_ratio * 100.0),
-        y,
-    ));
-    y += LINE_HEIGHT;
-    lines.push(kv_line(
-        "Human-authored",
-        &format!("{:.0}%", (1.0 - score.ai_ratio) * 100.0),
-        y,
-    ));
-    y += LINE_HEIGHT;
-    lines.push(kv_line("Total commits", &git.total_commits.to_string(), y));
+    if git.no_git {
+        lines.push(kv_line("Git history", "none (project only)", y));
+    } else {
+        lines.push(kv_line(
+            "AI-authored",
+            &format!("{:.0}%", score.ai_ratio * 100.0),
+            y,
+        ));
+        y += LINE_HEIGHT;
+        lines.push(kv_line(
+            "Human-authored",
+            &format!("{:.0}%", (1.0 - score.ai_ratio) * 100.0),
+            y,
+        ));
+        y += LINE_HEIGHT;
+        lines.push(kv_line("Total commits", &git.total_commits.to_string(), y));
+    }
     y += LINE_HEIGHT + 4;
 
     // ── AI Tools ──
@@ -73,7 +182,7 @@ pub fn render_svg(
         });
         y += LINE_HEIGHT;
         let mut tools: Vec<_> = git.ai_tools.iter().collect();
-        tools.sort_by(|a, b| b.1.cmp(&a.1));
+        tools.sort_by_key(|t| std::cmp::Reverse(t.1));
         for (tool, count) in &tools {
             let pct = (*count as f64 / git.total_commits.max(1) as f64) * 100.0;
             lines.push(kv_line(
@@ -94,7 +203,7 @@ pub fn render_svg(
     y += LINE_HEIGHT;
 
     let deps_str = if project.deps.total > 0 {
-        format!("{} ({})", project.deps.total, project.deps.manager)
+        format!("{} ({})", project.deps.counts(), project.deps.manager)
     } else {
         "0".to_string()
     };
@@ -116,7 +225,7 @@ pub fn render_svg(
 
     lines.push(kv_line(
         "Lines of code",
-        &fmt_num(project.languages.total_lines),
+        &fmt_num(project.languages.code_lines),
         y,
     ));
     y += LINE_HEIGHT + 4;
@@ -143,7 +252,7 @@ pub fn render_svg(
     }
 
     // ── Timeline ──
-    let timeline = build_timeline(&git.commits);
+    let timeline = build_timeline(&git.daily);
     // Only show if we have at least 2 months of data; cap at 12 months
     if timeline.len() >= 2 {
         let timeline_data: Vec<_> = timeline
@@ -170,7 +279,10 @@ pub fn render_svg(
     }
 
     // ── Security ──
-    if project.security.env_in_git || project.security.hardcoded_secrets_hints > 0 {
+    if project.security.env_in_git
+        || project.security.hardcoded_secrets_hints > 0
+        || project.security.private_keys_count > 0
+    {
         lines.push(SvgLine::Section {
             text: "SECURITY".to_string(),
             y,
@@ -198,6 +310,16 @@ pub fn render_svg(
             });
             y += LINE_HEIGHT;
         }
+        if project.security.private_keys_count > 0 {
+            lines.push(SvgLine::Warning {
+                text: format!(
+                    "{} private key file(s) committed!",
+                    project.security.private_keys_count
+                ),
+                y,
+            });
+            y += LINE_HEIGHT;
+        }
         y += 4;
     }
 
@@ -209,7 +331,7 @@ pub fn render_svg(
     lines.push(SvgLine::CenteredBold {
         text: format!("VIBE SCORE: {} ({}pts)", score.grade, score.points),
         y,
-        color: YELLOW.to_string(),
+        color: theme.score.clone(),
     });
     y += LINE_HEIGHT;
 
@@ -224,13 +346,24 @@ pub fn render_svg(
     lines.push(SvgLine::CenteredNormal {
         text: "generated by vibereport.dev".to_string(),
         y,
-        color: DOTS.to_string(),
+        color: theme.dots.clone(),
     });
     y += LINE_HEIGHT;
 
     let total_height = y + PADDING;
     let content_width = WIDTH - 2 * PADDING;
     let cx = WIDTH / 2; // center x
+    let SvgTheme {
+        background,
+        border,
+        text: text_color,
+        dimmed,
+        dots,
+        bar,
+        bar_empty,
+        warning,
+        ..
+    } = theme;
 
     // ── Build SVG ──
     let mut svg = String::new();
@@ -240,12 +373,12 @@ pub fn render_svg(
 
     // Background
     svg.push_str(&format!(
-        r#"<rect width="{WIDTH}" height="{total_height}" rx="12" fill="{BG}"/>"#,
+        r#"<rect width="{WIDTH}" height="{total_height}" rx="12" fill="{background}"/>"#,
     ));
 
     // Border
     svg.push_str(&format!(
-        r#"<rect x="8" y="8" width="{}" height="{}" rx="8" fill="none" stroke="{BORDER}" stroke-width="1.5" opacity="0.5"/>"#,
+        r#"<rect x="8" y="8" width="{}" height="{}" rx="8" fill="none" stroke="{border}" stroke-width="1.5" opacity="0.5"/>"#,
         WIDTH - 16,
         total_height - 16,
     ));
@@ -270,13 +403,13 @@ pub fn render_svg(
                 let x1 = PADDING;
                 let x2 = WIDTH - PADDING;
                 svg.push_str(&format!(
-                    r#"<line x1="{x1}" y1="{y}" x2="{x2}" y2="{y}" stroke="{BORDER}" stroke-width="0.5" opacity="0.3"/>"#,
+                    r#"<line x1="{x1}" y1="{y}" x2="{x2}" y2="{y}" stroke="{border}" stroke-width="0.5" opacity="0.3"/>"#,
                 ));
             }
             SvgLine::Section { text, y } => {
                 let x = PADDING + 4;
                 svg.push_str(&format!(
-                    r#"<text x="{x}" y="{y}" font-family="{FONT_FAMILY}" font-size="{}" font-weight="bold" fill="{BORDER}" opacity="0.8">{}</text>"#,
+                    r#"<text x="{x}" y="{y}" font-family="{FONT_FAMILY}" font-size="{}" font-weight="bold" fill="{border}" opacity="0.8">{}</text>"#,
                     FONT_SIZE - 1,
                     xml_escape(text),
                 ));
@@ -287,7 +420,7 @@ pub fn render_svg(
                 let vx = WIDTH - PADDING - 12;
                 // Label (dimmed)
                 svg.push_str(&format!(
-                    r#"<text x="{lx}" y="{y}" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" fill="{DIMMED}">{}</text>"#,
+                    r#"<text x="{lx}" y="{y}" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" fill="{dimmed}">{}</text>"#,
                     xml_escape(label),
                 ));
                 // Dots
@@ -298,14 +431,14 @@ pub fn render_svg(
                     let mut dx = dots_x1;
                     while dx < dots_x2 {
                         svg.push_str(&format!(
-                            r#"<circle cx="{dx}" cy="{dot_y}" r="1" fill="{DOTS}"/>"#,
+                            r#"<circle cx="{dx}" cy="{dot_y}" r="1" fill="{dots}"/>"#,
                         ));
                         dx += 6;
                     }
                 }
                 // Value (white bold)
                 svg.push_str(&format!(
-                    r#"<text x="{vx}" y="{y}" text-anchor="end" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" font-weight="bold" fill="{WHITE}">{}</text>"#,
+                    r#"<text x="{vx}" y="{y}" text-anchor="end" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" font-weight="bold" fill="{text_color}">{}</text>"#,
                     xml_escape(value),
                 ));
             }
@@ -319,23 +452,23 @@ pub fn render_svg(
 
                 // Language name
                 svg.push_str(&format!(
-                    r#"<text x="{lx}" y="{y}" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" fill="{WHITE}">{}</text>"#,
+                    r#"<text x="{lx}" y="{y}" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" fill="{text_color}">{}</text>"#,
                     xml_escape(lang),
                 ));
                 // Empty bar background
                 svg.push_str(&format!(
-                    r#"<rect x="{bar_x}" y="{bar_y}" width="{bar_w}" height="{bar_h}" rx="3" fill="{BAR_EMPTY}"/>"#,
+                    r#"<rect x="{bar_x}" y="{bar_y}" width="{bar_w}" height="{bar_h}" rx="3" fill="{bar_empty}"/>"#,
                 ));
                 // Filled bar
                 if filled_w > 0 {
                     svg.push_str(&format!(
-                        r#"<rect x="{bar_x}" y="{bar_y}" width="{filled_w}" height="{bar_h}" rx="3" fill="{GREEN}"/>"#,
+                        r#"<rect x="{bar_x}" y="{bar_y}" width="{filled_w}" height="{bar_h}" rx="3" fill="{bar}"/>"#,
                     ));
                 }
                 // Percentage
                 let pct_x = WIDTH - PADDING - 12;
                 svg.push_str(&format!(
-                    r#"<text x="{pct_x}" y="{y}" text-anchor="end" font-family="{FONT_FAMILY}" font-size="{}" fill="{DIMMED}">{:.1}%</text>"#,
+                    r#"<text x="{pct_x}" y="{y}" text-anchor="end" font-family="{FONT_FAMILY}" font-size="{}" fill="{dimmed}">{:.1}%</text>"#,
                     FONT_SIZE - 1,
                     pct,
                 ));
@@ -355,14 +488,14 @@ pub fn render_svg(
                     let ly = chart_top as f64 + chart_h as f64 * frac;
                     // Gridline
                     svg.push_str(&format!(
-                        r#"<line x1="{chart_x}" y1="{ly:.0}" x2="{}" y2="{ly:.0}" stroke="{DOTS}" stroke-width="0.5" opacity="0.5"/>"#,
+                        r#"<line x1="{chart_x}" y1="{ly:.0}" x2="{}" y2="{ly:.0}" stroke="{dots}" stroke-width="0.5" opacity="0.5"/>"#,
                         chart_x + chart_w,
                     ));
                     // Label
                     let label_x = chart_x - 8;
                     let text_y = ly + 4.0; // vertical centering for text baseline
                     svg.push_str(&format!(
-                        r#"<text x="{label_x}" y="{text_y:.0}" text-anchor="end" font-family="{FONT_FAMILY}" font-size="{}" fill="{DIMMED}">{pct_label}</text>"#,
+                        r#"<text x="{label_x}" y="{text_y:.0}" text-anchor="end" font-family="{FONT_FAMILY}" font-size="{}" fill="{dimmed}">{pct_label}</text>"#,
                         FONT_SIZE - 2,
                     ));
                 }
@@ -386,7 +519,7 @@ pub fn render_svg(
 
                         // Background bar (full height)
                         svg.push_str(&format!(
-                            r#"<rect x="{bx}" y="{chart_top}" width="{bar_w}" height="{chart_h}" rx="3" fill="{BAR_EMPTY}"/>"#,
+                            r#"<rect x="{bx}" y="{chart_top}" width="{bar_w}" height="{chart_h}" rx="3" fill="{bar_empty}"/>"#,
                         ));
 
                         // Filled bar (from bottom, proportional to ai_ratio)
@@ -394,7 +527,7 @@ pub fn render_svg(
                         if fill_h > 0 {
                             let fill_y = chart_bottom - fill_h;
                             svg.push_str(&format!(
-                                r#"<rect x="{bx}" y="{fill_y}" width="{bar_w}" height="{fill_h}" rx="3" fill="{GREEN}"/>"#,
+                                r#"<rect x="{bx}" y="{fill_y}" width="{bar_w}" height="{fill_h}" rx="3" fill="{bar}"/>"#,
                             ));
                         }
 
@@ -403,7 +536,7 @@ pub fn render_svg(
                         let label_y = chart_bottom + 14;
                         let month_idx = (ms.month as usize).saturating_sub(1).min(11);
                         svg.push_str(&format!(
-                            r#"<text x="{label_x}" y="{label_y}" text-anchor="middle" font-family="{FONT_FAMILY}" font-size="{}" fill="{DIMMED}">{}</text>"#,
+                            r#"<text x="{label_x}" y="{label_y}" text-anchor="middle" font-family="{FONT_FAMILY}" font-size="{}" fill="{dimmed}">{}</text>"#,
                             FONT_SIZE - 3,
                             month_names[month_idx],
                         ));
@@ -413,13 +546,13 @@ pub fn render_svg(
             SvgLine::Warning { text, y } => {
                 let x = PADDING + 12;
                 svg.push_str(&format!(
-                    r#"<text x="{x}" y="{y}" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" font-weight="bold" fill="{RED}">!! {}</text>"#,
+                    r#"<text x="{x}" y="{y}" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}" font-weight="bold" fill="{warning}">!! {}</text>"#,
                     xml_escape(text),
                 ));
             }
             SvgLine::Roast { text, y } => {
                 svg.push_str(&format!(
-                    r#"<text x="{cx}" y="{y}" text-anchor="middle" font-family="{FONT_FAMILY}" font-size="{}" font-style="italic" fill="{DIMMED}">{}</text>"#,
+                    r#"<text x="{cx}" y="{y}" text-anchor="middle" font-family="{FONT_FAMILY}" font-size="{}" font-style="italic" fill="{dimmed}">{}</text>"#,
                     FONT_SIZE - 1,
                     xml_escape(text),
                 ));
@@ -528,11 +661,7 @@ mod tests {
             } else {
                 vec![]
             },
-            commits: vec![],
-            first_commit_date: None,
-            last_commit_date: None,
-            repo_fingerprint: None,
-            remote_url: None,
+            ..Default::default()
         }
     }
 
@@ -545,19 +674,26 @@ mod tests {
         ProjectStats {
             deps: crate::project::deps::DepsInfo {
                 total: 42,
+                direct: 42,
                 manager: "cargo".into(),
             },
             tests: crate::project::tests_detect::TestsInfo {
                 has_tests: true,
                 test_files_count: 8,
                 frameworks: vec!["cargo test".to_string()],
+                test_ratio: 0.2,
             },
             languages: crate::project::languages::LanguageStats {
                 languages,
                 total_lines: 5000,
+                code_lines: 5000,
+                config_docs_lines: 0,
+                generated_lines: 0,
             },
             security: crate::project::security::SecurityInfo::default(),
             vibe: crate::project::vibe_detect::VibeInfo::default(),
+            monorepo: false,
+            license: None,
         }
     }
 
@@ -576,7 +712,7 @@ mod tests {
         let git = mock_git_stats(0.6);
         let project = mock_project_stats();
         let score = mock_vibe_score(0.6);
-        let svg = render_svg(&git, &project, &score, "my-project");
+        let svg = render_svg(&git, &project, &score, "my-project", &SvgTheme::default());
 
         assert!(svg.contains("<svg"), "SVG should contain opening <svg tag");
         assert!(
@@ -595,7 +731,7 @@ mod tests {
         let git = mock_git_stats(0.5);
         let project = mock_project_stats();
         let score = mock_vibe_score(0.5);
-        let svg = render_svg(&git, &project, &score, "test-repo");
+        let svg = render_svg(&git, &project, &score, "test-repo", &SvgTheme::default());
 
         assert!(
             svg.contains(r#"width="600""#),
@@ -619,7 +755,7 @@ mod tests {
             ai_ratio: 0.0,
             breakdown: vec![],
         };
-        let svg = render_svg(&git, &project, &score, "human-repo");
+        let svg = render_svg(&git, &project, &score, "human-repo", &SvgTheme::default());
 
         assert!(svg.contains("0%"), "Should show 0% AI-authored");
         assert!(svg.contains("100%"), "Should show 100% human-authored");
@@ -638,14 +774,60 @@ mod tests {
         project.security.env_in_git = true;
         project.security.env_files_count = 1;
         let score = mock_vibe_score(0.3);
-        let svg = render_svg(&git, &project, &score, "leaky-repo");
+        let svg = render_svg(&git, &project, &score, "leaky-repo", &SvgTheme::default());
 
         assert!(svg.contains("SECURITY"), "Should contain SECURITY section");
         assert!(
             svg.contains(".env committed to git!"),
             "Should contain .env warning"
         );
-        assert!(svg.contains(RED), "Warning should use red color");
+        assert!(
+            svg.contains(&SvgTheme::default().warning),
+            "Warning should use red color"
+        );
+    }
+
+    #[test]
+    fn test_svg_uses_theme_colors() {
+        let git = mock_git_stats(0.6);
+        let project = mock_project_stats();
+        let score = mock_vibe_score(0.6);
+        let light = SvgTheme::light();
+        let svg = render_svg(&git, &project, &score, "my-project", &light);
+
+        assert!(svg.contains(&format!(r#"fill="{}""#, light.background)));
+        assert!(svg.contains(&format!(r#"fill="{}""#, light.bar)));
+        assert!(!svg.contains(&SvgTheme::dark().background));
+    }
+
+    #[test]
+    fn test_resolve_builtin_themes() {
+        assert_eq!(SvgTheme::resolve("dark").unwrap(), SvgTheme::default());
+        assert_eq!(SvgTheme::resolve("light").unwrap(), SvgTheme::light());
+    }
+
+    #[test]
+    fn test_theme_file_overrides_some_colors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("theme.json");
+        std::fs::write(&path, r##"{"background": "#fff", "bar": "green"}"##).unwrap();
+        let theme = SvgTheme::resolve(path.to_str().unwrap()).unwrap();
+        assert_eq!(theme.background, "#fff");
+        assert_eq!(theme.bar, "green");
+        assert_eq!(theme.text, SvgTheme::dark().text);
+    }
+
+    #[test]
+    fn test_theme_file_rejects_bad_input() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("theme.json");
+        std::fs::write(&path, r##"{"text": "red\" onload=\"x"}"##).unwrap();
+        let err = SvgTheme::load(&path).unwrap_err().to_string();
+        assert!(err.contains("invalid color for `text`"), "{}", err);
+
+        std::fs::write(&path, r##"{"backgrund": "#fff"}"##).unwrap();
+        assert!(SvgTheme::load(&path).is_err());
+        assert!(SvgTheme::resolve("/nonexistent/theme.json").is_err());
     }
 
     #[test]
@@ -1,11 +1,101 @@
+use crate::git::ai_detect::AiTool;
 use crate::git::parser::GitStats;
-use crate::git::timeline::{build_timeline, MonthlyStats};
+use crate::git::timeline::{build_timeline_with, Granularity, TimelineBucket};
+use crate::git::velocity;
+use crate::history::Entry;
+use crate::project::languages::top_languages;
 use crate::project::ProjectStats;
-use crate::score::calculator::VibeScore;
+use crate::scanner::multi_report::{RepoSort, ScoreWeight};
+use crate::score::calculator::{QualityScore, VibeScore};
+use chrono::Datelike;
 use owo_colors::OwoColorize;
 
-/// Inner width (content area between the two border chars).
-const W: usize = 52;
+/// Default inner width (content area between the two border chars).
+pub const DEFAULT_WIDTH: usize = 52;
+
+/// Languages listed before the rest are rolled up into "Other".
+pub const DEFAULT_TOP_LANGUAGES: usize = 5;
+
+/// Smallest inner width the fixed-size rows (language bars, vibe checks) fit in.
+pub const MIN_WIDTH: usize = 40;
+
+/// Terminal rendering options, set from CLI flags.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOptions {
+    /// Timeline chart granularity (picked from the history span if `None`).
+    pub timeline: Option<Granularity>,
+    /// Emit ANSI colors. Off for `--no-color` / `NO_COLOR`.
+    pub color: bool,
+    /// Inner box width (`--width`), clamped to at least `MIN_WIDTH`.
+    pub width: usize,
+    /// Language rows shown before the "Other" rollup (`--top-languages`).
+    pub top_languages: usize,
+    /// Multi-repo table order (`--sort`, flipped by `--reverse`).
+    pub sort: RepoSort,
+    pub reverse: bool,
+    /// Weight of the second global average (`--weight-by`).
+    pub weight_by: ScoreWeight,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            timeline: None,
+            color: true,
+            width: DEFAULT_WIDTH,
+            top_languages: DEFAULT_TOP_LANGUAGES,
+            sort: RepoSort::default(),
+            reverse: false,
+            weight_by: ScoreWeight::default(),
+        }
+    }
+}
+
+/// Output sink threaded through the line builders.
+struct Term {
+    color: bool,
+    /// Inner width (content area between the two border chars).
+    width: usize,
+}
+
+impl Term {
+    fn new(options: &RenderOptions) -> Self {
+        Self {
+            color: options.color,
+            width: options.width.max(MIN_WIDTH),
+        }
+    }
+
+    /// Print one line, stripped of ANSI styling when color is off.
+    /// Layout is unchanged since widths are computed on unstyled text.
+    fn println(&self, line: String) {
+        if self.color {
+            println!("{}", line);
+        } else {
+            println!("{}", strip_ansi(&line));
+        }
+    }
+}
+
+/// Remove ANSI CSI escape sequences (`ESC [ ... final-byte`).
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 //  Public API
@@ -17,49 +107,80 @@ pub fn render_with_name(
     project: &ProjectStats,
     score: &VibeScore,
     repo_name: &str,
+    options: &RenderOptions,
 ) {
-    println!();
-    border_top();
-    blank();
+    let t = &Term::new(options);
+    t.println(String::new());
+    border_top(t);
+    blank(t);
 
     // ── Title ──
-    center_bold("VIBE REPORT");
+    center_bold(t, "VIBE REPORT");
     let subtitle = format!("{}  {}", repo_name, emoji_for_grade(&score.grade));
-    center_dimmed(&subtitle);
-    blank();
-    separator();
-    blank();
+    center_dimmed(t, &subtitle);
+    if let Some(span) = active_span(git) {
+        center_dimmed(t, &span);
+    }
+    blank(t);
+    separator(t);
+    blank(t);
 
     // ── AI vs Human ──
-    kv("AI-authored", &format!("{:.0}%", score.ai_ratio * 100.0));
-    kv(
-        "Human-authored",
-        &format!("{:.0}%", (1.0 - score.ai_ratio) * 100.0),
-    );
-    kv("Total commits", &git.total_commits.to_string());
-    blank();
+    if git.no_git {
+        kv(t, "Git history", "none (project only)");
+    } else {
+        kv(t, "AI-authored", &format!("{:.0}%", score.ai_ratio * 100.0));
+        kv(
+            t,
+            "Human-authored",
+            &format!("{:.0}%", (1.0 - score.ai_ratio) * 100.0),
+        );
+        kv(t, "Total commits", &git.total_commits.to_string());
+    }
+    if let Some(era) = &git.ai_era {
+        kv(t, "AI era since", &era.since.format("%Y-%m-%d").to_string());
+        kv(
+            t,
+            "Lifetime AI",
+            &format!(
+                "{:.0}% of {} commits",
+                era.lifetime_ai_ratio * 100.0,
+                era.lifetime_commits
+            ),
+        );
+    }
+    blank(t);
 
     // ── AI Tool Breakdown ──
     if !git.ai_tools.is_empty() {
-        section("AI TOOLS");
+        section(t, "AI TOOLS");
         let mut tools: Vec<_> = git.ai_tools.iter().collect();
-        tools.sort_by(|a, b| b.1.cmp(&a.1));
+        tools.sort_by_key(|t| std::cmp::Reverse(t.1));
         for (tool, count) in &tools {
             let pct = (*count as f64 / git.total_commits.max(1) as f64) * 100.0;
-            kv_indent(&tool.to_string(), &format!("{} ({:.0}%)", count, pct));
+            kv_indent(t, &tool.to_string(), &format!("{} ({:.0}%)", count, pct));
         }
-        blank();
+        blank(t);
     }
 
     // ── Project Stats ──
-    section("PROJECT");
+    section(t, "PROJECT");
     if project.deps.total > 0 {
         kv(
+            t,
             "Dependencies",
-            &format!("{} ({})", project.deps.total, project.deps.manager),
+            &if project.monorepo {
+                format!(
+                    "{} ({}, monorepo)",
+                    project.deps.counts(),
+                    project.deps.manager
+                )
+            } else {
+                format!("{} ({})", project.deps.counts(), project.deps.manager)
+            },
         );
     } else {
-        kv("Dependencies", "0");
+        kv(t, "Dependencies", "0");
     }
 
     let test_str = if project.tests.has_tests {
@@ -72,82 +193,324 @@ pub fn render_with_name(
     } else {
         "none".to_string()
     };
-    kv("Tests", &test_str);
-    kv("Lines of code", &fmt_num(project.languages.total_lines));
+    kv(t, "Tests", &test_str);
+    kv(t, "License", project.license.as_deref().unwrap_or("none"));
+    kv(t, "Lines of code", &fmt_num(project.languages.code_lines));
+    if project.languages.generated_lines > 0 {
+        kv(
+            t,
+            "Generated",
+            &format!(
+                "{} lines (not counted)",
+                fmt_num(project.languages.generated_lines)
+            ),
+        );
+    }
 
     // ── Top Languages ──
-    let mut langs: Vec<_> = project.languages.languages.iter().collect();
-    langs.sort_by(|a, b| b.1.cmp(a.1));
-    if !langs.is_empty() {
-        blank();
-        section("LANGUAGES");
-        for (lang, lines) in langs.iter().take(5) {
-            let pct = (**lines as f64 / project.languages.total_lines.max(1) as f64) * 100.0;
-            lang_row(lang, pct);
+    if !project.languages.languages.is_empty() {
+        let (langs, other) = project.languages.top(options.top_languages);
+        let pct =
+            |lines: usize| (lines as f64 / project.languages.total_lines.max(1) as f64) * 100.0;
+        blank(t);
+        section(t, "LANGUAGES");
+        for (lang, lines) in langs {
+            lang_row(t, lang, pct(lines));
+        }
+        if other > 0 {
+            lang_row(t, "Other", pct(other));
         }
     }
 
     // ── Timeline ──
-    let timeline = build_timeline(&git.commits);
+    let granularity = options
+        .timeline
+        .unwrap_or_else(|| Granularity::for_span(&git.daily));
+    let timeline = build_timeline_with(&git.daily, granularity);
     if timeline.len() >= 2 {
-        blank();
-        render_timeline_chart(&timeline);
+        blank(t);
+        render_timeline_chart(t, &timeline, granularity);
     }
 
-    blank();
-    separator();
-    blank();
+    // ── Velocity ──
+    let velocity = velocity::compute(&git.daily);
+    if let Some(weekday) = velocity.busiest_weekday {
+        blank(t);
+        section(t, "VELOCITY");
+        kv(t, "Active days", &fmt_num(velocity.active_days));
+        kv(
+            t,
+            "Commits per active day",
+            &format!("{:.1}", velocity.commits_per_active_day),
+        );
+        let streak = velocity.longest_streak;
+        let plural = if streak == 1 { "" } else { "s" };
+        kv(t, "Longest streak", &format!("{} day{}", streak, plural));
+        kv(t, "Busiest day", velocity::weekday_name(weekday));
+    }
+
+    blank(t);
+    separator(t);
+    blank(t);
 
-    // ── Score Breakdown (pills + vibe checks) ──
-    section("SCORE BREAKDOWN");
+    // ── Score Breakdown (factors by points + vibe checks) ──
+    section(t, "SCORE BREAKDOWN");
     if !score.breakdown.is_empty() {
-        render_breakdown_pills(&score.breakdown);
+        let mut factors: Vec<_> = score.breakdown.iter().collect();
+        factors.sort_by_key(|f| std::cmp::Reverse(f.points));
+        for factor in factors {
+            kv(t, &factor.label, &format!("+{}", factor.points));
+        }
     }
-    blank();
-    render_vibe_checks(project, git);
-    blank();
+    blank(t);
+    render_vibe_checks(t, project, git);
+    blank(t);
 
     // ── Score ──
-    score_line(&score.grade, score.points);
-    blank();
-    roast_line(&score.roast);
+    score_line(
+        t,
+        &format!("VIBE SCORE: {} ({}pts)", score.grade, score.points),
+    );
+    blank(t);
+    roast_line(t, &score.roast);
+
+    blank(t);
+    border_bot(t);
+    t.println(String::new());
+}
+
+/// "active for N months" from the first to the last counted commit
+/// (days under a month), or None without commits.
+fn active_span(git: &GitStats) -> Option<String> {
+    let (first, last) = (git.first_commit_date?, git.last_commit_date?);
+    let mut months = (last.year() - first.year()) * 12 + last.month() as i32 - first.month() as i32;
+    if last.day() < first.day() {
+        months -= 1;
+    }
+    let (n, unit) = if months >= 1 {
+        (months as i64, "month")
+    } else {
+        ((last - first).num_days().max(1), "day")
+    };
+    let plural = if n == 1 { "" } else { "s" };
+    Some(format!("active for {} {}{}", n, unit, plural))
+}
+
+/// Render the code-health report (--quality): the factors that earned
+/// points, the vibe checks, and the 0-100 quality grade.
+pub fn render_quality(
+    git: &GitStats,
+    project: &ProjectStats,
+    quality: &QualityScore,
+    repo_name: &str,
+    options: &RenderOptions,
+) {
+    let t = &Term::new(options);
+    t.println(String::new());
+    border_top(t);
+    blank(t);
+
+    center_bold(t, "QUALITY REPORT");
+    center_dimmed(t, repo_name);
+    blank(t);
+    separator(t);
+    blank(t);
+
+    section(t, "HEALTH BREAKDOWN");
+    let mut factors: Vec<_> = quality.breakdown.iter().collect();
+    factors.sort_by_key(|f| std::cmp::Reverse(f.points));
+    for factor in factors {
+        kv(t, &factor.label, &format!("+{}", factor.points));
+    }
+    blank(t);
+    render_vibe_checks(t, project, git);
+    blank(t);
+
+    score_line(
+        t,
+        &format!("QUALITY SCORE: {} ({}/100)", quality.grade, quality.points),
+    );
+    blank(t);
+    roast_line(t, &quality.roast);
+
+    blank(t);
+    border_bot(t);
+    t.println(String::new());
+}
+
+/// Render the report as a single line (`--oneline`) for shell prompts and CI
+/// statuses: `repo: 62% AI · B+ · 4.2K LOC · "roast"`.
+pub fn render_oneline(
+    project: &ProjectStats,
+    score: &VibeScore,
+    repo_name: &str,
+    options: &RenderOptions,
+) {
+    Term::new(options).println(oneline(repo_name, score, project.languages.code_lines));
+}
+
+fn oneline(repo_name: &str, score: &VibeScore, code_lines: usize) -> String {
+    let sep = " \u{00B7} ".dimmed().to_string();
+    format!(
+        "{}: {}{}{}{}{} LOC{}{}",
+        repo_name.bold(),
+        format!("{:.0}% AI", score.ai_ratio * 100.0).cyan(),
+        sep,
+        score.grade.bold().yellow(),
+        sep,
+        fmt_num(code_lines),
+        sep,
+        format!("\"{}\"", score.roast).italic().dimmed(),
+    )
+}
+
+/// List the commits classified as AI (`--verbose`), newest first, so a
+/// false positive in `detect_ai_tool` can be traced to its commit.
+pub fn render_ai_commits(git: &GitStats, options: &RenderOptions) {
+    let t = &Term::new(options);
+    for line in ai_commit_lines(git, t.width) {
+        t.println(line);
+    }
+    t.println(String::new());
+}
+
+/// Table lines as wide as the report box (`width` + borders and margin).
+fn ai_commit_lines(git: &GitStats, width: usize) -> Vec<String> {
+    let ai: Vec<_> = git
+        .commits
+        .iter()
+        .filter(|c| c.ai_tool != AiTool::Human)
+        .collect();
+    if ai.is_empty() {
+        return vec![format!("  {}", "No commits classified as AI.".dimmed())];
+    }
+
+    let mut lines = vec![format!(
+        "  {} {}",
+        "AI COMMITS".bold().cyan(),
+        format!("({} of {})", ai.len(), git.total_commits).dimmed()
+    )];
+    let tools: Vec<String> = ai.iter().map(|c| c.ai_tool.to_string()).collect();
+    let tool_w = tools.iter().map(|t| display_width(t)).max().unwrap_or(0);
+    let subject_w = (width + 4).saturating_sub(2 + 8 + 2 + tool_w + 2).max(10);
+    for (commit, tool) in ai.iter().zip(&tools) {
+        lines.push(format!(
+            "  {:<8}  {}{}  {}",
+            commit.hash.yellow(),
+            tool.cyan(),
+            " ".repeat(tool_w - display_width(tool)),
+            truncate_to_width(&commit.message, subject_w),
+        ));
+    }
+    lines
+}
 
-    blank();
-    border_bot();
-    println!();
+/// Cut `s` to at most `max` display columns, ending with "…" when shortened.
+fn truncate_to_width(s: &str, max: usize) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut w = 0;
+    for ch in s.chars() {
+        let cw = display_width(ch.encode_utf8(&mut [0; 4]));
+        if w + cw + 1 > max {
+            break;
+        }
+        out.push(ch);
+        w += cw;
+    }
+    out.push('\u{2026}');
+    out
 }
 
+/// Print a repo's score over its recorded scans (`--history`), oldest first.
+pub fn render_history(repo_name: &str, scans: &[&Entry], options: &RenderOptions) {
+    let t = &Term::new(options);
+    t.println(String::new());
+    for line in history_lines(repo_name, scans) {
+        t.println(line);
+    }
+    t.println(String::new());
+}
+
+/// Header with a sparkline of the scores, then one row per scan with the
+/// change since the previous one.
+fn history_lines(repo_name: &str, scans: &[&Entry]) -> Vec<String> {
+    let plural = if scans.len() == 1 { "" } else { "s" };
+    let mut lines = vec![format!(
+        "  {} {} {}",
+        "HISTORY".bold().cyan(),
+        repo_name.bold(),
+        format!("({} scan{})", scans.len(), plural).dimmed()
+    )];
+    let (min, max) = scans.iter().fold((u32::MAX, 0), |(lo, hi), e| {
+        (lo.min(e.points), hi.max(e.points))
+    });
+    if scans.len() > 1 {
+        let spark: String = scans
+            .iter()
+            .map(|e| {
+                let level = (e.points - min) as usize * 7 / (max - min).max(1) as usize;
+                SPARK_LEVELS[level]
+            })
+            .collect();
+        lines.push(format!("  {}", spark.cyan()));
+    }
+
+    let mut previous = None;
+    for scan in scans {
+        let mut line = format!(
+            "  {}  {:>3} {:<2}  {:>4} AI  {:>6} commits",
+            scan.scanned_at.format("%Y-%m-%d %H:%M").dimmed(),
+            scan.points.bold(),
+            scan.grade.yellow(),
+            format!("{:.0}%", scan.ai_ratio * 100.0),
+            fmt_num(scan.commits),
+        );
+        if let Some(p) = previous {
+            let delta = fmt_delta(scan.points as i64 - p as i64, "pt");
+            line.push_str(&format!("  {}", delta.dimmed()));
+        }
+        lines.push(line);
+        previous = Some(scan.points);
+    }
+    lines
+}
+
+/// Sparkline glyphs, lowest to highest.
+const SPARK_LEVELS: [char; 8] = [
+    '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}',
+];
+
 /// Render a full vibe report (without explicit repo name).
 #[allow(dead_code)]
 pub fn render(git: &GitStats, project: &ProjectStats, score: &VibeScore) {
-    render_with_name(git, project, score, "");
+    render_with_name(git, project, score, "", &RenderOptions::default());
 }
 
 /// Render a multi-repo summary table.
-pub fn render_multi(report: &crate::scanner::multi_report::MultiReport) {
-    println!();
-    println!(
+pub fn render_multi(report: &crate::scanner::multi_report::MultiReport, options: &RenderOptions) {
+    let t = &Term::new(options);
+    t.println(String::new());
+    t.println(format!(
         "  {} {}",
         "YOUR DEV LIFE — Vibe Report".bold().white(),
         format!("({} repos)", report.repos.len()).dimmed()
-    );
-    println!();
+    ));
+    t.println(String::new());
 
     // Column headers
-    println!(
+    t.println(format!(
         "  {:<25} {:>5}  {:>5}  {}",
         "REPO".dimmed(),
         "AI%".dimmed(),
         "SCORE".dimmed(),
         "ROAST".dimmed()
-    );
-    println!("  {}", "\u{2500}".repeat(70).bright_black());
-
-    // Sort repos by score descending
-    let mut sorted: Vec<_> = report.repos.iter().collect();
-    sorted.sort_by(|a, b| b.score.points.cmp(&a.score.points));
+    ));
+    t.println(format!("  {}", "\u{2500}".repeat(70).bright_black()));
 
-    for repo in &sorted {
+    for repo in report.sorted(options.sort, options.reverse) {
         let ai_pct = format!("{:.0}%", repo.score.ai_ratio * 100.0);
         let grade = &repo.score.grade;
         let roast_short = if repo.score.roast.chars().count() > 35 {
@@ -156,27 +519,213 @@ pub fn render_multi(report: &crate::scanner::multi_report::MultiReport) {
         } else {
             format!("\"{}\"", repo.score.roast)
         };
-        println!(
+        t.println(format!(
             "  {:<25} {:>5}  {:>5}  {}",
             repo.name.white().bold(),
             ai_pct.cyan(),
             grade.yellow().bold(),
             roast_short.dimmed()
-        );
+        ));
+    }
+
+    // Global languages, across every repo
+    if !report.languages.is_empty() {
+        let total: usize = report.languages.values().sum();
+        let (langs, other) = top_languages(&report.languages, options.top_languages);
+        t.println(String::new());
+        t.println(format!("  {}", "GLOBAL LANGUAGES".bold().cyan()));
+        for (lang, lines) in langs {
+            multi_lang_row(t, lang, lines, total);
+        }
+        if other > 0 {
+            multi_lang_row(t, "Other", other, total);
+        }
+    }
+
+    // Global AI tools, across every repo
+    if !report.ai_tools.is_empty() {
+        t.println(String::new());
+        t.println(format!("  {}", "GLOBAL AI TOOLS".bold().cyan()));
+        for (tool, commits) in &report.ai_tools {
+            let pct = *commits as f64 / report.total_commits.max(1) as f64 * 100.0;
+            let name = tool.to_string();
+            let pad = 16_usize.saturating_sub(display_width(&name));
+            t.println(format!(
+                "  {}{} {}  {}",
+                name.white(),
+                " ".repeat(pad),
+                format!("{:>5.1}%", pct).yellow(),
+                format!(
+                    "{} commit{}",
+                    fmt_num(*commits),
+                    if *commits == 1 { "" } else { "s" }
+                )
+                .dimmed()
+            ));
+        }
     }
 
     // Global summary
-    println!();
-    println!("  {}", "\u{2500}".repeat(70).bright_black());
+    t.println(String::new());
+    t.println(format!("  {}", "\u{2500}".repeat(70).bright_black()));
+    let weighted = report.weighted_score(options.weight_by);
     let global_summary = format!(
-        "GLOBAL: {:.0}% AI | {} lines | Avg Score: {} ({})",
+        "GLOBAL: {:.0}% AI | {} lines | Avg Score: {} ({}) | By {}: {} ({})",
         report.global_ai_ratio * 100.0,
         fmt_num(report.total_lines),
         grade_from_points(report.average_score),
-        report.average_score
+        report.average_score,
+        options.weight_by.as_str(),
+        grade_from_points(weighted),
+        weighted
+    );
+    t.println(format!("  {}", global_summary.bold().white()));
+    t.println(String::new());
+}
+
+/// Unboxed language row for `render_multi`: name, bar, share and line count.
+fn multi_lang_row(t: &Term, lang: &str, lines: usize, total: usize) {
+    let pct = lines as f64 / total.max(1) as f64 * 100.0;
+    let bar_w = 20_usize;
+    let filled = ((pct / 100.0) * bar_w as f64).round() as usize;
+    let pad = 14_usize.saturating_sub(display_width(lang));
+    t.println(format!(
+        "  {}{} {}{} {:>5.1}%  {}",
+        lang.white(),
+        " ".repeat(pad),
+        "\u{2588}".repeat(filled).cyan(),
+        "\u{2591}".repeat(bar_w - filled).bright_black(),
+        pct,
+        format!("{} lines", fmt_num(lines)).dimmed()
+    ));
+}
+
+/// One side of a `--compare` run: the commits in that window and their score.
+pub struct DiffSide<'a> {
+    pub git: &'a GitStats,
+    pub score: &'a VibeScore,
+}
+
+/// Render the before/after deltas of a `--compare` run.
+/// Increases are red, decreases green, unchanged values dimmed.
+pub fn render_diff(
+    before: &DiffSide,
+    after: &DiffSide,
+    repo_name: &str,
+    pivot: &str,
+    options: &RenderOptions,
+) {
+    let t = &Term::new(options);
+    t.println(String::new());
+    border_top(t);
+    blank(t);
+
+    // ── Title ──
+    center_bold(t, "VIBE DIFF");
+    center_dimmed(t, &format!("{}  before vs. since {}", repo_name, pivot));
+    blank(t);
+    separator(t);
+    blank(t);
+
+    // ── Headline numbers ──
+    let pct = |r: f64| (r * 100.0).round() as i64;
+    let (ai_before, ai_after) = (pct(before.score.ai_ratio), pct(after.score.ai_ratio));
+    diff_row(
+        t,
+        3,
+        "AI-authored",
+        &format!("{}%", ai_before),
+        &format!("{}%", ai_after),
+        ai_after - ai_before,
+        "pt",
     );
-    println!("  {}", global_summary.bold().white());
-    println!();
+    let (c_before, c_after) = (before.git.total_commits, after.git.total_commits);
+    diff_row(
+        t,
+        3,
+        "Commits",
+        &c_before.to_string(),
+        &c_after.to_string(),
+        c_after as i64 - c_before as i64,
+        "",
+    );
+    let (a_before, a_after) = (before.git.ai_commits, after.git.ai_commits);
+    diff_row(
+        t,
+        3,
+        "AI commits",
+        &a_before.to_string(),
+        &a_after.to_string(),
+        a_after as i64 - a_before as i64,
+        "",
+    );
+    diff_row(
+        t,
+        3,
+        "Vibe score",
+        &format!("{} ({})", before.score.grade, before.score.points),
+        &format!("{} ({})", after.score.grade, after.score.points),
+        after.score.points as i64 - before.score.points as i64,
+        "",
+    );
+
+    // ── Per-tool shifts ──
+    let shifts = tool_shifts(before.git, after.git);
+    if !shifts.is_empty() {
+        blank(t);
+        section(t, "AI TOOLS");
+        for (tool, b, a) in &shifts {
+            diff_row(
+                t,
+                5,
+                tool,
+                &b.to_string(),
+                &a.to_string(),
+                *a as i64 - *b as i64,
+                "",
+            );
+        }
+    }
+
+    blank(t);
+    border_bot(t);
+    t.println(String::new());
+}
+
+/// Commit counts per AI tool in both windows (missing = 0), busiest "after" first.
+fn tool_shifts(before: &GitStats, after: &GitStats) -> Vec<(String, usize, usize)> {
+    let count = |git: &GitStats, name: &str| {
+        git.ai_tools
+            .iter()
+            .find(|(tool, _)| tool.to_string() == name)
+            .map_or(0, |(_, n)| *n)
+    };
+    let mut names: Vec<String> = before
+        .ai_tools
+        .iter()
+        .chain(after.ai_tools.iter())
+        .map(|(tool, _)| tool.to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    let mut shifts: Vec<_> = names
+        .into_iter()
+        .map(|name| {
+            let (b, a) = (count(before, &name), count(after, &name));
+            (name, b, a)
+        })
+        .collect();
+    shifts.sort_by_key(|(_, b, a)| std::cmp::Reverse((*a, *b)));
+    shifts
+}
+
+/// "+12pt", "-3", or "=" when unchanged.
+fn fmt_delta(delta: i64, unit: &str) -> String {
+    if delta == 0 {
+        "=".to_string()
+    } else {
+        format!("{:+}{}", delta, unit)
+    }
 }
 
 /// Convert numeric points to a letter grade.
@@ -250,84 +799,84 @@ fn display_width(s: &str) -> usize {
 //  Low-level line builders
 //
 //  Every line is exactly:
-//     "  " + border_left + <W display-columns of content> + border_right
+//     "  " + border_left + <t.width display-columns of content> + border_right
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
-fn border_top() {
-    println!(
+fn border_top(t: &Term) {
+    t.println(format!(
         "  {}",
-        format!("\u{256D}{}\u{256E}", "\u{2500}".repeat(W)).cyan()
-    );
+        format!("\u{256D}{}\u{256E}", "\u{2500}".repeat(t.width)).cyan()
+    ));
 }
 
-fn border_bot() {
-    println!(
+fn border_bot(t: &Term) {
+    t.println(format!(
         "  {}",
-        format!("\u{2570}{}\u{256F}", "\u{2500}".repeat(W)).cyan()
-    );
+        format!("\u{2570}{}\u{256F}", "\u{2500}".repeat(t.width)).cyan()
+    ));
 }
 
-fn separator() {
-    println!(
+fn separator(t: &Term) {
+    t.println(format!(
         "  {}",
-        format!("\u{251C}{}\u{2524}", "\u{2500}".repeat(W)).cyan()
-    );
+        format!("\u{251C}{}\u{2524}", "\u{2500}".repeat(t.width)).cyan()
+    ));
 }
 
-fn blank() {
-    println!(
+fn blank(t: &Term) {
+    t.println(format!(
         "  {}{}{}",
         "\u{2502}".cyan(),
-        " ".repeat(W),
+        " ".repeat(t.width),
         "\u{2502}".cyan()
-    );
+    ));
 }
 
 // ── Content line builders ─────────────────────────────────────────
 
-fn center_bold(text: &str) {
+fn center_bold(t: &Term, text: &str) {
     let dw = display_width(text);
-    let lp = (W.saturating_sub(dw)) / 2;
-    let rp = W.saturating_sub(dw).saturating_sub(lp);
-    println!(
+    let lp = (t.width.saturating_sub(dw)) / 2;
+    let rp = t.width.saturating_sub(dw).saturating_sub(lp);
+    t.println(format!(
         "  {}{}{}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(lp),
         text.bold().white(),
         " ".repeat(rp),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
-fn center_dimmed(text: &str) {
+fn center_dimmed(t: &Term, text: &str) {
     let dw = display_width(text);
-    let lp = (W.saturating_sub(dw)) / 2;
-    let rp = W.saturating_sub(dw).saturating_sub(lp);
-    println!(
+    let lp = (t.width.saturating_sub(dw)) / 2;
+    let rp = t.width.saturating_sub(dw).saturating_sub(lp);
+    t.println(format!(
         "  {}{}{}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(lp),
         text.dimmed(),
         " ".repeat(rp),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
-fn section(label: &str) {
+fn section(t: &Term, label: &str) {
     // Display: "   -- LABEL"
     let prefix_display = "   \u{2500}\u{2500} ";
     let dw = display_width(prefix_display) + display_width(label);
-    let rp = W.saturating_sub(dw);
-    println!(
+    let rp = t.width.saturating_sub(dw);
+    t.println(format!(
         "  {}{}{}{}",
         "\u{2502}".cyan(),
         format!("   \u{2500}\u{2500} {}", label).cyan().bold(),
         " ".repeat(rp),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
-fn kv(label: &str, value: &str) {
+fn kv(t: &Term, label: &str, value: &str) {
     // Layout: "   {label}  {dots}  {value}  "
     //          ^3          ^2      ^2       ^2 = margins
     let ml = 3_usize;
@@ -336,9 +885,9 @@ fn kv(label: &str, value: &str) {
     let label_w = display_width(label);
     let value_w = display_width(value);
     let fixed = ml + label_w + gap + gap + value_w + mr;
-    let ndots = W.saturating_sub(fixed).max(1);
+    let ndots = t.width.saturating_sub(fixed).max(1);
 
-    println!(
+    t.println(format!(
         "  {}{}{}  {}  {}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(ml),
@@ -347,19 +896,19 @@ fn kv(label: &str, value: &str) {
         value.white().bold(),
         " ".repeat(mr),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
-fn kv_indent(label: &str, value: &str) {
+fn kv_indent(t: &Term, label: &str, value: &str) {
     let ml = 5_usize;
     let mr = 2_usize;
     let gap = 2_usize;
     let label_w = display_width(label);
     let value_w = display_width(value);
     let fixed = ml + label_w + gap + gap + value_w + mr;
-    let ndots = W.saturating_sub(fixed).max(1);
+    let ndots = t.width.saturating_sub(fixed).max(1);
 
-    println!(
+    t.println(format!(
         "  {}{}{}  {}  {}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(ml),
@@ -368,21 +917,55 @@ fn kv_indent(label: &str, value: &str) {
         value.white().bold(),
         " ".repeat(mr),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
-fn lang_row(lang: &str, pct: f64) {
-    // Layout: "     {lang:<14} {bar:12} {pct:>6}  "
+fn diff_row(t: &Term, ml: usize, label: &str, from: &str, to: &str, delta: i64, unit: &str) {
+    // Layout: "   {label}  {dots}  {from} → {to}  {delta}  "
+    let mr = 2_usize;
+    let gap = 2_usize;
+    let delta_str = fmt_delta(delta, unit);
+    let change = format!("{} \u{2192} {}", from, to);
+    let fixed = ml
+        + display_width(label)
+        + gap
+        + gap
+        + display_width(&change)
+        + gap
+        + display_width(&delta_str)
+        + mr;
+    let ndots = t.width.saturating_sub(fixed).max(1);
+    let delta_colored = match delta.signum() {
+        1 => delta_str.red().bold().to_string(),
+        -1 => delta_str.green().bold().to_string(),
+        _ => delta_str.dimmed().to_string(),
+    };
+
+    t.println(format!(
+        "  {}{}{}  {}  {}  {}{}{}",
+        "\u{2502}".cyan(),
+        " ".repeat(ml),
+        label.dimmed(),
+        ".".repeat(ndots).bright_black(),
+        change.white().bold(),
+        delta_colored,
+        " ".repeat(mr),
+        "\u{2502}".cyan(),
+    ));
+}
+
+fn lang_row(t: &Term, lang: &str, pct: f64) {
+    // Layout: "     {lang:<14} {bar} {pct:>6}  " — bar is 12 cols at the default width
     let ml = 5_usize;
     let mr = 2_usize;
     let lang_col = 14_usize;
-    let bar_w = 12_usize;
+    let bar_w = t.width.saturating_sub(MIN_WIDTH).max(6);
     let pct_str = format!("{:>5.1}%", pct);
     let pct_w = pct_str.len(); // ASCII, so len == display width
 
     // Total used display columns
     let used = ml + lang_col + 1 + bar_w + 1 + pct_w + mr;
-    let extra = W.saturating_sub(used);
+    let extra = t.width.saturating_sub(used);
 
     let filled = ((pct / 100.0) * bar_w as f64).round() as usize;
     let empty_count = bar_w.saturating_sub(filled);
@@ -393,7 +976,7 @@ fn lang_row(lang: &str, pct: f64) {
     let lang_dw = display_width(lang);
     let lang_pad = lang_col.saturating_sub(lang_dw);
 
-    println!(
+    t.println(format!(
         "  {}{}{}{} {}{} {}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(ml),
@@ -404,61 +987,72 @@ fn lang_row(lang: &str, pct: f64) {
         pct_str.dimmed(),
         " ".repeat(mr + extra),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
-fn score_line(grade: &str, points: u32) {
-    let text = format!("VIBE SCORE: {} ({}pts)", grade, points);
-    let dw = display_width(&text);
-    let lp = (W.saturating_sub(dw)) / 2;
-    let rp = W.saturating_sub(dw).saturating_sub(lp);
-    println!(
+fn score_line(t: &Term, text: &str) {
+    let dw = display_width(text);
+    let lp = (t.width.saturating_sub(dw)) / 2;
+    let rp = t.width.saturating_sub(dw).saturating_sub(lp);
+    t.println(format!(
         "  {}{}{}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(lp),
         text.bold().yellow(),
         " ".repeat(rp),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
-fn roast_line(roast: &str) {
+fn roast_line(t: &Term, roast: &str) {
     let text = format!("\"{}\"", roast);
     let dw = display_width(&text);
-    let lp = (W.saturating_sub(dw)) / 2;
-    let rp = W.saturating_sub(dw).saturating_sub(lp);
-    println!(
+    let lp = (t.width.saturating_sub(dw)) / 2;
+    let rp = t.width.saturating_sub(dw).saturating_sub(lp);
+    t.println(format!(
         "  {}{}{}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(lp),
         text.italic().dimmed(),
         " ".repeat(rp),
         "\u{2502}".cyan(),
-    );
+    ));
 }
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 //  Vibe Check (chaos badges)
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
-fn render_vibe_checks(project: &ProjectStats, git: &GitStats) {
-    let checks: Vec<(bool, &str)> = vec![
+fn render_vibe_checks(t: &Term, project: &ProjectStats, git: &GitStats) {
+    let mut checks: Vec<(bool, &str)> = vec![
         (project.tests.has_tests, "Tests"),
         (!project.vibe.no_linting, "Linting"),
         (!project.vibe.no_ci_cd, "CI/CD"),
         (!project.vibe.no_gitignore, ".gitignore"),
         (!project.vibe.no_readme, "README"),
-        (!project.security.env_in_git, "No .env leaked"),
+        (project.license.is_some(), "License"),
+        (
+            !project.security.env_in_git && !project.security.env_in_history,
+            "No .env leaked",
+        ),
         (project.security.hardcoded_secrets_hints == 0, "No secrets"),
+        (project.security.private_keys_count == 0, "No private keys"),
         (!project.vibe.node_modules_in_git, "Clean vendor"),
-        (!project.vibe.mega_commit, "No mega commit"),
-        (!project.vibe.single_branch, "Multiple branches"),
-        (!(git.ai_ratio > 0.0 && project.vibe.boomer_ai), "AI config"),
     ];
+    // Without history these would all pass vacuously
+    if !git.no_git {
+        checks.extend([
+            (!project.vibe.mega_commit, "No mega commit"),
+            (!project.vibe.single_branch, "Multiple branches"),
+            (!project.vibe.low_effort_commits, "Commit messages"),
+            (!(git.ai_ratio > 0.0 && project.vibe.boomer_ai), "AI config"),
+        ]);
+    }
 
     // Render as two columns
     let ml = 3_usize;
-    let col_w = 24_usize;
+    // Half the row, but keep room for the longest right-hand label + a space
+    let col_w = (t.width.saturating_sub(ml) / 2).min(t.width.saturating_sub(ml + 20));
 
     let mut i = 0;
     while i < checks.len() {
@@ -477,14 +1071,14 @@ fn render_vibe_checks(project: &ProjectStats, git: &GitStats) {
             .unwrap_or(0);
 
         let total = ml + col1_dw + col1_pad + col2_dw;
-        let rp = W.saturating_sub(total);
+        let rp = t.width.saturating_sub(total);
 
         let col1_colored = check_item(ok1, label1);
         let col2_colored = col2_info
             .map(|(ok, label)| check_item(ok, label))
             .unwrap_or_default();
 
-        println!(
+        t.println(format!(
             "  {}{}{}{}{}{}{}",
             "\u{2502}".cyan(),
             " ".repeat(ml),
@@ -493,7 +1087,7 @@ fn render_vibe_checks(project: &ProjectStats, git: &GitStats) {
             col2_colored,
             " ".repeat(rp),
             "\u{2502}".cyan(),
-        );
+        ));
 
         i += 2;
     }
@@ -518,12 +1112,15 @@ const MONTH_NAMES: [&str; 12] = [
 /// Number of rows in the bar chart (0%, 20%, 40%, 60%, 80%, 100%).
 const CHART_ROWS: usize = 6;
 
-/// Maximum number of months to display (latest N if more data).
-const MAX_MONTHS: usize = 12;
+/// Chart prefix: "  100% │ " = 10 display columns (7 label + 1 space + 1 axis + 1 space).
+const CHART_PREFIX_W: usize = 10;
+
+/// Columns kept free right of the bars (12 bars fit at the default width).
+const CHART_RIGHT_MARGIN: usize = 6;
 
-/// Render a vertical bar chart of AI% per month inside the box.
+/// Render a vertical bar chart of AI% per day/week/month inside the box.
 ///
-/// Layout within W=52 content columns:
+/// Layout within t.width content columns (52 by default):
 ///
 ///   "  100% │ ██ ██ ██ ██ ██ ██                    "
 ///    ^^     ^ ^                                     ^
@@ -532,37 +1129,37 @@ const MAX_MONTHS: usize = 12;
 /// Y-axis label: 6 chars right-aligned ("  100%")
 /// Separator: " │ " = 3 chars
 /// Prefix total: 9 display columns
-/// Bars area: up to MAX_MONTHS * 3 chars
-/// Right padding fills the rest to W.
-fn render_timeline_chart(timeline: &[MonthlyStats]) {
-    // Take at most MAX_MONTHS (latest months).
-    let months: &[MonthlyStats] = if timeline.len() > MAX_MONTHS {
-        &timeline[timeline.len() - MAX_MONTHS..]
+/// Bars area: as many 3-char bars as fit (latest buckets win)
+/// Right padding fills the rest to t.width.
+fn render_timeline_chart(t: &Term, timeline: &[TimelineBucket], granularity: Granularity) {
+    let max_bars = t.width.saturating_sub(CHART_PREFIX_W + CHART_RIGHT_MARGIN) / 3;
+    let months: &[TimelineBucket] = if timeline.len() > max_bars {
+        &timeline[timeline.len() - max_bars..]
     } else {
         timeline
     };
 
     let n = months.len();
-    // prefix = "  100% │ " = 10 display columns (7 label + 1 space + 1 axis + 1 space)
-    let prefix_w: usize = 10;
+    let prefix_w = CHART_PREFIX_W;
     let bars_w: usize = n * 3; // each bar = "██ " (3 cols), last one has trailing space too
     let total_content = prefix_w + bars_w;
-    let right_pad = W.saturating_sub(total_content);
+    let right_pad = t.width.saturating_sub(total_content);
 
-    section("TIMELINE");
+    section(t, "TIMELINE");
     // Subtitle explaining what the chart shows
-    let subtitle = "AI-authored commits % per month";
+    let subtitle = format!("AI-authored commits % per {}", granularity.unit());
+    let subtitle = subtitle.as_str();
     let sub_ml = 5_usize;
     let sub_dw = sub_ml + display_width(subtitle);
-    let sub_rp = W.saturating_sub(sub_dw);
-    println!(
+    let sub_rp = t.width.saturating_sub(sub_dw);
+    t.println(format!(
         "  {}{}{}{}{}",
         "\u{2502}".cyan(),
         " ".repeat(sub_ml),
         subtitle.dimmed(),
         " ".repeat(sub_rp),
         "\u{2502}".cyan(),
-    );
+    ));
 
     // Y-axis thresholds: 100, 80, 60, 40, 20, 0
     for row in 0..CHART_ROWS {
@@ -590,7 +1187,7 @@ fn render_timeline_chart(timeline: &[MonthlyStats]) {
 
         let bars_str = bar_segments.concat();
 
-        println!(
+        t.println(format!(
             "  {}{} {} {}{}{}",
             "\u{2502}".cyan(),
             format!("  {}", label).dimmed(),
@@ -598,7 +1195,7 @@ fn render_timeline_chart(timeline: &[MonthlyStats]) {
             bars_str,
             " ".repeat(right_pad),
             "\u{2502}".cyan(),
-        );
+        ));
     }
 
     // X-axis line: "        └─────..."
@@ -607,94 +1204,36 @@ fn render_timeline_chart(timeline: &[MonthlyStats]) {
     let axis_prefix = "        \u{2514}";
     let axis_dashes = "\u{2500}".repeat(axis_line_w);
     let axis_dw = display_width(axis_prefix) + axis_line_w;
-    let axis_rp = W.saturating_sub(axis_dw);
-    println!(
+    let axis_rp = t.width.saturating_sub(axis_dw);
+    t.println(format!(
         "  {}{}{}{}",
         "\u{2502}".cyan(),
         format!("{}{}", axis_prefix, axis_dashes).bright_black(),
         " ".repeat(axis_rp),
         "\u{2502}".cyan(),
-    );
+    ));
 
     // Month labels row
     let mut labels = String::new();
     for m in months {
-        let name = MONTH_NAMES[(m.month as usize).saturating_sub(1).min(11)];
+        let name = match granularity {
+            Granularity::Day => format!("{:02}", m.start.day()),
+            Granularity::Week => format!("{:02}", m.start.iso_week().week()),
+            Granularity::Month => MONTH_NAMES[m.start.month0() as usize].to_string(),
+        };
         labels.push_str(&format!("{:<3}", name));
     }
     let labels_prefix = "         "; // 9 spaces to align under bars
     let labels_dw = display_width(labels_prefix) + display_width(&labels);
-    let labels_rp = W.saturating_sub(labels_dw);
-    println!(
+    let labels_rp = t.width.saturating_sub(labels_dw);
+    t.println(format!(
         "  {}{}{}{}{}",
         "\u{2502}".cyan(),
         labels_prefix,
         labels.dimmed(),
         " ".repeat(labels_rp),
         "\u{2502}".cyan(),
-    );
-}
-
-// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-//  Score breakdown pills
-// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-
-fn render_breakdown_pills(factors: &[crate::score::calculator::ScoreFactor]) {
-    let ml = 3_usize; // left margin
-    let gap = 2_usize; // gap between pills
-
-    let mut line_pills: Vec<String> = Vec::new();
-    let mut line_width: usize = ml;
-
-    for factor in factors {
-        let pill = format!("{} +{}", factor.label, factor.points);
-        let pill_dw = display_width(&pill);
-
-        // Check if pill fits on current line
-        let needed = if line_pills.is_empty() {
-            pill_dw
-        } else {
-            gap + pill_dw
-        };
-
-        if line_width + needed > W && !line_pills.is_empty() {
-            // Flush current line
-            let content: String = line_pills.join("  ");
-            let content_dw = ml + display_width(&content);
-            let rp = W.saturating_sub(content_dw);
-            println!(
-                "  {}{}{}{}{}",
-                "\u{2502}".cyan(),
-                " ".repeat(ml),
-                content.yellow(),
-                " ".repeat(rp),
-                "\u{2502}".cyan(),
-            );
-            line_pills.clear();
-            line_width = ml;
-        }
-
-        if !line_pills.is_empty() {
-            line_width += gap;
-        }
-        line_width += pill_dw;
-        line_pills.push(pill);
-    }
-
-    // Flush remaining pills
-    if !line_pills.is_empty() {
-        let content: String = line_pills.join("  ");
-        let content_dw = ml + display_width(&content);
-        let rp = W.saturating_sub(content_dw);
-        println!(
-            "  {}{}{}{}{}",
-            "\u{2502}".cyan(),
-            " ".repeat(ml),
-            content.yellow(),
-            " ".repeat(rp),
-            "\u{2502}".cyan(),
-        );
-    }
+    ));
 }
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
@@ -734,6 +1273,54 @@ fn emoji_for_grade(grade: &str) -> &'static str {
 mod tests {
     use super::*;
 
+    fn span_between(first: &str, last: &str) -> Option<String> {
+        let date = |s: &str| {
+            chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        active_span(&GitStats {
+            first_commit_date: Some(date(first)),
+            last_commit_date: Some(date(last)),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn active_span_counts_whole_months() {
+        assert_eq!(
+            span_between("2025-01-15", "2025-04-15").unwrap(),
+            "active for 3 months"
+        );
+        assert_eq!(
+            span_between("2025-01-15", "2025-04-14").unwrap(),
+            "active for 2 months"
+        );
+        assert_eq!(
+            span_between("2024-11-01", "2025-12-01").unwrap(),
+            "active for 13 months"
+        );
+        assert_eq!(
+            span_between("2025-01-31", "2025-03-01").unwrap(),
+            "active for 1 month"
+        );
+    }
+
+    #[test]
+    fn active_span_under_a_month_counts_days() {
+        assert_eq!(
+            span_between("2025-06-01", "2025-06-20").unwrap(),
+            "active for 19 days"
+        );
+        assert_eq!(
+            span_between("2025-06-01", "2025-06-01").unwrap(),
+            "active for 1 day"
+        );
+        assert_eq!(active_span(&GitStats::default()), None);
+    }
+
     #[test]
     fn fmt_num_works() {
         assert_eq!(fmt_num(0), "0");
@@ -751,6 +1338,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn strip_ansi_removes_styles_only() {
+        let styled = format!("{}{}", "\u{2502}".cyan(), "VIBE".bold().white());
+        assert_eq!(strip_ansi(&styled), "\u{2502}VIBE");
+        assert_eq!(
+            strip_ansi("plain \u{2588}\u{2588}"),
+            "plain \u{2588}\u{2588}"
+        );
+        assert_eq!(
+            display_width(&strip_ansi(&styled)),
+            display_width("\u{2502}VIBE")
+        );
+    }
+
+    #[test]
+    fn oneline_summary() {
+        let score = VibeScore {
+            grade: "B+".into(),
+            points: 67,
+            roast: "Solid vibe-to-code ratio.".into(),
+            ai_ratio: 0.62,
+            breakdown: vec![],
+        };
+        let line = oneline("repo", &score, 4200);
+        assert_eq!(
+            strip_ansi(&line),
+            "repo: 62% AI \u{00B7} B+ \u{00B7} 4.2K LOC \u{00B7} \"Solid vibe-to-code ratio.\""
+        );
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn ai_commit_table_lists_only_ai_commits() {
+        let commit = |hash: &str, tool: AiTool, message: &str| crate::git::parser::CommitInfo {
+            hash: hash.into(),
+            message: message.into(),
+            author: "dev".into(),
+            timestamp: chrono::Utc::now().fixed_offset(),
+            ai_tool: tool,
+            lines_added: 0,
+            lines_removed: 0,
+        };
+        let git = GitStats {
+            total_commits: 3,
+            commits: vec![
+                commit("aaaa1111", AiTool::Cursor, "Add login"),
+                commit("bbbb2222", AiTool::Human, "Fix typo"),
+                commit("cccc3333", AiTool::ClaudeCode, &"x".repeat(100)),
+            ],
+            ..Default::default()
+        };
+        let lines: Vec<String> = ai_commit_lines(&git, DEFAULT_WIDTH)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert_eq!(lines[0], "  AI COMMITS (2 of 3)");
+        assert_eq!(lines[1], "  aaaa1111  Cursor       Add login");
+        assert!(lines[2].starts_with("  cccc3333  Claude Code  xxx"));
+        assert!(lines[2].ends_with('\u{2026}'));
+        assert_eq!(display_width(&lines[2]), DEFAULT_WIDTH + 4);
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn ai_commit_table_without_ai_commits() {
+        let lines = ai_commit_lines(&GitStats::default(), DEFAULT_WIDTH);
+        assert_eq!(strip_ansi(&lines[0]), "  No commits classified as AI.");
+    }
+
+    #[test]
+    fn fmt_delta_signs() {
+        assert_eq!(fmt_delta(12, "pt"), "+12pt");
+        assert_eq!(fmt_delta(-3, ""), "-3");
+        assert_eq!(fmt_delta(0, "pt"), "=");
+    }
+
+    #[test]
+    fn tool_shifts_union_of_both_windows() {
+        let stats = |ai_tools: Vec<(AiTool, usize)>| GitStats {
+            total_commits: 10,
+            ai_commits: ai_tools.iter().map(|(_, n)| n).sum(),
+            human_commits: 0,
+            ai_ratio: 0.0,
+            ai_tools,
+            ..Default::default()
+        };
+        let before = stats(vec![(AiTool::GithubCopilot, 4)]);
+        let after = stats(vec![(AiTool::ClaudeCode, 7), (AiTool::GithubCopilot, 1)]);
+
+        assert_eq!(
+            tool_shifts(&before, &after),
+            vec![
+                ("Claude Code".to_string(), 0, 7),
+                ("GitHub Copilot".to_string(), 4, 1),
+            ]
+        );
+    }
+
     #[test]
     fn display_width_ascii() {
         assert_eq!(display_width("hello"), 5);
@@ -771,4 +1456,33 @@ mod tests {
         assert_eq!(display_width("\u{26A1}"), 2); // lightning
         assert_eq!(display_width("\u{270D}\u{FE0F}"), 2); // writing hand + VS16
     }
+
+    #[test]
+    fn history_lines_show_sparkline_and_deltas() {
+        let scan = |points: u32, day: u32| Entry {
+            repo: "me/app".to_string(),
+            scanned_at: format!("2026-03-{:02}T09:30:00Z", day).parse().unwrap(),
+            points,
+            grade: grade_from_points(points).to_string(),
+            ai_ratio: 0.25,
+            commits: 1200,
+        };
+        let scans = [scan(20, 1), scan(48, 2), scan(48, 3)];
+        let refs: Vec<&Entry> = scans.iter().collect();
+        let lines: Vec<String> = history_lines("me/app", &refs)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+
+        assert_eq!(lines[0], "  HISTORY me/app (3 scans)");
+        assert_eq!(lines[1], "  \u{2581}\u{2588}\u{2588}");
+        assert!(lines[2].starts_with("  2026-03-01 09:30   20"));
+        assert!(lines[2].contains("25% AI"));
+        assert!(lines[2].contains("1.2K commits"));
+        assert!(lines[3].ends_with("+28pt"));
+        assert!(lines[4].ends_with("="));
+
+        // A single scan has nothing to chart
+        assert_eq!(history_lines("me/app", &refs[..1]).len(), 2);
+    }
 }
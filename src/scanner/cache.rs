@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// A cached clone fetched this recently is reused without touching the network.
+pub const FRESH_FOR: Duration = Duration::from_secs(60 * 60);
+/// Clones not fetched for this long are deleted.
+const MAX_AGE: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+/// Once the cache grows past this, least recently fetched clones are deleted.
+const MAX_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Touched after every clone/fetch. Lives inside `.git` so project analysis
+/// never sees it.
+const MARKER: &str = ".git/vibereport-fetched";
+
+/// Where remote clones are cached: `$XDG_CACHE_HOME/vibereport`, falling back
+/// to `~/.cache/vibereport` (or `%LOCALAPPDATA%\vibereport` on Windows).
+pub fn cache_root() -> Option<PathBuf> {
+    let non_empty = |key: &str| std::env::var_os(key).filter(|v| !v.is_empty());
+    non_empty("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| non_empty("LOCALAPPDATA").map(PathBuf::from))
+        .map(|dir| dir.join("vibereport"))
+}
+
+/// Cache directory for one `user/repo`; branch clones get their own entry
+/// since a `--branch` shallow clone only holds that branch.
+pub fn entry_dir(root: &Path, user: &str, repo: &str, branch: Option<&str>) -> PathBuf {
+    let name = match branch {
+        Some(branch) => {
+            let safe: String = branch
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("{}@{}", repo, safe)
+        }
+        None => repo.to_string(),
+    };
+    root.join(user).join(name)
+}
+
+/// Record that `dir` was just cloned or fetched.
+pub fn mark_fetched(dir: &Path) -> std::io::Result<()> {
+    std::fs::write(dir.join(MARKER), b"")
+}
+
+fn last_fetched(dir: &Path) -> Option<SystemTime> {
+    std::fs::metadata(dir.join(MARKER)).ok()?.modified().ok()
+}
+
+/// Whether `dir` was fetched within `max_age` of `now`.
+pub fn is_fresh(dir: &Path, now: SystemTime, max_age: Duration) -> bool {
+    last_fetched(dir)
+        .and_then(|t| now.duration_since(t).ok())
+        .is_some_and(|age| age < max_age)
+}
+
+/// Keep the cache bounded: drop clones older than the max age, then the least
+/// recently fetched ones until the total fits the size limit. `keep` is the
+/// clone about to be analyzed and is never removed.
+pub fn evict(root: &Path, keep: &Path, now: SystemTime) {
+    evict_with_limits(root, keep, now, MAX_AGE, MAX_BYTES);
+}
+
+fn evict_with_limits(root: &Path, keep: &Path, now: SystemTime, max_age: Duration, max_bytes: u64) {
+    let mut entries: Vec<(SystemTime, u64, PathBuf)> = Vec::new();
+    for dir in list_entries(root) {
+        if dir == keep {
+            continue;
+        }
+        if !is_fresh(&dir, now, max_age) {
+            let _ = std::fs::remove_dir_all(&dir);
+            continue;
+        }
+        let fetched = last_fetched(&dir).unwrap_or(SystemTime::UNIX_EPOCH);
+        entries.push((fetched, dir_size(&dir), dir));
+    }
+
+    let mut total: u64 = dir_size(keep) + entries.iter().map(|(_, size, _)| size).sum::<u64>();
+    entries.sort_by_key(|(fetched, _, _)| *fetched);
+    for (_, size, dir) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+        total -= size;
+    }
+}
+
+/// Cached clones live at `<root>/<user>/<repo>`.
+fn list_entries(root: &Path) -> Vec<PathBuf> {
+    let subdirs = |dir: &Path| -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .map(|rd| {
+                rd.flatten()
+                    .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+                    .map(|e| e.path())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    subdirs(root)
+        .iter()
+        .flat_map(|user| subdirs(user))
+        .collect()
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(t) if t.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//  Tests
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// A fake cached clone with `bytes` of content, fetched at `fetched`.
+    fn fake_entry(root: &Path, name: &str, bytes: usize, fetched: SystemTime) -> PathBuf {
+        let dir = root.join("user").join(name);
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join("blob"), vec![0u8; bytes]).unwrap();
+        mark_fetched(&dir).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(dir.join(MARKER))
+            .unwrap()
+            .set_modified(fetched)
+            .unwrap();
+        dir
+    }
+
+    #[test]
+    fn entry_dir_separates_branches() {
+        let root = Path::new("/cache");
+        assert_eq!(
+            entry_dir(root, "vercel", "next.js", None),
+            Path::new("/cache/vercel/next.js")
+        );
+        assert_eq!(
+            entry_dir(root, "vercel", "next.js", Some("feat/x")),
+            Path::new("/cache/vercel/next.js@feat_x")
+        );
+    }
+
+    #[test]
+    fn freshness_follows_last_fetch() {
+        let root = TempDir::new().unwrap();
+        let now = SystemTime::now();
+        let dir = fake_entry(root.path(), "repo", 1, now - 2 * DAY);
+        assert!(is_fresh(&dir, now, 3 * DAY));
+        assert!(!is_fresh(&dir, now, DAY));
+        assert!(!is_fresh(&root.path().join("missing"), now, DAY));
+    }
+
+    #[test]
+    fn evicts_expired_entries() {
+        let root = TempDir::new().unwrap();
+        let now = SystemTime::now();
+        let old = fake_entry(root.path(), "old", 1, now - 30 * DAY);
+        let recent = fake_entry(root.path(), "recent", 1, now - DAY);
+
+        evict_with_limits(root.path(), Path::new("/nowhere"), now, 7 * DAY, u64::MAX);
+        assert!(!old.exists());
+        assert!(recent.exists());
+    }
+
+    #[test]
+    fn evicts_least_recently_fetched_over_size_limit() {
+        let root = TempDir::new().unwrap();
+        let now = SystemTime::now();
+        let oldest = fake_entry(root.path(), "a", 100, now - 3 * DAY);
+        let middle = fake_entry(root.path(), "b", 100, now - 2 * DAY);
+        let newest = fake_entry(root.path(), "c", 100, now - DAY);
+        // `keep` is the oldest but is in use, so it survives
+        evict_with_limits(root.path(), &oldest, now, 7 * DAY, 250);
+        assert!(oldest.exists());
+        assert!(!middle.exists());
+        assert!(newest.exists());
+    }
+}
@@ -1,5 +1,11 @@
+use crate::project::gitignore::GitignoreFilter;
+use crate::project::vibe_detect::is_regular_dir;
+use ignore::gitignore::GitignoreBuilder;
 use std::path::{Path, PathBuf};
 
+/// Gitignore-style patterns at the scan root, checked on top of `SKIP_DIRS`.
+pub const IGNORE_FILE: &str = ".vibereportignore";
+
 /// Directories that should be skipped during repo discovery.
 const SKIP_DIRS: &[&str] = &[
     "node_modules",
@@ -11,22 +17,104 @@ const SKIP_DIRS: &[&str] = &[
     ".next",
 ];
 
-/// Recursively find all directories containing a `.git` folder.
+/// Recursively find all directories containing a `.git` folder, or a `.git`
+/// file (submodules and worktrees point at their real git dir through one).
 /// Stops descending into a directory once a `.git` is found (doesn't look for nested repos).
-/// Skips: node_modules, target, .git, vendor, dist, build, .next, and hidden directories.
-pub fn find_git_repos(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+/// Skips: node_modules, target, .git, vendor, dist, build, .next, hidden directories,
+/// and anything matched by a `.vibereportignore` at `root`.
+/// With `skip_submodules`, repos a parent's `.gitmodules` declares
+/// are left out so they aren't counted on top of their superproject.
+pub fn find_git_repos(root: &Path, max_depth: usize, skip_submodules: bool) -> Vec<PathBuf> {
     let mut repos = Vec::new();
-    walk_for_repos(root, &mut repos, 0, max_depth);
+    let ignore = GitignoreFilter::from_file(root, IGNORE_FILE);
+    walk_for_repos(root, &ignore, &mut repos, 0, max_depth);
+    if skip_submodules {
+        repos.retain(|repo| !is_submodule(repo));
+    }
     repos
 }
 
-fn walk_for_repos(dir: &Path, repos: &mut Vec<PathBuf>, depth: usize, max_depth: usize) {
+/// Drop repos matching any `--exclude` glob. Patterns use gitignore syntax
+/// relative to `root`: `tmp-*` matches a repo (or a parent dir) named like
+/// that at any depth, `**/vendor/**` anything under a `vendor` dir.
+pub fn exclude_repos(
+    root: &Path,
+    repos: Vec<PathBuf>,
+    patterns: &[String],
+) -> Result<Vec<PathBuf>, String> {
+    if patterns.is_empty() {
+        return Ok(repos);
+    }
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .map_err(|e| format!("invalid --exclude pattern \"{}\": {}", pattern, e))?;
+    }
+    let matcher = builder
+        .build()
+        .map_err(|e| format!("invalid --exclude pattern: {}", e))?;
+    Ok(repos
+        .into_iter()
+        .filter(|repo| {
+            !repo.starts_with(root) || !matcher.matched_path_or_any_parents(repo, true).is_ignore()
+        })
+        .collect())
+}
+
+/// A `.git` directory or file. Symlinks aren't followed.
+fn has_git_entry(dir: &Path) -> bool {
+    std::fs::symlink_metadata(dir.join(".git"))
+        .map(|m| m.is_dir() || m.is_file())
+        .unwrap_or(false)
+}
+
+/// Whether some ancestor of `repo` declares it as a submodule: a `path` in
+/// the ancestor's `.gitmodules` that leads to `repo`. Ancestors above the scan
+/// root count too: scanning inside a superproject still finds its submodules.
+fn is_submodule(repo: &Path) -> bool {
+    let repo = std::fs::canonicalize(repo).unwrap_or_else(|_| repo.to_path_buf());
+    repo.ancestors().skip(1).any(|parent| {
+        submodule_paths(parent)
+            .iter()
+            .any(|path| parent.join(path) == repo)
+    })
+}
+
+/// The `submodule.<name>.path` entries of `dir/.gitmodules`, relative to `dir`.
+fn submodule_paths(dir: &Path) -> Vec<PathBuf> {
+    let Ok(bytes) = std::fs::read(dir.join(".gitmodules")) else {
+        return Vec::new();
+    };
+    let Ok(modules) = gix::config::File::from_bytes_no_includes(
+        &bytes,
+        gix::config::file::Metadata::api(),
+        Default::default(),
+    ) else {
+        return Vec::new();
+    };
+    modules
+        .sections_by_name("submodule")
+        .into_iter()
+        .flatten()
+        .filter_map(|section| section.value("path"))
+        .map(|path| gix::path::from_bstr(path.as_ref()).into_owned())
+        .collect()
+}
+
+fn walk_for_repos(
+    dir: &Path,
+    ignore: &GitignoreFilter,
+    repos: &mut Vec<PathBuf>,
+    depth: usize,
+    max_depth: usize,
+) {
     if depth > max_depth {
         return;
     }
 
     // If this directory contains .git, it's a repo — add it and stop descending.
-    if dir.join(".git").is_dir() {
+    if has_git_entry(dir) {
         repos.push(dir.to_path_buf());
         return;
     }
@@ -38,10 +126,13 @@ fn walk_for_repos(dir: &Path, repos: &mut Vec<PathBuf>, depth: usize, max_depth:
 
     for entry in entries.flatten() {
         let path = entry.path();
-        if path.is_dir() {
+        if is_regular_dir(&path) {
             let name = entry.file_name().to_string_lossy().to_string();
-            if !SKIP_DIRS.contains(&name.as_str()) && !name.starts_with('.') {
-                walk_for_repos(&path, repos, depth + 1, max_depth);
+            if !SKIP_DIRS.contains(&name.as_str())
+                && !name.starts_with('.')
+                && !ignore.is_ignored(&path, true)
+            {
+                walk_for_repos(&path, ignore, repos, depth + 1, max_depth);
             }
         }
     }
@@ -53,6 +144,19 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[cfg(unix)]
+    #[test]
+    fn does_not_follow_symlink_loops() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let repo = root.join("org").join("project");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        // org/loop -> root: following it would revisit org/ forever
+        std::os::unix::fs::symlink(root, root.join("org").join("loop")).unwrap();
+
+        assert_eq!(find_git_repos(root, 50, false), vec![repo]);
+    }
+
     #[test]
     fn finds_git_repos() {
         let dir = TempDir::new().unwrap();
@@ -65,7 +169,7 @@ mod tests {
         let repo_b = root.join("org").join("project-b");
         fs::create_dir_all(repo_b.join(".git")).unwrap();
 
-        let repos = find_git_repos(root, 5);
+        let repos = find_git_repos(root, 5, false);
         assert_eq!(repos.len(), 2);
         assert!(repos.contains(&repo_a));
         assert!(repos.contains(&repo_b));
@@ -84,7 +188,7 @@ mod tests {
         let nm_repo = root.join("node_modules").join("some-pkg");
         fs::create_dir_all(nm_repo.join(".git")).unwrap();
 
-        let repos = find_git_repos(root, 5);
+        let repos = find_git_repos(root, 5, false);
         assert_eq!(repos.len(), 1);
         assert!(repos.contains(&real));
     }
@@ -103,12 +207,12 @@ mod tests {
         fs::create_dir_all(deep.join(".git")).unwrap();
 
         // max_depth=2 should only find the shallow one
-        let repos = find_git_repos(root, 2);
+        let repos = find_git_repos(root, 2, false);
         assert_eq!(repos.len(), 1);
         assert!(repos.contains(&shallow));
 
         // max_depth=5 should find both
-        let repos = find_git_repos(root, 5);
+        let repos = find_git_repos(root, 5, false);
         assert_eq!(repos.len(), 2);
     }
 
@@ -125,7 +229,7 @@ mod tests {
         let inner = outer.join("submodules").join("inner");
         fs::create_dir_all(inner.join(".git")).unwrap();
 
-        let repos = find_git_repos(root, 5);
+        let repos = find_git_repos(root, 5, false);
         assert_eq!(repos.len(), 1);
         assert!(repos.contains(&outer));
     }
@@ -143,8 +247,129 @@ mod tests {
         let hidden = root.join(".hidden-dir").join("secret-project");
         fs::create_dir_all(hidden.join(".git")).unwrap();
 
-        let repos = find_git_repos(root, 5);
+        let repos = find_git_repos(root, 5, false);
         assert_eq!(repos.len(), 1);
         assert!(repos.contains(&visible));
     }
+
+    #[test]
+    fn respects_vibereportignore() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for repo in [
+            "archive/old",
+            "experiments/x",
+            "clients/acme",
+            "clients/mine",
+            "work/app",
+        ] {
+            fs::create_dir_all(root.join(repo).join(".git")).unwrap();
+        }
+        fs::write(
+            root.join(IGNORE_FILE),
+            "# not mine\narchive/\nexperiments\nclients/*\n!clients/mine\n",
+        )
+        .unwrap();
+
+        let mut repos = find_git_repos(root, 5, false);
+        repos.sort();
+        assert_eq!(
+            repos,
+            vec![root.join("clients/mine"), root.join("work/app")]
+        );
+    }
+
+    #[test]
+    fn exclude_globs_accumulate() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let repos: Vec<PathBuf> = ["app", "tmp-spike", "libs/vendor/dep", "scratch/tmp-old/x"]
+            .iter()
+            .map(|p| root.join(p))
+            .collect();
+        let patterns = ["**/vendor/**".to_string(), "tmp-*".to_string()];
+
+        let kept = exclude_repos(root, repos.clone(), &patterns).unwrap();
+        assert_eq!(kept, vec![root.join("app")]);
+        assert_eq!(exclude_repos(root, repos.clone(), &[]).unwrap(), repos);
+        // The scan root itself is never matched
+        assert_eq!(
+            exclude_repos(root, vec![root.to_path_buf()], &patterns).unwrap(),
+            vec![root.to_path_buf()]
+        );
+    }
+
+    #[test]
+    fn exclude_rejects_bad_glob() {
+        let dir = TempDir::new().unwrap();
+        let err = exclude_repos(dir.path(), vec![], &["{a,b".to_string()]).unwrap_err();
+        assert!(err.contains("{a,b"), "{}", err);
+    }
+
+    /// Superproject `super/` with a submodule checked out at `super/libs/dep`,
+    /// whose `.git` is a file pointing into `super/.git/modules`.
+    fn submodule_layout(root: &Path) -> (PathBuf, PathBuf) {
+        let superproject = root.join("super");
+        fs::create_dir_all(superproject.join(".git").join("modules").join("dep")).unwrap();
+        fs::write(
+            superproject.join(".gitmodules"),
+            "[submodule \"dep\"]\n\tpath = libs/dep\n",
+        )
+        .unwrap();
+        let dep = superproject.join("libs").join("dep");
+        fs::create_dir_all(&dep).unwrap();
+        fs::write(dep.join(".git"), "gitdir: ../../.git/modules/dep\n").unwrap();
+        (superproject, dep)
+    }
+
+    #[test]
+    fn finds_repo_with_git_file() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        // A linked worktree: `.git` is a file naming the main repo's git dir
+        let worktree = root.join("feature");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(
+            worktree.join(".git"),
+            "gitdir: /elsewhere/.git/worktrees/feature\n",
+        )
+        .unwrap();
+
+        assert_eq!(find_git_repos(root, 5, false), vec![worktree]);
+    }
+
+    #[test]
+    fn submodule_is_covered_by_its_superproject() {
+        let dir = TempDir::new().unwrap();
+        let (superproject, _) = submodule_layout(dir.path());
+
+        assert_eq!(find_git_repos(dir.path(), 5, false), vec![superproject]);
+    }
+
+    #[test]
+    fn skip_submodules_when_scanning_inside_a_superproject() {
+        let dir = TempDir::new().unwrap();
+        let (superproject, dep) = submodule_layout(dir.path());
+        let libs = superproject.join("libs");
+
+        assert_eq!(find_git_repos(&libs, 5, false), vec![dep]);
+        assert!(find_git_repos(&libs, 5, true).is_empty());
+        // The superproject itself has no `.gitmodules` above it
+        assert_eq!(find_git_repos(dir.path(), 5, true), vec![superproject]);
+    }
+
+    #[test]
+    fn undeclared_nested_repo_is_not_a_submodule() {
+        let dir = TempDir::new().unwrap();
+        let (superproject, dep) = submodule_layout(dir.path());
+        // A repo cloned into the superproject's tree by hand, not in `.gitmodules`
+        let scratch = superproject.join("libs").join("scratch");
+        fs::create_dir_all(scratch.join(".git")).unwrap();
+        let libs = superproject.join("libs");
+
+        let mut found = find_git_repos(&libs, 5, false);
+        found.sort();
+        assert_eq!(found, vec![dep, scratch.clone()]);
+        assert_eq!(find_git_repos(&libs, 5, true), vec![scratch]);
+    }
 }
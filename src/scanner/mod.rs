@@ -1,3 +1,5 @@
+pub mod cache;
 pub mod discover;
 pub mod multi_report;
+pub mod progress;
 pub mod remote;
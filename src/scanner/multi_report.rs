@@ -1,6 +1,8 @@
+use crate::git::ai_detect::AiTool;
 use crate::git::parser::GitStats;
 use crate::project::ProjectStats;
 use crate::score::calculator::VibeScore;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Debug)]
@@ -22,6 +24,124 @@ pub struct MultiReport {
     pub global_ai_ratio: f64,
     pub total_lines: usize,
     pub average_score: u32,
+    /// Lines per language summed over every repo.
+    pub languages: HashMap<String, usize>,
+    /// Commits per AI tool summed over every repo, most used first
+    /// (ties by tool name).
+    pub ai_tools: Vec<(AiTool, usize)>,
+}
+
+/// Column the multi-repo table is ordered by (`--sort`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepoSort {
+    /// Vibe score, highest first.
+    #[default]
+    Score,
+    /// AI ratio, highest first.
+    Ai,
+    /// Lines of code, largest first.
+    Lines,
+    /// Counted commits, most first.
+    Commits,
+    /// Repo name, A to Z.
+    Name,
+}
+
+impl std::str::FromStr for RepoSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "score" => Ok(RepoSort::Score),
+            "ai" => Ok(RepoSort::Ai),
+            "lines" => Ok(RepoSort::Lines),
+            "commits" => Ok(RepoSort::Commits),
+            "name" => Ok(RepoSort::Name),
+            _ => Err(format!(
+                "expected score, ai, lines, commits, or name (got \"{}\")",
+                s
+            )),
+        }
+    }
+}
+
+/// What each repo's score is weighted by in the global average (`--weight-by`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoreWeight {
+    /// Counted commits.
+    #[default]
+    Commits,
+    /// Lines of code.
+    Lines,
+}
+
+impl ScoreWeight {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScoreWeight::Commits => "commits",
+            ScoreWeight::Lines => "lines",
+        }
+    }
+}
+
+impl std::str::FromStr for ScoreWeight {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "commits" => Ok(ScoreWeight::Commits),
+            "lines" => Ok(ScoreWeight::Lines),
+            _ => Err(format!("expected commits or lines (got \"{}\")", s)),
+        }
+    }
+}
+
+impl MultiReport {
+    /// Average score with each repo weighted by `by`, so a 5000-commit repo
+    /// outweighs a 2-commit one. Falls back to the plain mean when every
+    /// weight is zero.
+    pub fn weighted_score(&self, by: ScoreWeight) -> u32 {
+        let weight = |r: &RepoReport| match by {
+            ScoreWeight::Commits => r.git_stats.total_commits,
+            ScoreWeight::Lines => r.project_stats.languages.code_lines,
+        };
+        let total: usize = self.repos.iter().map(weight).sum();
+        if total == 0 {
+            return self.average_score;
+        }
+        let weighted: usize = self
+            .repos
+            .iter()
+            .map(|r| r.score.points as usize * weight(r))
+            .sum();
+        (weighted / total) as u32
+    }
+
+    /// Repos in table order: `by` in its natural direction (see `RepoSort`),
+    /// flipped by `reverse`. Ties fall back to the name so the order is stable.
+    pub fn sorted(&self, by: RepoSort, reverse: bool) -> Vec<&RepoReport> {
+        let mut repos: Vec<_> = self.repos.iter().collect();
+        repos.sort_by(|a, b| {
+            let primary = match by {
+                RepoSort::Score => b.score.points.cmp(&a.score.points),
+                RepoSort::Ai => b.score.ai_ratio.total_cmp(&a.score.ai_ratio),
+                RepoSort::Lines => b
+                    .project_stats
+                    .languages
+                    .code_lines
+                    .cmp(&a.project_stats.languages.code_lines),
+                RepoSort::Commits => b.git_stats.total_commits.cmp(&a.git_stats.total_commits),
+                RepoSort::Name => std::cmp::Ordering::Equal,
+            };
+            let ordering = primary.then_with(|| a.name.cmp(&b.name));
+            if reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        repos
+    }
 }
 
 /// Aggregate individual repo reports into a combined multi-report.
@@ -37,6 +157,23 @@ pub fn aggregate(repos: Vec<RepoReport>) -> MultiReport {
         .iter()
         .map(|r| r.project_stats.languages.total_lines)
         .sum();
+    let mut languages: HashMap<String, usize> = HashMap::new();
+    for repo in &repos {
+        for (language, lines) in &repo.project_stats.languages.languages {
+            *languages.entry(language.clone()).or_insert(0) += lines;
+        }
+    }
+    let mut ai_tools: HashMap<AiTool, usize> = HashMap::new();
+    for repo in &repos {
+        for (tool, commits) in &repo.git_stats.ai_tools {
+            *ai_tools.entry(tool.clone()).or_insert(0) += commits;
+        }
+    }
+    let mut ai_tools: Vec<(AiTool, usize)> = ai_tools.into_iter().filter(|(_, n)| *n > 0).collect();
+    ai_tools.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| a.0.to_string().cmp(&b.0.to_string()))
+    });
     let average_score = if repos.is_empty() {
         0
     } else {
@@ -50,13 +187,14 @@ pub fn aggregate(repos: Vec<RepoReport>) -> MultiReport {
         global_ai_ratio,
         total_lines,
         average_score,
+        languages,
+        ai_tools,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::ai_detect::AiTool;
 
     fn mock_repo_report(
         name: &str,
@@ -79,28 +217,31 @@ mod tests {
                 human_commits: total_commits - ai_commits,
                 ai_ratio,
                 ai_tools: vec![(AiTool::ClaudeCode, ai_commits)],
-                commits: vec![],
-                first_commit_date: None,
-                last_commit_date: None,
-                repo_fingerprint: None,
-                remote_url: None,
+                ..Default::default()
             },
             project_stats: ProjectStats {
                 deps: crate::project::deps::DepsInfo {
                     total: 10,
+                    direct: 10,
                     manager: "npm".into(),
                 },
                 tests: crate::project::tests_detect::TestsInfo {
                     has_tests: true,
                     test_files_count: 5,
                     frameworks: vec![],
+                    test_ratio: 0.0,
                 },
                 languages: crate::project::languages::LanguageStats {
                     languages: std::collections::HashMap::new(),
                     total_lines,
+                    code_lines: total_lines,
+                    config_docs_lines: 0,
+                    generated_lines: 0,
                 },
                 security: crate::project::security::SecurityInfo::default(),
                 vibe: crate::project::vibe_detect::VibeInfo::default(),
+                monorepo: false,
+                license: None,
             },
             score: VibeScore {
                 grade: "B".to_string(),
@@ -112,6 +253,137 @@ mod tests {
         }
     }
 
+    fn names(repos: &[&RepoReport]) -> Vec<String> {
+        repos.iter().map(|r| r.name.clone()).collect()
+    }
+
+    #[test]
+    fn sorted_by_each_column() {
+        let report = aggregate(vec![
+            mock_repo_report("b-small", 10, 9, 100, 40),
+            mock_repo_report("a-big", 50, 5, 9000, 80),
+            mock_repo_report("c-busy", 500, 100, 2000, 60),
+        ]);
+
+        assert_eq!(
+            names(&report.sorted(RepoSort::Score, false)),
+            ["a-big", "c-busy", "b-small"]
+        );
+        assert_eq!(
+            names(&report.sorted(RepoSort::Ai, false)),
+            ["b-small", "c-busy", "a-big"]
+        );
+        assert_eq!(
+            names(&report.sorted(RepoSort::Lines, false)),
+            ["a-big", "c-busy", "b-small"]
+        );
+        assert_eq!(
+            names(&report.sorted(RepoSort::Commits, false)),
+            ["c-busy", "a-big", "b-small"]
+        );
+        assert_eq!(
+            names(&report.sorted(RepoSort::Name, false)),
+            ["a-big", "b-small", "c-busy"]
+        );
+        assert_eq!(
+            names(&report.sorted(RepoSort::Score, true)),
+            ["b-small", "c-busy", "a-big"]
+        );
+    }
+
+    #[test]
+    fn sorted_ties_break_by_name() {
+        let report = aggregate(vec![
+            mock_repo_report("zeta", 10, 1, 100, 50),
+            mock_repo_report("alpha", 10, 1, 100, 50),
+        ]);
+        assert_eq!(
+            names(&report.sorted(RepoSort::Score, false)),
+            ["alpha", "zeta"]
+        );
+        assert_eq!(
+            names(&report.sorted(RepoSort::Score, true)),
+            ["zeta", "alpha"]
+        );
+    }
+
+    #[test]
+    fn parse_repo_sort() {
+        assert_eq!("AI".parse::<RepoSort>(), Ok(RepoSort::Ai));
+        assert_eq!("lines".parse::<RepoSort>(), Ok(RepoSort::Lines));
+        assert!("size".parse::<RepoSort>().is_err());
+    }
+
+    #[test]
+    fn aggregate_sums_languages_across_repos() {
+        let mut web = mock_repo_report("web", 10, 5, 1500, 50);
+        let mut api = mock_repo_report("api", 10, 5, 700, 50);
+        let langs = |pairs: &[(&str, usize)]| {
+            pairs
+                .iter()
+                .map(|(name, lines)| (name.to_string(), *lines))
+                .collect::<HashMap<_, _>>()
+        };
+        web.project_stats.languages.languages = langs(&[("TypeScript", 1000), ("CSS", 500)]);
+        api.project_stats.languages.languages = langs(&[("TypeScript", 200), ("Rust", 500)]);
+
+        let report = aggregate(vec![web, api]);
+        assert_eq!(
+            report.languages,
+            langs(&[("TypeScript", 1200), ("CSS", 500), ("Rust", 500)])
+        );
+        assert!(aggregate(vec![]).languages.is_empty());
+    }
+
+    #[test]
+    fn aggregate_sums_ai_tools_across_repos() {
+        let mut web = mock_repo_report("web", 10, 6, 100, 50);
+        let mut api = mock_repo_report("api", 10, 6, 100, 50);
+        web.git_stats.ai_tools = vec![(AiTool::Cursor, 4), (AiTool::ClaudeCode, 2)];
+        api.git_stats.ai_tools = vec![(AiTool::ClaudeCode, 2), (AiTool::Aider, 4)];
+
+        let report = aggregate(vec![web, api]);
+        // Aider and Claude Code tie on 4 and fall back to name order
+        assert_eq!(
+            report.ai_tools,
+            [
+                (AiTool::Aider, 4),
+                (AiTool::ClaudeCode, 4),
+                (AiTool::Cursor, 4)
+            ]
+        );
+        assert!(aggregate(vec![]).ai_tools.is_empty());
+    }
+
+    #[test]
+    fn weighted_score_follows_the_bigger_repo() {
+        let report = aggregate(vec![
+            mock_repo_report("tiny", 2, 0, 9000, 20),
+            mock_repo_report("main", 198, 0, 1000, 80),
+        ]);
+        assert_eq!(report.average_score, 50);
+        // (20 * 2 + 80 * 198) / 200
+        assert_eq!(report.weighted_score(ScoreWeight::Commits), 79);
+        // (20 * 9000 + 80 * 1000) / 10000
+        assert_eq!(report.weighted_score(ScoreWeight::Lines), 26);
+    }
+
+    #[test]
+    fn weighted_score_without_weights_is_the_mean() {
+        let report = aggregate(vec![
+            mock_repo_report("a", 0, 0, 0, 40),
+            mock_repo_report("b", 0, 0, 0, 60),
+        ]);
+        assert_eq!(report.weighted_score(ScoreWeight::Commits), 50);
+        assert_eq!(aggregate(vec![]).weighted_score(ScoreWeight::Lines), 0);
+    }
+
+    #[test]
+    fn parse_score_weight() {
+        assert_eq!("Lines".parse::<ScoreWeight>(), Ok(ScoreWeight::Lines));
+        assert!("stars".parse::<ScoreWeight>().is_err());
+    }
+
     #[test]
     fn aggregate_empty() {
         let report = aggregate(vec![]);
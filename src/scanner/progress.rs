@@ -0,0 +1,94 @@
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+/// Progress feedback for `--scan-all`: an "analyzing 12/47" bar on a color
+/// terminal, or the plain `name ... OK` lines otherwise (pipes, CI, --no-color).
+pub struct ScanProgress {
+    bar: Option<ProgressBar>,
+}
+
+impl ScanProgress {
+    pub fn new(total: usize, fancy: bool) -> Self {
+        let bar = fancy.then(|| Self::styled_bar(total, ProgressDrawTarget::stderr()));
+        Self { bar }
+    }
+
+    fn styled_bar(total: usize, target: ProgressDrawTarget) -> ProgressBar {
+        let bar = ProgressBar::with_draw_target(Some(total as u64), target);
+        bar.set_style(
+            ProgressStyle::with_template("  analyzing {pos}/{len} [{bar:24.cyan/blue}] {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("█▓░"),
+        );
+        bar
+    }
+
+    /// A repo finished analyzing.
+    pub fn ok(&self, name: &str, commits: usize) {
+        match &self.bar {
+            Some(bar) => {
+                bar.set_message(name.to_string());
+                bar.inc(1);
+            }
+            None => eprintln!("  {} ... OK ({} commits)", name, commits),
+        }
+    }
+
+    /// A repo failed; always printed so errors don't disappear behind the bar.
+    pub fn skipped(&self, name: &str, reason: &str) {
+        let line = format!("  {} ... skipped ({})", name, reason);
+        match &self.bar {
+            Some(bar) => {
+                bar.println(line);
+                bar.inc(1);
+            }
+            None => eprintln!("{}", line),
+        }
+    }
+
+    /// A repo left out by --min-commits: quiet behind the bar (main.rs prints
+    /// the total), one line per repo in plain mode.
+    pub fn below_min_commits(&self, name: &str, commits: usize) {
+        match &self.bar {
+            Some(bar) => bar.inc(1),
+            None => eprintln!("  {} ... skipped ({} commits)", name, commits),
+        }
+    }
+
+    /// Remove the bar before the final report is printed.
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//  Tests
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_counts_ok_and_skipped_repos() {
+        let progress = ScanProgress {
+            bar: Some(ScanProgress::styled_bar(3, ProgressDrawTarget::hidden())),
+        };
+        progress.ok("api", 12);
+        progress.skipped("broken", "not a git repo");
+        progress.below_min_commits("scratch", 1);
+
+        let bar = progress.bar.as_ref().unwrap();
+        assert_eq!(bar.position(), 3);
+        assert_eq!(bar.length(), Some(3));
+        assert_eq!(bar.message(), "api");
+    }
+
+    #[test]
+    fn plain_mode_has_no_bar() {
+        let progress = ScanProgress::new(5, false);
+        assert!(progress.bar.is_none());
+        progress.finish();
+    }
+}
@@ -1,3 +1,4 @@
+use super::roast::CustomRoasts;
 use crate::git::parser::GitStats;
 use crate::project::ProjectStats;
 
@@ -21,154 +22,227 @@ pub struct VibeScore {
     pub breakdown: Vec<ScoreFactor>,
 }
 
+/// Points awarded per scoring factor. Defaults are the stock Vibe Score;
+/// teams override any subset with a `[weights]` table in the config file,
+/// e.g. to weigh leaked secrets more and a missing README less.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoreWeights {
+    /// Points at a 100% AI ratio (scaled linearly).
+    pub ai_ratio: u32,
+    pub no_tests: u32,
+    /// Points at a test ratio of 0, shrinking to 0 at a healthy ratio.
+    pub few_tests: u32,
+    /// Per `.env` file in git, up to `env_in_git_max`.
+    pub env_in_git: u32,
+    pub env_in_git_max: u32,
+    /// Per hardcoded secret hint, up to `hardcoded_secrets_max`.
+    pub hardcoded_secrets: u32,
+    pub hardcoded_secrets_max: u32,
+    /// Per committed private key, up to `private_keys_max`.
+    pub private_keys: u32,
+    pub private_keys_max: u32,
+    /// Points at 100+ dependencies (scaled linearly).
+    pub deps: u32,
+    pub no_linting: u32,
+    pub no_ci_cd: u32,
+    pub boomer_ai: u32,
+    pub node_modules_in_git: u32,
+    pub mega_commit: u32,
+    pub no_gitignore: u32,
+    pub no_readme: u32,
+    pub no_license: u32,
+    pub todo_flood: u32,
+    pub single_branch: u32,
+    pub low_effort_commits: u32,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            ai_ratio: 60,
+            no_tests: 20,
+            few_tests: 10,
+            env_in_git: 20,
+            env_in_git_max: 60,
+            hardcoded_secrets: 20,
+            hardcoded_secrets_max: 60,
+            private_keys: 30,
+            private_keys_max: 60,
+            deps: 10,
+            no_linting: 10,
+            no_ci_cd: 10,
+            boomer_ai: 10,
+            node_modules_in_git: 15,
+            mega_commit: 10,
+            no_gitignore: 10,
+            no_readme: 10,
+            no_license: 5,
+            todo_flood: 5,
+            single_branch: 5,
+            low_effort_commits: 5,
+        }
+    }
+}
+
+/// How the AI ratio `x` (0.0 to 1.0) maps to its share of the "AI Ratio"
+/// points (`--ai-curve`), before scaling by `ScoreWeights::ai_ratio`:
+///
+/// - `linear`: `x` — every percent counts the same (default).
+/// - `quadratic`: `x²` — low ratios barely count, the climb from 50% (0.25)
+///   to 90% (0.81) matters most.
+/// - `logistic`: an S-curve `1 / (1 + e^(-10(x - 0.5)))`, rescaled so 0% and
+///   100% map exactly to 0 and 1 — flat at the ends, steep around 50%.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AiCurve {
+    #[default]
+    Linear,
+    Quadratic,
+    Logistic,
+}
+
+/// Steepness of the logistic curve around 50%.
+const LOGISTIC_STEEPNESS: f64 = 10.0;
+
+impl AiCurve {
+    pub fn apply(self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            AiCurve::Linear => x,
+            AiCurve::Quadratic => x * x,
+            AiCurve::Logistic => {
+                let sigmoid = |t: f64| 1.0 / (1.0 + (-LOGISTIC_STEEPNESS * (t - 0.5)).exp());
+                // The sigmoid is symmetric: sigmoid(1) = 1 - sigmoid(0)
+                let floor = sigmoid(0.0);
+                (sigmoid(x) - floor) / (1.0 - 2.0 * floor)
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for AiCurve {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "linear" => Ok(AiCurve::Linear),
+            "quadratic" => Ok(AiCurve::Quadratic),
+            "logistic" => Ok(AiCurve::Logistic),
+            _ => Err(format!(
+                "expected linear, quadratic, or logistic (got \"{}\")",
+                s
+            )),
+        }
+    }
+}
+
+/// Tweaks to `calculate` beyond the stats themselves.
+#[derive(Debug, Clone, Default)]
+pub struct ScoreOptions {
+    /// Picks the roast (--seed); by default derived from the repo fingerprint.
+    pub seed: Option<u64>,
+    /// Team roasts from `--roasts` or the config file.
+    pub roasts: CustomRoasts,
+    /// Per-factor points from the config's `[weights]` table.
+    pub weights: ScoreWeights,
+    /// Mapping of the AI ratio to points (`--ai-curve`).
+    pub ai_curve: AiCurve,
+}
+
 /// Compute the Vibe Score based on git stats and project stats.
 /// Higher score = more "vibe coded" (this is not a quality judgment,
 /// it's a fun metric for how AI-assisted your project is).
 /// Score CAN exceed 100 for true vibe chaos (S+ tier).
-pub fn calculate(git: &GitStats, project: &ProjectStats) -> VibeScore {
+pub fn calculate(git: &GitStats, project: &ProjectStats, options: &ScoreOptions) -> VibeScore {
+    let w = &options.weights;
     let mut points: u32 = 0;
     let mut breakdown: Vec<ScoreFactor> = Vec::new();
+    let mut add = |label: String, pts: u32| {
+        if pts > 0 {
+            points += pts;
+            breakdown.push(ScoreFactor { label, points: pts });
+        }
+    };
+    let flag = |on: bool, pts: u32| if on { pts } else { 0 };
 
     // AI ratio (0-60 points)
-    let ai_pts = (git.ai_ratio * 60.0) as u32;
-    points += ai_pts;
-    if ai_pts > 0 {
-        breakdown.push(ScoreFactor {
-            label: "AI Ratio".into(),
-            points: ai_pts,
-        });
-    }
-
-    // No tests (+20) or few tests (+10)
-    if !project.tests.has_tests {
-        points += 20;
-        breakdown.push(ScoreFactor {
-            label: "No Tests".into(),
-            points: 20,
-        });
-    } else if project.tests.test_files_count < 3 {
-        points += 10;
-        breakdown.push(ScoreFactor {
-            label: "Few Tests".into(),
-            points: 10,
-        });
+    add(
+        "AI Ratio".into(),
+        (options.ai_curve.apply(ai_weight(git)) * w.ai_ratio as f64) as u32,
+    );
+
+    // No tests (+20) or few tests (0-10, scaled by test ratio)
+    if !project.tests.has_test_files() {
+        add("No Tests".into(), w.no_tests);
+    } else {
+        add(
+            "Few Tests".into(),
+            few_tests_points(project.tests.test_ratio, w.few_tests),
+        );
     }
 
     // .env in git (+20/file, max 60)
-    let env_points = (project.security.env_files_count as u32 * 20).min(60);
-    points += env_points;
-    if env_points > 0 {
-        breakdown.push(ScoreFactor {
-            label: ".env in Git".into(),
-            points: env_points,
-        });
-    }
+    let env_files = project.security.env_files_count as u32;
+    add(
+        ".env in Git".into(),
+        env_files.saturating_mul(w.env_in_git).min(w.env_in_git_max),
+    );
 
     // Hardcoded secrets (+20/each, max 60)
-    let secrets_points = (project.security.hardcoded_secrets_hints as u32 * 20).min(60);
-    points += secrets_points;
-    if secrets_points > 0 {
-        breakdown.push(ScoreFactor {
-            label: "Hardcoded Secrets".into(),
-            points: secrets_points,
-        });
-    }
+    let secrets = project.security.hardcoded_secrets_hints as u32;
+    add(
+        "Hardcoded Secrets".into(),
+        secrets
+            .saturating_mul(w.hardcoded_secrets)
+            .min(w.hardcoded_secrets_max),
+    );
+
+    // Private key files (+30/each, max 60)
+    let keys = project.security.private_keys_count as u32;
+    add(
+        "Private Keys".into(),
+        keys.saturating_mul(w.private_keys).min(w.private_keys_max),
+    );
 
     // Deps bloat (0-10)
-    let deps_score = (project.deps.total as f64 / 100.0).min(1.0) * 10.0;
-    let deps_pts = deps_score as u32;
-    points += deps_pts;
-    if deps_pts > 0 {
-        breakdown.push(ScoreFactor {
-            label: format!("{} Deps", project.deps.total),
-            points: deps_pts,
-        });
-    }
-
-    // No linting (+10)
-    if project.vibe.no_linting {
-        points += 10;
-        breakdown.push(ScoreFactor {
-            label: "No Linting".into(),
-            points: 10,
-        });
-    }
-
-    // No CI/CD (+10)
-    if project.vibe.no_ci_cd {
-        points += 10;
-        breakdown.push(ScoreFactor {
-            label: "No CI/CD".into(),
-            points: 10,
-        });
-    }
-
-    // Boomer AI (+10)
-    if project.vibe.boomer_ai {
-        points += 10;
-        breakdown.push(ScoreFactor {
-            label: "Boomer AI".into(),
-            points: 10,
-        });
-    }
-
-    // node_modules in git (+15)
-    if project.vibe.node_modules_in_git {
-        points += 15;
-        breakdown.push(ScoreFactor {
-            label: "node_modules in Git".into(),
-            points: 15,
-        });
-    }
-
-    // Mega commit (+10)
-    if project.vibe.mega_commit {
-        points += 10;
-        breakdown.push(ScoreFactor {
-            label: "Mega Commit".into(),
-            points: 10,
-        });
-    }
-
-    // No .gitignore (+10)
-    if project.vibe.no_gitignore {
-        points += 10;
-        breakdown.push(ScoreFactor {
-            label: "No .gitignore".into(),
-            points: 10,
-        });
-    }
-
-    // No README (+10)
-    if project.vibe.no_readme {
-        points += 10;
-        breakdown.push(ScoreFactor {
-            label: "No README".into(),
-            points: 10,
-        });
-    }
-
-    // TODO flood (+5)
-    if project.vibe.todo_flood {
-        points += 5;
-        breakdown.push(ScoreFactor {
-            label: "TODO Flood".into(),
-            points: 5,
-        });
-    }
-
-    // Single branch (+5)
-    if project.vibe.single_branch {
-        points += 5;
-        breakdown.push(ScoreFactor {
-            label: "Single Branch".into(),
-            points: 5,
-        });
-    }
+    let deps_score = (weighed_deps(project) / BLOATED_DEPS).min(1.0) * w.deps as f64;
+    add(format!("{} Deps", project.deps.total), deps_score as u32);
+
+    let vibe = &project.vibe;
+    add("No Linting".into(), flag(vibe.no_linting, w.no_linting));
+    add("No CI/CD".into(), flag(vibe.no_ci_cd, w.no_ci_cd));
+    add("Boomer AI".into(), flag(vibe.boomer_ai, w.boomer_ai));
+    add(
+        "node_modules in Git".into(),
+        flag(vibe.node_modules_in_git, w.node_modules_in_git),
+    );
+    add("Mega Commit".into(), flag(vibe.mega_commit, w.mega_commit));
+    add(
+        "No .gitignore".into(),
+        flag(vibe.no_gitignore, w.no_gitignore),
+    );
+    add("No README".into(), flag(vibe.no_readme, w.no_readme));
+    add(
+        "No License".into(),
+        flag(project.license.is_none(), w.no_license),
+    );
+    add("TODO Flood".into(), flag(vibe.todo_flood, w.todo_flood));
+    add(
+        "Single Branch".into(),
+        flag(vibe.single_branch, w.single_branch),
+    );
+    add(
+        "Low-Effort Commits".into(),
+        flag(vibe.low_effort_commits, w.low_effort_commits),
+    );
 
     // Score is NOT capped — true chaos can exceed 100
     let grade = grade_from_points(points);
-    let roast = super::roast::pick_roast(points, git.ai_ratio, project);
+    let seed = options.seed.unwrap_or_else(|| {
+        super::roast::seed_from(git.repo_fingerprint.as_deref().unwrap_or_default())
+    });
+    let ai_ratio = (!git.no_git).then_some(git.ai_ratio);
+    let roast = super::roast::pick_roast(points, ai_ratio, project, seed, &options.roasts);
 
     VibeScore {
         grade,
@@ -179,6 +253,139 @@ pub fn calculate(git: &GitStats, project: &ProjectStats) -> VibeScore {
     }
 }
 
+/// Blend the commit-based AI ratio with the line-based one so a single
+/// 3000-line AI commit weighs more than a one-line AI typo fix.
+/// Falls back to the commit ratio when no line data is available.
+fn ai_weight(git: &GitStats) -> f64 {
+    if git.total_lines_added == 0 {
+        git.ai_ratio
+    } else {
+        (git.ai_ratio + git.ai_line_ratio) / 2.0
+    }
+}
+
+/// Test-to-source ratio at which a suite counts as healthy (1 test file per 5 sources).
+const HEALTHY_TEST_RATIO: f64 = 0.2;
+
+/// "Few Tests" points for a repo that has tests: `max` at a ratio of 0,
+/// shrinking linearly to 0 at `HEALTHY_TEST_RATIO`.
+fn few_tests_points(test_ratio: f64, max: u32) -> u32 {
+    let shortfall = (1.0 - test_ratio / HEALTHY_TEST_RATIO).clamp(0.0, 1.0);
+    (shortfall * max as f64).round() as u32
+}
+
+/// Code-health score (--quality): the inverse view of the Vibe Score.
+/// Tests, CI/CD, linting, clean secrets and a lean dependency tree push
+/// it up; capped at 100.
+#[derive(Debug, Clone)]
+pub struct QualityScore {
+    /// Letter grade A+ to F
+    pub grade: String,
+    /// Numeric score 0-100
+    pub points: u32,
+    /// Tagline from its own table (not the vibe roasts)
+    pub roast: String,
+    /// Points earned per factor
+    pub breakdown: Vec<ScoreFactor>,
+}
+
+/// A monorepo's lockfile covers all its packages, so the bloat checks see
+/// its dependency count divided by this.
+const MONOREPO_DEPS_ALLOWANCE: f64 = 3.0;
+
+/// Dependency count as judged by the deps-bloat and "Lean Deps" factors and
+/// the "dependency_hell" roast: `DepsInfo.total`, the lockfile's resolved
+/// packages when there is one.
+pub(crate) fn weighed_deps(project: &ProjectStats) -> f64 {
+    let total = project.deps.total as f64;
+    if project.monorepo {
+        total / MONOREPO_DEPS_ALLOWANCE
+    } else {
+        total
+    }
+}
+
+/// The deps-bloat factor earns its full points at this many packages. Sized
+/// for lockfile counts: a small JS app already resolves a few hundred.
+const BLOATED_DEPS: f64 = 500.0;
+
+/// Dependencies at or below this count earn full "Lean Deps" points...
+const LEAN_DEPS: usize = 500;
+/// ...shrinking linearly to 0 here, where the "dependency_hell" roast kicks in.
+pub(crate) const HEAVY_DEPS: usize = 2500;
+
+/// Compute the code-health score from the same project stats as `calculate`.
+pub fn quality(project: &ProjectStats) -> QualityScore {
+    let mut points: u32 = 0;
+    let mut breakdown: Vec<ScoreFactor> = Vec::new();
+    let mut add = |label: &str, pts: u32| {
+        if pts > 0 {
+            points += pts;
+            breakdown.push(ScoreFactor {
+                label: label.into(),
+                points: pts,
+            });
+        }
+    };
+    let flag = |on: bool, pts: u32| if on { pts } else { 0 };
+    let security = &project.security;
+    let vibe = &project.vibe;
+
+    // Tests: 15 for having any, up to 15 more for a healthy test ratio
+    let tests = &project.tests;
+    add("Tests", flag(tests.has_tests, 15));
+    let coverage = (tests.test_ratio / HEALTHY_TEST_RATIO).clamp(0.0, 1.0);
+    add(
+        "Test Ratio",
+        flag(tests.has_tests, (coverage * 15.0).round() as u32),
+    );
+
+    add("CI/CD", flag(!vibe.no_ci_cd, 15));
+    add("Linting", flag(!vibe.no_linting, 15));
+
+    // Secrets (20)
+    let env_clean = !security.env_in_git && !security.env_in_history;
+    add("No .env Leaked", flag(env_clean, 7));
+    add("No Secrets", flag(security.hardcoded_secrets_hints == 0, 7));
+    add("No Private Keys", flag(security.private_keys_count == 0, 6));
+
+    // Lean dependency tree (10)
+    let excess = (weighed_deps(project) - LEAN_DEPS as f64).max(0.0);
+    let lean = 1.0 - (excess / (HEAVY_DEPS - LEAN_DEPS) as f64).min(1.0);
+    add("Lean Deps", (lean * 10.0).round() as u32);
+
+    add("README", flag(!vibe.no_readme, 5));
+    add(".gitignore", flag(!vibe.no_gitignore, 5));
+
+    QualityScore {
+        grade: quality_grade(points).into(),
+        roast: quality_roast(points).into(),
+        points,
+        breakdown,
+    }
+}
+
+fn quality_grade(points: u32) -> &'static str {
+    match points {
+        95.. => "A+",
+        85..=94 => "A",
+        70..=84 => "B",
+        55..=69 => "C",
+        40..=54 => "D",
+        _ => "F",
+    }
+}
+
+fn quality_roast(points: u32) -> &'static str {
+    match points {
+        90.. => "Clean enough to eat off.",
+        75..=89 => "Responsible adult detected.",
+        60..=74 => "Mostly load-bearing.",
+        40..=59 => "Held together with duct tape and hope.",
+        _ => "A health inspector would close this repo.",
+    }
+}
+
 /// Map points to letter grade. S+ for scores above 100.
 pub fn grade_from_points(points: u32) -> String {
     match points {
@@ -199,6 +406,53 @@ pub fn grade_from_points(points: u32) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn ai_curves_keep_the_endpoints() {
+        for curve in [AiCurve::Linear, AiCurve::Quadratic, AiCurve::Logistic] {
+            assert!(curve.apply(0.0).abs() < 1e-9, "{:?}", curve);
+            assert!((curve.apply(1.0) - 1.0).abs() < 1e-9, "{:?}", curve);
+        }
+        assert!((AiCurve::Logistic.apply(0.5) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ai_curves_shape() {
+        assert_eq!(AiCurve::Linear.apply(0.4), 0.4);
+        assert!((AiCurve::Quadratic.apply(0.5) - 0.25).abs() < 1e-9);
+        assert!((AiCurve::Quadratic.apply(0.9) - 0.81).abs() < 1e-9);
+        // Logistic: flat below ~20%, saturated above ~80%
+        assert!(AiCurve::Logistic.apply(0.1) < 0.02);
+        assert!(AiCurve::Logistic.apply(0.9) > 0.98);
+    }
+
+    #[test]
+    fn parses_ai_curve_names() {
+        assert_eq!("linear".parse(), Ok(AiCurve::Linear));
+        assert_eq!("Quadratic".parse(), Ok(AiCurve::Quadratic));
+        assert_eq!("logistic".parse(), Ok(AiCurve::Logistic));
+        assert!("cubic".parse::<AiCurve>().is_err());
+    }
+
+    #[test]
+    fn ai_curve_changes_ai_ratio_points() {
+        let git = mock_git_stats(0.9);
+        let project = mock_project_stats(0, true);
+        let ai_points = |ai_curve| {
+            let options = ScoreOptions {
+                ai_curve,
+                ..Default::default()
+            };
+            calculate(&git, &project, &options)
+                .breakdown
+                .iter()
+                .find(|f| f.label == "AI Ratio")
+                .map_or(0, |f| f.points)
+        };
+        assert_eq!(ai_points(AiCurve::Linear), 54);
+        assert_eq!(ai_points(AiCurve::Quadratic), 48);
+        assert_eq!(ai_points(AiCurve::Logistic), 59);
+    }
     use crate::git::ai_detect::AiTool;
 
     fn mock_git_stats(ai_ratio: f64) -> GitStats {
@@ -208,11 +462,7 @@ mod tests {
             human_commits: (100.0 * (1.0 - ai_ratio)) as usize,
             ai_ratio,
             ai_tools: vec![(AiTool::ClaudeCode, (100.0 * ai_ratio) as usize)],
-            commits: vec![],
-            first_commit_date: None,
-            last_commit_date: None,
-            repo_fingerprint: None,
-            remote_url: None,
+            ..Default::default()
         }
     }
 
@@ -220,19 +470,26 @@ mod tests {
         ProjectStats {
             deps: crate::project::deps::DepsInfo {
                 total: deps,
+                direct: deps,
                 manager: "npm".into(),
             },
             tests: crate::project::tests_detect::TestsInfo {
                 has_tests,
                 test_files_count: if has_tests { 10 } else { 0 },
                 frameworks: vec![],
+                test_ratio: if has_tests { 0.25 } else { 0.0 },
             },
             languages: crate::project::languages::LanguageStats {
                 languages: std::collections::HashMap::new(),
                 total_lines: 5000,
+                code_lines: 5000,
+                config_docs_lines: 0,
+                generated_lines: 0,
             },
             security: crate::project::security::SecurityInfo::default(),
             vibe: crate::project::vibe_detect::VibeInfo::default(),
+            monorepo: false,
+            license: None,
         }
     }
 
@@ -240,7 +497,7 @@ mod tests {
     fn high_ai_no_tests_high_score() {
         let git = mock_git_stats(0.9);
         let proj = mock_project_stats(200, false);
-        let score = calculate(&git, &proj);
+        let score = calculate(&git, &proj, &ScoreOptions::default());
         assert!(
             score.points >= 70,
             "Expected high score, got {}",
@@ -252,7 +509,7 @@ mod tests {
     fn zero_ai_with_tests_low_score() {
         let git = mock_git_stats(0.0);
         let proj = mock_project_stats(5, true);
-        let score = calculate(&git, &proj);
+        let score = calculate(&git, &proj, &ScoreOptions::default());
         assert!(
             score.points <= 30,
             "Expected low score, got {}",
@@ -268,7 +525,7 @@ mod tests {
         proj.security.env_files_count = 4;
         proj.security.hardcoded_secrets_hints = 5;
         proj.languages.total_lines = 50000;
-        let score = calculate(&git, &proj);
+        let score = calculate(&git, &proj, &ScoreOptions::default());
         assert!(
             score.points > 100,
             "Expected score > 100 for peak chaos, got {}",
@@ -281,7 +538,7 @@ mod tests {
     fn grade_matches_points() {
         let git = mock_git_stats(0.5);
         let proj = mock_project_stats(50, true);
-        let score = calculate(&git, &proj);
+        let score = calculate(&git, &proj, &ScoreOptions::default());
         let expected_grade = grade_from_points(score.points);
         assert_eq!(score.grade, expected_grade);
     }
@@ -290,7 +547,7 @@ mod tests {
     fn roast_is_not_empty() {
         let git = mock_git_stats(0.5);
         let proj = mock_project_stats(10, true);
-        let score = calculate(&git, &proj);
+        let score = calculate(&git, &proj, &ScoreOptions::default());
         assert!(!score.roast.is_empty(), "Roast should not be empty");
     }
 
@@ -298,11 +555,11 @@ mod tests {
     fn env_files_add_security_points() {
         let git = mock_git_stats(0.5);
         let mut proj = mock_project_stats(10, true);
-        let score_clean = calculate(&git, &proj);
+        let score_clean = calculate(&git, &proj, &ScoreOptions::default());
 
         proj.security.env_in_git = true;
         proj.security.env_files_count = 3;
-        let score_dirty = calculate(&git, &proj);
+        let score_dirty = calculate(&git, &proj, &ScoreOptions::default());
 
         assert!(
             score_dirty.points > score_clean.points,
@@ -312,6 +569,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn huge_counts_saturate_at_the_cap() {
+        let git = mock_git_stats(0.5);
+        let mut proj = mock_project_stats(10, true);
+        proj.security.env_in_git = true;
+        proj.security.env_files_count = u32::MAX as usize;
+        let score = calculate(&git, &proj, &ScoreOptions::default());
+        let env = score
+            .breakdown
+            .iter()
+            .find(|f| f.label == ".env in Git")
+            .unwrap();
+        assert_eq!(env.points, 60);
+    }
+
+    #[test]
+    fn missing_license_adds_a_few_points() {
+        let git = mock_git_stats(0.5);
+        let mut proj = mock_project_stats(10, true);
+        let unlicensed = calculate(&git, &proj, &ScoreOptions::default());
+
+        proj.license = Some("MIT".into());
+        let licensed = calculate(&git, &proj, &ScoreOptions::default());
+
+        assert_eq!(unlicensed.points, licensed.points + 5);
+        assert!(!licensed.breakdown.iter().any(|f| f.label == "No License"));
+    }
+
+    #[test]
+    fn private_keys_weigh_heavily() {
+        let git = mock_git_stats(0.5);
+        let mut proj = mock_project_stats(10, true);
+        let clean = calculate(&git, &proj, &ScoreOptions::default());
+
+        proj.security.private_keys_count = 1;
+        let one_key = calculate(&git, &proj, &ScoreOptions::default());
+        assert_eq!(one_key.points, clean.points + 30);
+
+        proj.security.private_keys_count = 5;
+        let many_keys = calculate(&git, &proj, &ScoreOptions::default());
+        assert_eq!(many_keys.points, clean.points + 60);
+        assert!(many_keys
+            .breakdown
+            .iter()
+            .any(|f| f.label == "Private Keys"));
+    }
+
+    #[test]
+    fn ai_line_ratio_shifts_ai_points() {
+        let proj = mock_project_stats(5, true);
+        let mut git = mock_git_stats(0.1);
+        let commits_only = calculate(&git, &proj, &ScoreOptions::default());
+
+        // One huge AI commit: 10% of commits but 90% of added lines
+        git.total_lines_added = 10_000;
+        git.ai_lines_added = 9_000;
+        git.ai_line_ratio = 0.9;
+        let weighted = calculate(&git, &proj, &ScoreOptions::default());
+
+        assert!(
+            weighted.points > commits_only.points,
+            "Line-heavy AI commits should add points: {} vs {}",
+            weighted.points,
+            commits_only.points
+        );
+    }
+
+    #[test]
+    fn zero_lines_added_uses_commit_ratio() {
+        let git = mock_git_stats(0.5);
+        assert!((ai_weight(&git) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn few_tests_scales_with_test_ratio() {
+        assert_eq!(few_tests_points(0.0, 10), 10);
+        assert_eq!(few_tests_points(0.1, 10), 5);
+        assert_eq!(few_tests_points(0.2, 10), 0);
+        assert_eq!(few_tests_points(0.8, 10), 0);
+
+        let git = mock_git_stats(0.5);
+        let mut proj = mock_project_stats(10, true);
+        proj.tests.test_ratio = 0.02;
+        let sparse = calculate(&git, &proj, &ScoreOptions::default());
+        proj.tests.test_ratio = 0.15;
+        let decent = calculate(&git, &proj, &ScoreOptions::default());
+        assert!(sparse.points > decent.points);
+        assert!(sparse.breakdown.iter().any(|f| f.label == "Few Tests"));
+    }
+
+    #[test]
+    fn test_framework_without_test_files_scores_as_no_tests() {
+        let git = mock_git_stats(0.5);
+        let mut proj = mock_project_stats(10, true);
+        proj.tests.test_files_count = 0;
+        proj.tests.test_ratio = 0.0;
+        let score = calculate(&git, &proj, &ScoreOptions::default());
+        assert!(score.breakdown.iter().any(|f| f.label == "No Tests"));
+        assert!(!score.breakdown.iter().any(|f| f.label == "Few Tests"));
+    }
+
     #[test]
     fn s_plus_grade_above_100() {
         assert_eq!(grade_from_points(101), "S+");
@@ -319,4 +677,136 @@ mod tests {
         assert_eq!(grade_from_points(100), "S");
         assert_eq!(grade_from_points(90), "S");
     }
+
+    #[test]
+    fn custom_weights_change_points() {
+        let git = mock_git_stats(0.5);
+        let mut proj = mock_project_stats(10, true);
+        proj.security.hardcoded_secrets_hints = 1;
+        proj.vibe.no_readme = true;
+        let stock = calculate(&git, &proj, &ScoreOptions::default());
+
+        let options = ScoreOptions {
+            weights: ScoreWeights {
+                hardcoded_secrets: 50,
+                hardcoded_secrets_max: 100,
+                no_readme: 0,
+                ..ScoreWeights::default()
+            },
+            ..ScoreOptions::default()
+        };
+        let security_team = calculate(&git, &proj, &options);
+        assert_eq!(security_team.points, stock.points + 30 - 10);
+        // Zero-weight factors drop out of the breakdown
+        assert!(!security_team
+            .breakdown
+            .iter()
+            .any(|f| f.label == "No README"));
+    }
+
+    #[test]
+    fn weights_deserialize_partially_over_defaults() {
+        let weights: ScoreWeights = toml::from_str("no_readme = 0\nenv_in_git = 40\n").unwrap();
+        assert_eq!(weights.no_readme, 0);
+        assert_eq!(weights.env_in_git, 40);
+        assert_eq!(weights.no_tests, ScoreWeights::default().no_tests);
+        assert!(toml::from_str::<ScoreWeights>("no_reamde = 0").is_err());
+    }
+
+    #[test]
+    fn quality_rewards_healthy_projects() {
+        let mut proj = mock_project_stats(50, true);
+        proj.tests.test_ratio = 0.3;
+        let q = quality(&proj);
+        assert_eq!(q.points, 100);
+        assert_eq!(q.grade, "A+");
+        assert_eq!(q.breakdown.iter().map(|f| f.points).sum::<u32>(), 100);
+    }
+
+    #[test]
+    fn quality_penalizes_chaos() {
+        let mut proj = mock_project_stats(4000, false);
+        proj.vibe.no_ci_cd = true;
+        proj.vibe.no_linting = true;
+        proj.vibe.no_readme = true;
+        proj.vibe.no_gitignore = true;
+        proj.security.env_in_git = true;
+        proj.security.hardcoded_secrets_hints = 2;
+        proj.security.private_keys_count = 1;
+        let q = quality(&proj);
+        assert_eq!(q.points, 0);
+        assert_eq!(q.grade, "F");
+        assert!(q.breakdown.is_empty());
+        assert_eq!(q.roast, "A health inspector would close this repo.");
+    }
+
+    #[test]
+    fn quality_scales_deps_and_test_ratio() {
+        let mut proj = mock_project_stats(1500, true);
+        proj.tests.test_ratio = 0.1;
+        let q = quality(&proj);
+        let factor = |label: &str| {
+            q.breakdown
+                .iter()
+                .find(|f| f.label == label)
+                .map(|f| f.points)
+        };
+        assert_eq!(factor("Lean Deps"), Some(5));
+        assert_eq!(factor("Test Ratio"), Some(8));
+    }
+
+    #[test]
+    fn monorepo_gets_a_larger_deps_budget() {
+        let git = mock_git_stats(0.0);
+        let points = |proj: &ProjectStats, label: &str| {
+            let score = calculate(&git, proj, &ScoreOptions::default());
+            score
+                .breakdown
+                .iter()
+                .chain(quality(proj).breakdown.iter())
+                .find(|f| f.label == label)
+                .map_or(0, |f| f.points)
+        };
+
+        let mut proj = mock_project_stats(1500, true);
+        assert_eq!(points(&proj, "1500 Deps"), 10);
+        assert_eq!(points(&proj, "Lean Deps"), 5);
+        proj.monorepo = true;
+        // Judged as 500 deps
+        assert_eq!(points(&proj, "Lean Deps"), 10);
+
+        proj.deps.total = 750;
+        assert_eq!(points(&proj, "750 Deps"), 5);
+        proj.monorepo = false;
+        assert_eq!(points(&proj, "750 Deps"), 10);
+    }
+
+    #[test]
+    fn lockfile_total_is_what_counts_toward_bloat() {
+        let git = mock_git_stats(0.0);
+        // A typical JS app: a few dozen declared deps, hundreds in the lockfile
+        let mut proj = mock_project_stats(30, true);
+        proj.deps.total = 300;
+        let score = calculate(&git, &proj, &ScoreOptions::default());
+        let deps = score.breakdown.iter().find(|f| f.label == "300 Deps");
+        assert_eq!(deps.map(|f| f.points), Some(6));
+        let lean = quality(&proj)
+            .breakdown
+            .into_iter()
+            .find(|f| f.label == "Lean Deps");
+        assert_eq!(lean.map(|f| f.points), Some(10));
+    }
+
+    #[test]
+    fn quality_is_independent_of_vibe_score() {
+        // Same project, opposite directions: more chaos = higher vibe, lower quality
+        let git = mock_git_stats(0.5);
+        let clean = mock_project_stats(10, true);
+        let mut messy = mock_project_stats(10, true);
+        messy.vibe.no_linting = true;
+        messy.vibe.no_ci_cd = true;
+        let opts = ScoreOptions::default();
+        assert!(calculate(&git, &messy, &opts).points > calculate(&git, &clean, &opts).points);
+        assert!(quality(&messy).points < quality(&clean).points);
+    }
 }
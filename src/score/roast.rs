@@ -1,64 +1,384 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use super::calculator::{weighed_deps, HEAVY_DEPS};
 use crate::project::ProjectStats;
 
+/// Equivalent roasts for one condition; `{loc}` is replaced by the line count.
+type Pool = &'static [&'static str];
+
+/// Which roast pool applies: a contextual condition or a score band (named
+/// by its lower bound). These names are the keys of a custom roasts file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoastKey {
+    Condition(&'static str),
+    Score(&'static str),
+}
+
+/// Condition names, in the order they are checked.
+const CONDITION_KEYS: &[&str] = &[
+    "private_keys",
+    "node_modules",
+    "boomer_ai",
+    "project_manager",
+    "no_safety_net",
+    "no_ai",
+    "many_env_files",
+    "env_in_git",
+    "dependency_hell",
+    "yolo",
+    "no_gitignore_no_readme",
+    "todo_flood",
+    "low_effort_commits",
+    "single_branch",
+    "no_ci_no_linting",
+];
+
+/// Score band lower bounds: "101" is 101+, "90" is 90-100, ..., "0" is 0-19.
+const SCORE_KEYS: &[&str] = &["101", "90", "80", "70", "60", "50", "40", "30", "20", "0"];
+
+/// Team-provided roasts (`--roasts` or `roasts` in the config), replacing the
+/// built-in pool for each condition or band they list. Anything not listed
+/// keeps the built-in roasts.
+///
+/// ```toml
+/// [conditions]
+/// env_in_git = ["Dave, the .env again?"]
+/// yolo = ["{loc} lines and not one test. Classic Dave."]
+///
+/// [scores]
+/// 90 = ["Ship it, the robots said so."]
+/// ```
+///
+/// The same shape is accepted as JSON when the file ends in `.json`.
+#[derive(Debug, Clone, Default)]
+pub struct CustomRoasts {
+    conditions: HashMap<String, Vec<String>>,
+    scores: HashMap<String, Vec<String>>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoastsFile {
+    #[serde(default)]
+    conditions: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    scores: BTreeMap<String, Vec<String>>,
+}
+
+impl CustomRoasts {
+    /// Load and validate a roasts file (TOML, or JSON for `.json` paths).
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let is_json = path.extension().is_some_and(|ext| ext == "json");
+        let file: Result<RoastsFile, String> = if is_json {
+            serde_json::from_str(&content).map_err(|e| e.to_string())
+        } else {
+            toml::from_str(&content).map_err(|e| e.to_string())
+        };
+        file.and_then(Self::validate)
+            .map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// Reject unknown condition/band names and empty pools rather than
+    /// silently never using them.
+    fn validate(file: RoastsFile) -> Result<Self, String> {
+        fn check(
+            section: &str,
+            pools: BTreeMap<String, Vec<String>>,
+            valid: &[&str],
+        ) -> Result<HashMap<String, Vec<String>>, String> {
+            for (key, roasts) in &pools {
+                if !valid.contains(&key.as_str()) {
+                    return Err(format!(
+                        "unknown {} key \"{}\" (expected one of: {})",
+                        section,
+                        key,
+                        valid.join(", ")
+                    ));
+                }
+                if roasts.is_empty() || roasts.iter().any(|r| r.trim().is_empty()) {
+                    return Err(format!(
+                        "{}.{} needs at least one non-empty roast",
+                        section, key
+                    ));
+                }
+            }
+            Ok(pools.into_iter().collect())
+        }
+        Ok(Self {
+            conditions: check("conditions", file.conditions, CONDITION_KEYS)?,
+            scores: check("scores", file.scores, SCORE_KEYS)?,
+        })
+    }
+
+    fn pool(&self, key: RoastKey) -> Option<&[String]> {
+        match key {
+            RoastKey::Condition(name) => self.conditions.get(name),
+            RoastKey::Score(band) => self.scores.get(band),
+        }
+        .map(Vec::as_slice)
+    }
+}
+
 /// Pick a fun roast tagline based on the score and project characteristics.
-pub fn pick_roast(points: u32, ai_ratio: f64, project: &ProjectStats) -> String {
+/// Each condition has a small pool of equivalent roasts; `seed` picks one,
+/// so a repo always gets the same roast but different repos get variety.
+/// `ai_ratio` is `None` without git history: no roast about AI use applies.
+pub fn pick_roast(
+    points: u32,
+    ai_ratio: Option<f64>,
+    project: &ProjectStats,
+    seed: u64,
+    custom: &CustomRoasts,
+) -> String {
+    let (key, builtin) = roast_pool(points, ai_ratio, project);
+    let roast = match custom.pool(key) {
+        Some(pool) => pool[pick_index(seed, pool)].as_str(),
+        None => builtin[pick_index(seed, builtin)],
+    };
+    roast.replace("{loc}", &fmt_loc(project.languages.total_lines))
+}
+
+/// Seed roast selection from a repo fingerprint (FNV-1a, stable across
+/// platforms and Rust versions, unlike `DefaultHasher`).
+pub fn seed_from(fingerprint: &str) -> u64 {
+    fnv1a(0xcbf2_9ce4_8422_2325, fingerprint.as_bytes())
+}
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Mix the pool's first entry into the seed so one repo doesn't always land
+/// on the same slot in every pool.
+fn pick_index<S: AsRef<str>>(seed: u64, pool: &[S]) -> usize {
+    (fnv1a(seed, pool[0].as_ref().as_bytes()) % pool.len() as u64) as usize
+}
+
+fn roast_pool(points: u32, ai_ratio: Option<f64>, project: &ProjectStats) -> (RoastKey, Pool) {
     // ── Contextual roasts (checked first, most specific wins) ──
 
+    if project.security.private_keys_count > 0 {
+        return (
+            RoastKey::Condition("private_keys"),
+            &[
+                "Your SSH key is open source now.",
+                "Private key, public repo. Pick one.",
+                "git push --keys-included",
+            ],
+        );
+    }
     if project.vibe.node_modules_in_git {
-        return "Committing node_modules. Bold strategy.".to_string();
+        return (
+            RoastKey::Condition("node_modules"),
+            &[
+                "Committing node_modules. Bold strategy.",
+                "node_modules in git. The repo is 98% other people's code.",
+                "Vendoring the entire npm registry, one commit at a time.",
+            ],
+        );
     }
     if project.vibe.boomer_ai {
-        return "Uses AI like a boomer uses email.".to_string();
-    }
-    if ai_ratio > 0.95 {
-        return "You're the project manager now.".to_string();
-    }
-    if ai_ratio > 0.9 && !project.tests.has_tests {
-        return "Vibe coded to production. No safety net.".to_string();
-    }
-    if ai_ratio == 0.0 {
-        return "Write code like it's 2019.".to_string();
+        return (
+            RoastKey::Condition("boomer_ai"),
+            &[
+                "Uses AI like a boomer uses email.",
+                "Copy-pastes from ChatGPT like it's 2023.",
+                "AI-assisted, manually delivered.",
+            ],
+        );
+    }
+    if ai_ratio.is_some_and(|r| r > 0.95) {
+        return (
+            RoastKey::Condition("project_manager"),
+            &[
+                "You're the project manager now.",
+                "You approve PRs. The AI writes them.",
+                "Technically, you're the AI's intern.",
+            ],
+        );
+    }
+    if ai_ratio.is_some_and(|r| r > 0.9) && !project.tests.has_test_files() {
+        return (
+            RoastKey::Condition("no_safety_net"),
+            &[
+                "Vibe coded to production. No safety net.",
+                "The AI wrote it. Nobody tested it. Ship it.",
+                "Trust falls with a language model.",
+            ],
+        );
+    }
+    if ai_ratio == Some(0.0) {
+        return (
+            RoastKey::Condition("no_ai"),
+            &[
+                "Write code like it's 2019.",
+                "Artisanal, hand-typed, no AI additives.",
+                "Still typing every semicolon by hand.",
+            ],
+        );
     }
     if project.security.env_files_count >= 3 {
-        return "Your secrets have secrets.".to_string();
+        return (
+            RoastKey::Condition("many_env_files"),
+            &[
+                "Your secrets have secrets.",
+                "An .env for every occasion.",
+                "Collecting .env files like Pokémon.",
+            ],
+        );
     }
     if project.security.env_in_git {
-        return "Secrets? What secrets?".to_string();
-    }
-    if project.deps.total > 500 {
-        return "node_modules is the real project.".to_string();
-    }
-    if !project.tests.has_tests && project.languages.total_lines > 10000 {
-        let loc = fmt_loc(project.languages.total_lines);
-        return format!("{} lines of YOLO.", loc);
+        return (
+            RoastKey::Condition("env_in_git"),
+            &[
+                "Secrets? What secrets?",
+                "The .env is in git. The secrets are in everyone's clone.",
+                "Environment variables, now with version control.",
+            ],
+        );
+    }
+    if weighed_deps(project) > HEAVY_DEPS as f64 {
+        return (
+            RoastKey::Condition("dependency_hell"),
+            &[
+                "node_modules is the real project.",
+                "Your dependencies have dependencies' dependencies.",
+                "More packages than lines you wrote.",
+            ],
+        );
+    }
+    if !project.tests.has_test_files() && project.languages.total_lines > 10000 {
+        return (
+            RoastKey::Condition("yolo"),
+            &[
+                "{loc} lines of YOLO.",
+                "{loc} lines, zero tests, infinite confidence.",
+                "{loc} lines tested in production.",
+            ],
+        );
     }
     if project.vibe.no_gitignore && project.vibe.no_readme {
-        return "No .gitignore, no README, no mercy.".to_string();
+        return (
+            RoastKey::Condition("no_gitignore_no_readme"),
+            &[
+                "No .gitignore, no README, no mercy.",
+                "Undocumented and unignored.",
+                "README? .gitignore? Never heard of them.",
+            ],
+        );
     }
     if project.vibe.todo_flood {
-        return "TODO: finish this project.".to_string();
-    }
-    if project.vibe.single_branch && ai_ratio > 0.5 {
-        return "One branch, one dream, one AI.".to_string();
+        return (
+            RoastKey::Condition("todo_flood"),
+            &[
+                "TODO: finish this project.",
+                "// TODO: write fewer TODOs",
+                "The backlog lives in the comments.",
+            ],
+        );
+    }
+    if project.vibe.low_effort_commits {
+        return (
+            RoastKey::Condition("low_effort_commits"),
+            &[
+                "\"fix\", \"wip\", \"asdf\": a commit log in three acts.",
+                "git log reads like a ransom note.",
+                "Commit messages by keyboard smash.",
+            ],
+        );
+    }
+    if project.vibe.single_branch && ai_ratio.is_some_and(|r| r > 0.5) {
+        return (
+            RoastKey::Condition("single_branch"),
+            &[
+                "One branch, one dream, one AI.",
+                "Branches are for trees.",
+                "main is the only branch you need. Apparently.",
+            ],
+        );
     }
     if project.vibe.no_ci_cd && project.vibe.no_linting {
-        return "Deploys from localhost. Formats with vibes.".to_string();
+        return (
+            RoastKey::Condition("no_ci_no_linting"),
+            &[
+                "Deploys from localhost. Formats with vibes.",
+                "CI stands for Crossed fIngers.",
+                "The linter is your gut feeling.",
+            ],
+        );
     }
 
     // ── Score-based fallback ──
     match points {
-        101.. => "Beyond vibe. You are the vibe.",
-        90..=100 => "The AI is the senior dev here.",
-        80..=89 => "You prompt, Claude delivers.",
-        70..=79 => "More vibes than version control.",
-        60..=69 => "Solid vibe-to-code ratio.",
-        50..=59 => "Half human, half machine.",
-        40..=49 => "Training wheels still on.",
-        30..=39 => "Mostly artisanal, free-range code.",
-        20..=29 => "You actually read the docs?",
-        _ => "Handcrafted with mass-produced tears.",
-    }
-    .to_string()
+        101.. => (
+            RoastKey::Score("101"),
+            &[
+                "Beyond vibe. You are the vibe.",
+                "Transcended code. Now pure vibes.",
+            ],
+        ),
+        90..=100 => (
+            RoastKey::Score("90"),
+            &[
+                "The AI is the senior dev here.",
+                "Your AI has commit access and a promotion.",
+            ],
+        ),
+        80..=89 => (
+            RoastKey::Score("80"),
+            &[
+                "You prompt, Claude delivers.",
+                "Prompt engineer, certified.",
+            ],
+        ),
+        70..=79 => (
+            RoastKey::Score("70"),
+            &[
+                "More vibes than version control.",
+                "Version-controlled vibes.",
+            ],
+        ),
+        60..=69 => (
+            RoastKey::Score("60"),
+            &["Solid vibe-to-code ratio.", "Vibing responsibly."],
+        ),
+        50..=59 => (
+            RoastKey::Score("50"),
+            &[
+                "Half human, half machine.",
+                "Centaur mode: half you, half model.",
+            ],
+        ),
+        40..=49 => (
+            RoastKey::Score("40"),
+            &["Training wheels still on.", "AI-curious."],
+        ),
+        30..=39 => (
+            RoastKey::Score("30"),
+            &[
+                "Mostly artisanal, free-range code.",
+                "Small-batch, hand-crafted commits.",
+            ],
+        ),
+        20..=29 => (
+            RoastKey::Score("20"),
+            &["You actually read the docs?", "Stack Overflow veteran."],
+        ),
+        _ => (
+            RoastKey::Score("0"),
+            &[
+                "Handcrafted with mass-produced tears.",
+                "Every bug is hand-made.",
+            ],
+        ),
+    }
 }
 
 fn fmt_loc(n: usize) -> String {
@@ -85,43 +405,66 @@ mod tests {
         ProjectStats {
             deps: DepsInfo {
                 total: 10,
+                direct: 10,
                 manager: "npm".into(),
             },
             tests: TestsInfo {
                 has_tests: true,
                 test_files_count: 5,
                 frameworks: vec![],
+                test_ratio: 0.0,
             },
             languages: LanguageStats {
                 languages: HashMap::new(),
                 total_lines: 5000,
+                code_lines: 5000,
+                config_docs_lines: 0,
+                generated_lines: 0,
             },
             security: SecurityInfo::default(),
             vibe: VibeInfo::default(),
+            monorepo: false,
+            license: None,
         }
     }
 
+    fn parse_roasts(content: &str) -> Result<CustomRoasts, String> {
+        toml::from_str(content)
+            .map_err(|e| e.to_string())
+            .and_then(CustomRoasts::validate)
+    }
+
+    /// Every roast the given state can produce.
+    fn roasts(points: u32, ai_ratio: f64, project: &ProjectStats) -> Vec<String> {
+        let loc = fmt_loc(project.languages.total_lines);
+        roast_pool(points, Some(ai_ratio), project)
+            .1
+            .iter()
+            .map(|r| r.replace("{loc}", &loc))
+            .collect()
+    }
+
     #[test]
     fn node_modules_in_git_roast() {
         let mut p = base_project();
         p.vibe.node_modules_in_git = true;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "Committing node_modules. Bold strategy.");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"Committing node_modules. Bold strategy.".to_string()));
     }
 
     #[test]
     fn boomer_ai_roast() {
         let mut p = base_project();
         p.vibe.boomer_ai = true;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "Uses AI like a boomer uses email.");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"Uses AI like a boomer uses email.".to_string()));
     }
 
     #[test]
     fn project_manager_roast() {
         let p = base_project();
-        let roast = pick_roast(60, 0.96, &p);
-        assert_eq!(roast, "You're the project manager now.");
+        let roast = roasts(60, 0.96, &p);
+        assert!(roast.contains(&"You're the project manager now.".to_string()));
     }
 
     #[test]
@@ -129,15 +472,22 @@ mod tests {
         let mut p = base_project();
         p.tests.has_tests = false;
         p.tests.test_files_count = 0;
-        let roast = pick_roast(80, 0.92, &p);
-        assert_eq!(roast, "Vibe coded to production. No safety net.");
+        let roast = roasts(80, 0.92, &p);
+        assert!(roast.contains(&"Vibe coded to production. No safety net.".to_string()));
     }
 
     #[test]
     fn zero_ai_roast() {
         let p = base_project();
-        let roast = pick_roast(10, 0.0, &p);
-        assert_eq!(roast, "Write code like it's 2019.");
+        let roast = roasts(10, 0.0, &p);
+        assert!(roast.contains(&"Write code like it's 2019.".to_string()));
+    }
+
+    #[test]
+    fn no_history_gets_no_ai_roast() {
+        // A --no-git scan has a 0 AI ratio only because nothing was read
+        let p = base_project();
+        assert_eq!(roast_pool(10, None, &p).0, RoastKey::Score("0"));
     }
 
     #[test]
@@ -145,8 +495,8 @@ mod tests {
         let mut p = base_project();
         p.security.env_files_count = 3;
         p.security.env_in_git = true;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "Your secrets have secrets.");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"Your secrets have secrets.".to_string()));
     }
 
     #[test]
@@ -154,16 +504,20 @@ mod tests {
         let mut p = base_project();
         p.security.env_in_git = true;
         p.security.env_files_count = 1;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "Secrets? What secrets?");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"Secrets? What secrets?".to_string()));
     }
 
     #[test]
     fn huge_deps_roast() {
         let mut p = base_project();
-        p.deps.total = 600;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "node_modules is the real project.");
+        p.deps.total = 3000;
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"node_modules is the real project.".to_string()));
+        // A monorepo's shared lockfile gets the same allowance as the score
+        p.monorepo = true;
+        let roast = roasts(50, 0.5, &p);
+        assert!(!roast.contains(&"node_modules is the real project.".to_string()));
     }
 
     #[test]
@@ -172,8 +526,18 @@ mod tests {
         p.tests.has_tests = false;
         p.tests.test_files_count = 0;
         p.languages.total_lines = 15000;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "15K lines of YOLO.");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"15K lines of YOLO.".to_string()));
+    }
+
+    #[test]
+    fn test_config_without_test_files_is_still_yolo() {
+        let mut p = base_project();
+        p.tests.has_tests = true;
+        p.tests.test_files_count = 0;
+        p.languages.total_lines = 15000;
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"15K lines of YOLO.".to_string()));
     }
 
     #[test]
@@ -181,8 +545,8 @@ mod tests {
         let mut p = base_project();
         p.vibe.no_gitignore = true;
         p.vibe.no_readme = true;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "No .gitignore, no README, no mercy.");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"No .gitignore, no README, no mercy.".to_string()));
     }
 
     #[test]
@@ -190,16 +554,24 @@ mod tests {
         let mut p = base_project();
         p.vibe.todo_flood = true;
         p.vibe.todo_count = 25;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "TODO: finish this project.");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"TODO: finish this project.".to_string()));
+    }
+
+    #[test]
+    fn low_effort_commits_roast() {
+        let mut p = base_project();
+        p.vibe.low_effort_commits = true;
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"git log reads like a ransom note.".to_string()));
     }
 
     #[test]
     fn single_branch_high_ai_roast() {
         let mut p = base_project();
         p.vibe.single_branch = true;
-        let roast = pick_roast(50, 0.6, &p);
-        assert_eq!(roast, "One branch, one dream, one AI.");
+        let roast = roasts(50, 0.6, &p);
+        assert!(roast.contains(&"One branch, one dream, one AI.".to_string()));
     }
 
     #[test]
@@ -207,29 +579,29 @@ mod tests {
         let mut p = base_project();
         p.vibe.no_ci_cd = true;
         p.vibe.no_linting = true;
-        let roast = pick_roast(50, 0.5, &p);
-        assert_eq!(roast, "Deploys from localhost. Formats with vibes.");
+        let roast = roasts(50, 0.5, &p);
+        assert!(roast.contains(&"Deploys from localhost. Formats with vibes.".to_string()));
     }
 
     #[test]
     fn score_based_fallback_high() {
         let p = base_project();
-        let roast = pick_roast(105, 0.5, &p);
-        assert_eq!(roast, "Beyond vibe. You are the vibe.");
+        let roast = roasts(105, 0.5, &p);
+        assert!(roast.contains(&"Beyond vibe. You are the vibe.".to_string()));
     }
 
     #[test]
     fn score_based_fallback_mid() {
         let p = base_project();
-        let roast = pick_roast(55, 0.5, &p);
-        assert_eq!(roast, "Half human, half machine.");
+        let roast = roasts(55, 0.5, &p);
+        assert!(roast.contains(&"Half human, half machine.".to_string()));
     }
 
     #[test]
     fn score_based_fallback_low() {
         let p = base_project();
-        let roast = pick_roast(5, 0.3, &p);
-        assert_eq!(roast, "Handcrafted with mass-produced tears.");
+        let roast = roasts(5, 0.3, &p);
+        assert!(roast.contains(&"Handcrafted with mass-produced tears.".to_string()));
     }
 
     #[test]
@@ -237,8 +609,8 @@ mod tests {
         // node_modules_in_git should trigger even with score of 105
         let mut p = base_project();
         p.vibe.node_modules_in_git = true;
-        let roast = pick_roast(105, 0.5, &p);
-        assert_eq!(roast, "Committing node_modules. Bold strategy.");
+        let roast = roasts(105, 0.5, &p);
+        assert!(roast.contains(&"Committing node_modules. Bold strategy.".to_string()));
     }
 
     #[test]
@@ -246,9 +618,9 @@ mod tests {
         let mut p = base_project();
         p.vibe.node_modules_in_git = true;
         p.vibe.boomer_ai = true;
-        let roast = pick_roast(50, 0.5, &p);
+        let roast = roasts(50, 0.5, &p);
         // node_modules_in_git is checked first
-        assert_eq!(roast, "Committing node_modules. Bold strategy.");
+        assert!(roast.contains(&"Committing node_modules. Bold strategy.".to_string()));
     }
 
     #[test]
@@ -256,8 +628,133 @@ mod tests {
         // single_branch with ai_ratio <= 0.5 should NOT trigger the single_branch roast
         let mut p = base_project();
         p.vibe.single_branch = true;
-        let roast = pick_roast(55, 0.4, &p);
+        let roast = roasts(55, 0.4, &p);
         // Falls through to score-based
-        assert_eq!(roast, "Half human, half machine.");
+        assert!(roast.contains(&"Half human, half machine.".to_string()));
+    }
+
+    #[test]
+    fn same_seed_same_roast() {
+        let p = base_project();
+        let seed = seed_from("github.com/user/repo");
+        assert_eq!(
+            pick_roast(55, Some(0.5), &p, seed, &CustomRoasts::default()),
+            pick_roast(55, Some(0.5), &p, seed, &CustomRoasts::default())
+        );
+    }
+
+    #[test]
+    fn seeds_spread_over_the_pool() {
+        let mut p = base_project();
+        p.security.env_in_git = true;
+        p.security.env_files_count = 1;
+        let pool = roasts(50, 0.5, &p);
+        let none = CustomRoasts::default();
+        let picked: std::collections::HashSet<String> = (0..50)
+            .map(|i| pick_roast(50, Some(0.5), &p, seed_from(&format!("repo-{}", i)), &none))
+            .collect();
+        assert!(picked.iter().all(|r| pool.contains(r)));
+        assert!(
+            picked.len() > 1,
+            "different repos should get different roasts"
+        );
+    }
+
+    #[test]
+    fn picked_roast_fills_in_line_count() {
+        let mut p = base_project();
+        p.tests.has_tests = false;
+        p.languages.total_lines = 15000;
+        for seed in 0..10 {
+            let roast = pick_roast(50, Some(0.5), &p, seed, &CustomRoasts::default());
+            assert!(roast.starts_with("15K lines"), "{}", roast);
+        }
+    }
+
+    #[test]
+    fn seed_from_is_stable() {
+        // FNV-1a test vector: must not change across releases
+        assert_eq!(seed_from("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn custom_roasts_replace_listed_pools_only() {
+        let custom = parse_roasts(
+            "[conditions]\nenv_in_git = [\"Dave, the .env again?\"]\n\n[scores]\n50 = [\"Team half-and-half.\"]\n",
+        )
+        .unwrap();
+        let mut p = base_project();
+        assert_eq!(
+            pick_roast(55, Some(0.5), &p, 1, &custom),
+            "Team half-and-half."
+        );
+
+        p.security.env_in_git = true;
+        p.security.env_files_count = 1;
+        assert_eq!(
+            pick_roast(55, Some(0.5), &p, 1, &custom),
+            "Dave, the .env again?"
+        );
+
+        // Not in the file: built-in pool
+        p.vibe.node_modules_in_git = true;
+        let roast = pick_roast(55, Some(0.5), &p, 1, &custom);
+        assert!(roasts(55, 0.5, &p).contains(&roast));
+    }
+
+    #[test]
+    fn custom_roasts_fill_in_line_count() {
+        let custom = parse_roasts("[conditions]\nyolo = [\"{loc} untested lines.\"]\n").unwrap();
+        let mut p = base_project();
+        p.tests.has_tests = false;
+        p.languages.total_lines = 15000;
+        assert_eq!(
+            pick_roast(50, Some(0.5), &p, 0, &custom),
+            "15K untested lines."
+        );
+    }
+
+    #[test]
+    fn custom_roasts_reject_bad_shapes() {
+        let err = parse_roasts("[conditions]\nenv_in_gti = [\"x\"]\n").unwrap_err();
+        assert!(
+            err.contains("unknown conditions key \"env_in_gti\""),
+            "{}",
+            err
+        );
+        let err = parse_roasts("[scores]\n55 = [\"x\"]\n").unwrap_err();
+        assert!(err.contains("unknown scores key \"55\""), "{}", err);
+        let err = parse_roasts("[scores]\n90 = []\n").unwrap_err();
+        assert!(err.contains("scores.90 needs at least one"), "{}", err);
+        assert!(parse_roasts("[roasts]\nx = 1\n").is_err());
+    }
+
+    #[test]
+    fn custom_roasts_load_json() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("roasts.json");
+        std::fs::write(&path, r#"{"scores": {"0": ["Artisanal."]}}"#).unwrap();
+        let custom = CustomRoasts::load(&path).unwrap();
+        assert_eq!(
+            pick_roast(5, Some(0.3), &base_project(), 0, &custom),
+            "Artisanal."
+        );
+    }
+
+    #[test]
+    fn every_pool_key_is_known() {
+        // Keys returned by roast_pool must be listed so custom files can use them
+        let mut p = base_project();
+        for points in [0, 25, 35, 45, 55, 65, 75, 85, 95, 105] {
+            match roast_pool(points, Some(0.5), &p).0 {
+                RoastKey::Score(k) => assert!(SCORE_KEYS.contains(&k)),
+                RoastKey::Condition(k) => panic!("unexpected condition {}", k),
+            }
+        }
+        p.security.private_keys_count = 1;
+        assert_eq!(
+            roast_pool(50, Some(0.5), &p).0,
+            RoastKey::Condition("private_keys")
+        );
     }
 }
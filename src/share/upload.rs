@@ -9,7 +9,11 @@ pub struct ReportPayload {
     pub score_points: u32,
     pub score_grade: String,
     pub roast: String,
+    /// Resolved dependencies (`DepsInfo::total`), what the deps-bloat points use.
     pub deps_count: usize,
+    /// Monorepo tooling found: the web report divides `deps_count` by the
+    /// same allowance as the CLI's deps-bloat factor.
+    pub monorepo: bool,
     pub has_tests: bool,
     pub total_lines: usize,
     pub total_commits: usize,
@@ -19,6 +23,16 @@ pub struct ReportPayload {
     pub chaos_badges: String, // JSON array of badge names
 }
 
+impl ReportPayload {
+    /// `--anonymous`: drop the owner and repo names and hash the fingerprint,
+    /// so nothing in the payload names the repo.
+    pub fn anonymize(&mut self) {
+        self.github_username = None;
+        self.repo_name = None;
+        self.repo_fingerprint = self.repo_fingerprint.as_deref().map(anonymize_fingerprint);
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
 pub struct ShareResponse {
@@ -28,8 +42,39 @@ pub struct ShareResponse {
     pub percentile: Option<f64>,
 }
 
+/// Public salt for `--anonymous`: it keeps hashed fingerprints apart from
+/// plain SHA-1s of the same string, but it is not a secret.
+const FINGERPRINT_SALT: &str = "vibereport-fingerprint-v1";
+
+/// `--anonymous`: send `anon:<salted sha1>` instead of the raw fingerprint
+/// (remote URL / root commit). The same repo always hashes to the same value,
+/// so the API can still deduplicate without seeing the URL.
+fn anonymize_fingerprint(fingerprint: &str) -> String {
+    let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+    hasher.update(FINGERPRINT_SALT.as_bytes());
+    hasher.update(fingerprint.as_bytes());
+    match hasher.try_finalize() {
+        Ok(id) => format!("anon:{}", id),
+        // Only on a detected SHA-1 collision attack; never send the raw value
+        Err(_) => "anon:".to_string(),
+    }
+}
+
 const API_URL: &str = "https://vibereport-api.clement-serizay.workers.dev";
 
+/// API base URL: `VIBEREPORT_API_URL` (for self-hosting and testing against
+/// a local server), falling back to the public API.
+fn api_url() -> String {
+    resolve_api_url(std::env::var("VIBEREPORT_API_URL").ok())
+}
+
+fn resolve_api_url(from_env: Option<String>) -> String {
+    from_env
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| API_URL.to_string())
+}
+
 /// Upload a report to the vibereport.dev API.
 /// Returns the share URL and leaderboard rank.
 pub fn upload_report(payload: &ReportPayload) -> Result<ShareResponse, Box<dyn std::error::Error>> {
@@ -37,7 +82,7 @@ pub fn upload_report(payload: &ReportPayload) -> Result<ShareResponse, Box<dyn s
         .timeout(std::time::Duration::from_secs(10))
         .build()?;
     let resp = client
-        .post(format!("{}/api/reports", API_URL))
+        .post(format!("{}/api/reports", api_url()))
         .json(payload)
         .send()?;
 
@@ -55,6 +100,16 @@ pub fn upload_report(payload: &ReportPayload) -> Result<ShareResponse, Box<dyn s
 mod tests {
     use super::*;
 
+    #[test]
+    fn api_url_env_overrides_default() {
+        assert_eq!(
+            resolve_api_url(Some("http://localhost:8787/".into())),
+            "http://localhost:8787"
+        );
+        assert_eq!(resolve_api_url(Some("  ".into())), API_URL);
+        assert_eq!(resolve_api_url(None), API_URL);
+    }
+
     #[test]
     fn payload_serializes_correctly() {
         let payload = ReportPayload {
@@ -66,6 +121,7 @@ mod tests {
             score_grade: "B+".into(),
             roast: "Ships fast, tests never".into(),
             deps_count: 42,
+            monorepo: false,
             has_tests: false,
             total_lines: 5000,
             total_commits: 100,
@@ -81,6 +137,7 @@ mod tests {
         assert!(json["github_username"].is_string());
         assert_eq!(json["total_commits"], 100);
         assert_eq!(json["ai_commits"], 75);
+        assert_eq!(json["monorepo"], false);
         assert_eq!(json["chaos_badges"], r#"["no-tests","boomer-ai"]"#);
     }
 
@@ -95,6 +152,7 @@ mod tests {
             score_grade: "F".into(),
             roast: "Write code like it's 2019.".into(),
             deps_count: 0,
+            monorepo: false,
             has_tests: true,
             total_lines: 100,
             total_commits: 10,
@@ -112,6 +170,49 @@ mod tests {
         assert_eq!(json["chaos_badges"], "[]");
     }
 
+    #[test]
+    fn anonymized_fingerprint_is_stable_and_hides_the_url() {
+        let fingerprint = "https://github.com/user/repo";
+        let anon = anonymize_fingerprint(fingerprint);
+        assert_eq!(anon, anonymize_fingerprint(fingerprint));
+        assert!(anon.starts_with("anon:"));
+        assert_eq!(anon.len(), "anon:".len() + 40);
+        assert!(!anon.contains("github"));
+        assert_ne!(anon, anonymize_fingerprint("https://github.com/user/other"));
+    }
+
+    #[test]
+    fn anonymized_payload_names_neither_owner_nor_repo() {
+        let mut payload = ReportPayload {
+            github_username: Some("octocat".into()),
+            repo_name: Some("secret-project".into()),
+            ai_ratio: 0.5,
+            ai_tool: "Claude Code".into(),
+            score_points: 50,
+            score_grade: "C".into(),
+            roast: "Mid.".into(),
+            deps_count: 10,
+            monorepo: false,
+            has_tests: true,
+            total_lines: 1000,
+            total_commits: 50,
+            ai_commits: 25,
+            languages: "{}".into(),
+            repo_fingerprint: Some("github.com/octocat/secret-project".into()),
+            chaos_badges: "[]".into(),
+        };
+        payload.anonymize();
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(!json.contains("octocat"), "{}", json);
+        assert!(!json.contains("secret-project"), "{}", json);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(json["github_username"].is_null());
+        assert!(json["repo_name"].is_null());
+        assert!(json["repo_fingerprint"]
+            .as_str()
+            .is_some_and(|f| f.starts_with("anon:")));
+    }
+
     #[test]
     fn payload_includes_fingerprint_in_json() {
         let fingerprint =
@@ -125,6 +226,7 @@ mod tests {
             score_grade: "C".into(),
             roast: "Mid.".into(),
             deps_count: 10,
+            monorepo: false,
             has_tests: true,
             total_lines: 1000,
             total_commits: 50,
@@ -1,11 +1,22 @@
-use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use axum::{
+    extract::{self, ConnectInfo, State},
+    http::{header, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+    Json, Router,
+};
 use chrono::Datelike;
 use futures::stream::{self, StreamExt};
 use regex::Regex;
 use serde::Deserialize;
-use std::sync::{Arc, LazyLock};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use subtle::ConstantTimeEq;
-use tokio::sync::Semaphore;
+use tokio::sync::{watch, Notify, Semaphore};
 use uuid::Uuid;
 
 // FIX 1: Regex patterns for repo URL validation
@@ -19,12 +30,300 @@ static REPO_SLUG_RE: LazyLock<Regex> =
 static SINCE_DATE_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap());
 
+/// `schema_version` of the vibereport `--json` output this worker understands.
+/// Keep in sync with `JSON_SCHEMA_VERSION` in the CLI.
+const SUPPORTED_SCHEMA_VERSION: u64 = 4;
+
+/// Whether a vibereport report has the JSON shape this worker was written for.
+fn is_supported_report(data: &serde_json::Value) -> bool {
+    data["schema_version"].as_u64() == Some(SUPPORTED_SCHEMA_VERSION)
+}
+
+/// Concurrent user web scans.
+const USER_SCAN_SLOTS: usize = 2;
+/// Concurrent repo scans during the index cron.
+const INDEX_SCAN_SLOTS: usize = 10;
+
+/// Prometheus counters for one kind of scan (user or index).
+#[derive(Default)]
+struct ScanCounters {
+    scans: AtomicU64,
+    failed: AtomicU64,
+    clone_timeouts: AtomicU64,
+    analysis_timeouts: AtomicU64,
+    disk_rejections: AtomicU64,
+}
+
+impl ScanCounters {
+    fn inc(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+struct Metrics {
+    user: ScanCounters,
+    index: ScanCounters,
+}
+
+/// Default user scans per minute per client IP (`SCAN_RATE_LIMIT`, 0 disables).
+const DEFAULT_SCAN_RATE_LIMIT: u32 = 5;
+/// Default user scan limits: long enough for most repos, short enough that
+/// a giant one can't hold a user slot for long.
+const DEFAULT_USER_CLONE_TIMEOUT_SECS: u64 = 120;
+const DEFAULT_USER_ANALYZE_TIMEOUT_SECS: u64 = 60;
+/// Default disk limits for scan clones (`SCAN_MIN_FREE_MB`, `SCAN_MAX_CLONE_MB`).
+const DEFAULT_MIN_FREE_MB: u64 = 2048;
+const DEFAULT_MAX_CLONE_MB: u64 = 1024;
+/// How often a running clone's size is checked against `SCAN_MAX_CLONE_MB`.
+const CLONE_SIZE_POLL: Duration = Duration::from_secs(1);
+/// Buckets kept before idle (full) ones are dropped.
+const RATE_LIMIT_MAX_TRACKED: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket per client IP: `per_minute` burst, refilled continuously.
+struct RateLimiter {
+    per_minute: u32,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take one token for `ip`; false when its bucket is empty.
+    fn check(&self, ip: IpAddr) -> bool {
+        if self.per_minute == 0 {
+            return true;
+        }
+        let capacity = self.per_minute as f64;
+        let refill = |bucket: &Bucket, now: Instant| {
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+            (bucket.tokens + elapsed * capacity / 60.0).min(capacity)
+        };
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= RATE_LIMIT_MAX_TRACKED {
+            buckets.retain(|_, bucket| refill(bucket, now) < capacity);
+        }
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        bucket.tokens = refill(bucket, now);
+        bucket.updated = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+/// Client IP for rate limiting: `CF-Connecting-IP`, which Cloudflare's edge
+/// sets to whoever reached the tunnel (a client can't forge it, unlike
+/// `X-Forwarded-For`), else a non-loopback socket address. None for a local
+/// call with neither (cloudflared itself connects from loopback, so that
+/// address would put every client in one bucket).
+fn client_ip(headers: &axum::http::HeaderMap, peer: SocketAddr) -> Option<IpAddr> {
+    headers
+        .get("cf-connecting-ip")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .or_else(|| Some(peer.ip()).filter(|ip| !ip.is_loopback()))
+}
+
+/// How often batches left in `RESULTS_DIR` are re-posted while the API is down.
+const RESEND_INTERVAL: Duration = Duration::from_secs(600);
+
+/// How long a finished index scan stays readable on /scan-status.
+const FINISHED_SCAN_TTL: Duration = Duration::from_secs(3600);
+
+/// Live state of one background index scan, streamed by /scan-status/{id}.
+#[derive(serde::Serialize, Clone)]
+struct IndexScanProgress {
+    total: usize,
+    completed: usize,
+    failed: usize,
+    failed_repos: Vec<String>,
+    done: bool,
+    /// Set when the scan task died before posting its results.
+    error: Option<String>,
+    #[serde(skip)]
+    finished_at: Option<Instant>,
+}
+
+/// Marks an index scan done however its task ends: dropped without `finish`
+/// (the task panicked or was cancelled), it also sets `error`, so /scan-status
+/// closes instead of waiting forever.
+struct ScanFinisher {
+    progress: Arc<watch::Sender<IndexScanProgress>>,
+    finished: bool,
+}
+
+impl ScanFinisher {
+    fn new(progress: Arc<watch::Sender<IndexScanProgress>>) -> Self {
+        Self {
+            progress,
+            finished: false,
+        }
+    }
+
+    fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for ScanFinisher {
+    fn drop(&mut self) {
+        let finished = self.finished;
+        self.progress.send_modify(|p| {
+            p.done = true;
+            p.finished_at = Some(Instant::now());
+            if !finished {
+                p.error = Some("Index scan stopped before posting its results".into());
+            }
+        });
+    }
+}
+
+/// Disk budget for scan clones under `/tmp`, in bytes.
+#[derive(Clone, Copy)]
+struct DiskLimits {
+    /// Space that must be left on `/tmp`, past what clones in flight may
+    /// still take, before a clone starts.
+    min_free: u64,
+    /// Largest clone analyzed; 0 disables the cap.
+    max_clone: u64,
+}
+
+/// `DiskLimits` plus the space promised to clones in flight: each one holds
+/// `max_clone` bytes until it's removed, so concurrent scans can't all pass the
+/// free-space check and then fill `/tmp` together.
+struct DiskBudget {
+    limits: DiskLimits,
+    reserved: Mutex<u64>,
+}
+
+/// One clone's share of the `DiskBudget`, given back on drop.
+struct DiskReservation<'a>(&'a DiskBudget);
+
+impl DiskBudget {
+    fn new(limits: DiskLimits) -> Self {
+        Self {
+            limits,
+            reserved: Mutex::new(0),
+        }
+    }
+
+    /// Refuse to start a clone when `/tmp`, less what other clones have
+    /// reserved, is under `min_free`; the error is the bytes left.
+    fn reserve(&self) -> Result<DiskReservation<'_>, u64> {
+        match rustix::fs::statvfs("/tmp") {
+            Ok(st) => self.reserve_from(st.f_bavail.saturating_mul(st.f_frsize)),
+            // Can't tell: let the clone try rather than refusing every scan
+            Err(e) => {
+                tracing::warn!("statvfs /tmp failed: {}", e);
+                self.reserve_from(u64::MAX)
+            }
+        }
+    }
+
+    fn reserve_from(&self, free: u64) -> Result<DiskReservation<'_>, u64> {
+        let mut reserved = self.reserved.lock().unwrap_or_else(|e| e.into_inner());
+        let left = free.saturating_sub(*reserved);
+        if left < self.limits.min_free {
+            return Err(left);
+        }
+        *reserved += self.limits.max_clone;
+        Ok(DiskReservation(self))
+    }
+}
+
+impl Drop for DiskReservation<'_> {
+    fn drop(&mut self) {
+        let mut reserved = self.0.reserved.lock().unwrap_or_else(|e| e.into_inner());
+        *reserved -= self.0.limits.max_clone;
+    }
+}
+
+/// The vibereport that scans run (`VIBEREPORT_BIN`) and the JSON flag it takes.
+struct Vibereport {
+    bin: String,
+    json_flag: &'static str,
+}
+
+impl Vibereport {
+    /// Probe `bin --help` once at startup, so a binary older than
+    /// `--json-compact` still works (with `--json`) during a staggered deploy.
+    async fn detect(bin: String) -> Self {
+        let help = match tokio::process::Command::new(&bin)
+            .arg("--help")
+            .output()
+            .await
+        {
+            Ok(out) => String::from_utf8_lossy(&out.stdout).into_owned(),
+            Err(e) => {
+                tracing::warn!("Could not run {} --help: {}", bin, e);
+                String::new()
+            }
+        };
+        let json_flag = json_flag_for(&help);
+        if json_flag != "--json-compact" {
+            tracing::warn!("{} has no --json-compact; scanning with {}", bin, json_flag);
+        }
+        Self { bin, json_flag }
+    }
+}
+
+/// `--json-compact` when `help` lists it, else the older pretty `--json`.
+fn json_flag_for(help: &str) -> &'static str {
+    if help.contains("--json-compact") {
+        "--json-compact"
+    } else {
+        "--json"
+    }
+}
+
 struct AppState {
-    user_semaphore: Semaphore,  // 2 slots for user web scans
-    index_semaphore: Semaphore, // 3 slots for index cron
+    user_semaphore: Semaphore,  // USER_SCAN_SLOTS user web scans
+    index_semaphore: Semaphore, // INDEX_SCAN_SLOTS repos for the index cron
     auth_token: String,
-    vibereport_bin: String,
+    vibereport: Vibereport,
     api_url: String, // FIX 2: api_url from env, not from request
+    metrics: Metrics,
+    scan_rate_limiter: RateLimiter,
+    index_scans: Mutex<HashMap<String, Arc<watch::Sender<IndexScanProgress>>>>,
+    /// Index result batches not yet accepted by the API (`RESULTS_DIR`).
+    results_dir: PathBuf,
+    /// Wakes `resend_pending_results` once a scan has persisted its batches.
+    pending_results: Notify,
+    /// Limits for one user scan's clone and analysis
+    /// (`SCAN_CLONE_TIMEOUT_SECS`, `SCAN_ANALYZE_TIMEOUT_SECS`).
+    user_clone_timeout: Duration,
+    user_analyze_timeout: Duration,
+    /// Fixed start of index scan history (`INDEX_SINCE`); the current
+    /// quarter's first day when unset.
+    index_since: Option<String>,
+    disk: DiskBudget,
+}
+
+/// Whether `Authorization: Bearer <token>` matches (FIX 4: constant-time comparison).
+fn bearer_authorized(headers: &axum::http::HeaderMap, auth_token: &str) -> bool {
+    let auth = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let expected = format!("Bearer {}", auth_token);
+    auth.as_bytes().ct_eq(expected.as_bytes()).into()
 }
 
 #[derive(Deserialize)]
@@ -35,18 +334,61 @@ struct ScanRequest {
 
 async fn scan_handler(
     State(state): State<Arc<AppState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     headers: axum::http::HeaderMap,
     Json(req): Json<ScanRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    // Auth check (FIX 4: constant-time comparison)
-    let auth = headers
-        .get("authorization")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
-    let expected = format!("Bearer {}", state.auth_token);
-    if auth.as_bytes().ct_eq(expected.as_bytes()).unwrap_u8() != 1 {
+    if !bearer_authorized(&headers, &state.auth_token) {
         return Err((StatusCode::UNAUTHORIZED, "Invalid token".into()));
     }
+    user_scan(&state, &headers, peer, req).await
+}
+
+/// `GET /scan?repo=user/repo&since=YYYY-MM-DD` for links and GET-only
+/// webhooks. The token comes from the usual header or `&token=`.
+#[derive(Deserialize)]
+struct ScanQuery {
+    repo: String,
+    since: Option<String>,
+    token: Option<String>,
+}
+
+async fn scan_get_handler(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    extract::Query(query): extract::Query<ScanQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let query_authorized = query
+        .token
+        .as_deref()
+        .is_some_and(|t| t.as_bytes().ct_eq(state.auth_token.as_bytes()).into());
+    if !query_authorized && !bearer_authorized(&headers, &state.auth_token) {
+        return Err((StatusCode::UNAUTHORIZED, "Invalid token".into()));
+    }
+    let req = ScanRequest {
+        repo: query.repo,
+        since: query.since,
+    };
+    user_scan(&state, &headers, peer, req).await
+}
+
+/// Rate limit, then run an authorized user scan under the user semaphore.
+async fn user_scan(
+    state: &AppState,
+    headers: &axum::http::HeaderMap,
+    peer: SocketAddr,
+    req: ScanRequest,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    if let Some(ip) = client_ip(headers, peer) {
+        if !state.scan_rate_limiter.check(ip) {
+            tracing::warn!("Rate limit exceeded for {}", ip);
+            return Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                "Rate limit exceeded, try again later".into(),
+            ));
+        }
+    }
 
     // Acquire user semaphore (max 2 concurrent user scans)
     let _permit = state.user_semaphore.acquire().await.map_err(|_| {
@@ -56,9 +398,114 @@ async fn scan_handler(
         )
     })?;
 
-    let uuid = Uuid::new_v4().to_string();
-    let tmp_dir = format!("/tmp/vibereport-{}", uuid);
+    let counters = &state.metrics.user;
+    ScanCounters::inc(&counters.scans);
+    let result = run_user_scan(state, req).await;
+    if result.is_err() {
+        ScanCounters::inc(&counters.failed);
+    }
+    result
+}
+
+// ── Scan temp dirs ──
+
+/// A clone directory under `/tmp`, removed when dropped so no early return,
+/// timeout or panic leaves it behind.
+struct ScanDir(PathBuf);
+
+impl ScanDir {
+    fn new(prefix: &str) -> Self {
+        ScanDir(PathBuf::from(format!("/tmp/{}-{}", prefix, Uuid::new_v4())))
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().unwrap_or_default()
+    }
+}
+
+impl Drop for ScanDir {
+    fn drop(&mut self) {
+        let path = std::mem::take(&mut self.0);
+        let remove = move || match std::fs::remove_dir_all(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!("Failed to remove {}: {}", path.display(), e),
+        };
+        // Off the async workers when there is a runtime to hand it to
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => drop(handle.spawn_blocking(remove)),
+            Err(_) => remove(),
+        }
+    }
+}
+
+/// Refuse a clone bigger than `max_clone`; the error is its size.
+async fn check_clone_size(dir: &ScanDir, limits: DiskLimits) -> Result<(), u64> {
+    if limits.max_clone == 0 {
+        return Ok(());
+    }
+    let path = dir.0.clone();
+    let size = tokio::task::spawn_blocking(move || dir_size(&path))
+        .await
+        .unwrap_or(0);
+    if size > limits.max_clone {
+        return Err(size);
+    }
+    Ok(())
+}
+
+/// Await `git clone`, killing it (`kill_on_drop`) as soon as `dir` grows past
+/// `max_clone` rather than letting a huge repo fill `/tmp` first; the error is
+/// the size it reached. Checked every `CLONE_SIZE_POLL`, so callers still check
+/// the finished clone.
+async fn clone_within_cap(
+    clone: impl std::future::Future<Output = std::io::Result<std::process::Output>>,
+    dir: &ScanDir,
+    limits: DiskLimits,
+) -> Result<std::io::Result<std::process::Output>, u64> {
+    let watchdog = async {
+        loop {
+            tokio::time::sleep(CLONE_SIZE_POLL).await;
+            if let Err(size) = check_clone_size(dir, limits).await {
+                return size;
+            }
+        }
+    };
+    tokio::select! {
+        output = clone => Ok(output),
+        size = watchdog => Err(size),
+    }
+}
+
+/// Apparent size of everything under `root` (what `du -s --apparent-size`
+/// reports); symlinks are not followed.
+fn dir_size(root: &Path) -> u64 {
+    let mut total = 0;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            match entry.metadata() {
+                Ok(meta) if meta.is_dir() => stack.push(entry.path()),
+                Ok(meta) => total += meta.len(),
+                Err(_) => {}
+            }
+        }
+    }
+    total
+}
+
+fn megabytes(bytes: u64) -> u64 {
+    bytes / (1024 * 1024)
+}
 
+/// Validate, clone and analyze one repo for a user web scan.
+async fn run_user_scan(
+    state: &AppState,
+    req: ScanRequest,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     // FIX 3: Validate since parameter
     let since = req.since.unwrap_or_else(|| "2025-01-01".into());
     if !SINCE_DATE_RE.is_match(&since) {
@@ -88,25 +535,56 @@ async fn scan_handler(
         format!("https://github.com/{}.git", cleaned)
     };
 
-    // Clone
-    let clone_result = tokio::process::Command::new("git")
+    let limits = state.disk.limits;
+    let _reservation = state.disk.reserve().map_err(|free| {
+        ScanCounters::inc(&state.metrics.user.disk_rejections);
+        tracing::warn!(
+            "Refusing scan of {}: only {} MB free on /tmp",
+            repo_url,
+            megabytes(free)
+        );
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Worker is low on disk space, try again later".into(),
+        )
+    })?;
+
+    // Clone. Not `--filter=blob:none`: vibereport diffs old blobs for line
+    // counts, so the timeout and the size cap bound a huge repo.
+    let tmp_dir = ScanDir::new("vibereport");
+    let clone_fut = tokio::process::Command::new("git")
         .args([
             "clone",
             &format!("--shallow-since={}", since),
             &repo_url,
-            &tmp_dir,
+            tmp_dir.path(),
         ])
-        .output()
-        .await
-        .map_err(|e| {
+        .kill_on_drop(true)
+        .output();
+    let capped_clone = clone_within_cap(clone_fut, &tmp_dir, limits);
+    let clone_result = match tokio::time::timeout(state.user_clone_timeout, capped_clone).await {
+        Ok(Err(size)) => return Err(clone_too_large(state, &repo_url, size)),
+        Ok(Ok(result)) => result.map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Clone failed: {}", e),
             )
-        })?;
+        })?,
+        Err(_) => {
+            ScanCounters::inc(&state.metrics.user.clone_timeouts);
+            tracing::warn!(
+                "Clone timed out for {} ({}s)",
+                repo_url,
+                state.user_clone_timeout.as_secs()
+            );
+            return Err((
+                StatusCode::GATEWAY_TIMEOUT,
+                "Clone timed out: repository too large".into(),
+            ));
+        }
+    };
 
     if !clone_result.status.success() {
-        let _ = tokio::fs::remove_dir_all(&tmp_dir).await;
         // FIX 5: Log stderr, return generic message
         let stderr = String::from_utf8_lossy(&clone_result.stderr);
         eprintln!("Clone failed for {}: {}", repo_url, stderr);
@@ -116,25 +594,40 @@ async fn scan_handler(
         ));
     }
 
+    if let Err(size) = check_clone_size(&tmp_dir, limits).await {
+        return Err(clone_too_large(state, &repo_url, size));
+    }
+
     // Run vibereport
-    let analyze_result = tokio::process::Command::new(&state.vibereport_bin)
-        .args([&tmp_dir, "--json", "--since", &since, "--no-share"])
-        .output()
-        .await
-        .map_err(|e| {
-            // FIX 7: Use tokio::fs in async context (spawn blocking cleanup)
-            let tmp = tmp_dir.clone();
-            tokio::spawn(async move {
-                let _ = tokio::fs::remove_dir_all(&tmp).await;
-            });
+    let analyze_fut = tokio::process::Command::new(&state.vibereport.bin)
+        .args([
+            tmp_dir.path(),
+            state.vibereport.json_flag,
+            "--since",
+            &since,
+            "--no-share",
+        ])
+        .kill_on_drop(true)
+        .output();
+    let analyze_result = match tokio::time::timeout(state.user_analyze_timeout, analyze_fut).await {
+        Ok(result) => result.map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Analysis failed: {}", e),
             )
-        })?;
+        })?,
+        Err(_) => {
+            ScanCounters::inc(&state.metrics.user.analysis_timeouts);
+            tracing::warn!(
+                "Analysis timed out for {} ({}s)",
+                repo_url,
+                state.user_analyze_timeout.as_secs()
+            );
+            return Err((StatusCode::GATEWAY_TIMEOUT, "Analysis timed out".into()));
+        }
+    };
 
-    // Cleanup
-    let _ = tokio::fs::remove_dir_all(&tmp_dir).await;
+    drop(tmp_dir);
 
     if !analyze_result.status.success() {
         // FIX 5: Log stderr, return generic message
@@ -150,10 +643,35 @@ async fn scan_handler(
             format!("Parse error: {}", e),
         )
     })?;
+    if !is_supported_report(&data) {
+        eprintln!(
+            "Unsupported report schema_version {} for {}",
+            data["schema_version"], repo_url
+        );
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Analysis failed: incompatible vibereport version".into(),
+        ));
+    }
 
     Ok(Json(data))
 }
 
+/// Count and log a user clone over `SCAN_MAX_CLONE_MB`.
+fn clone_too_large(state: &AppState, repo_url: &str, size: u64) -> (StatusCode, String) {
+    ScanCounters::inc(&state.metrics.user.disk_rejections);
+    tracing::warn!(
+        "Clone of {} reached {} MB, over the {} MB cap",
+        repo_url,
+        megabytes(size),
+        megabytes(state.disk.limits.max_clone)
+    );
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "Repository too large to scan".into(),
+    )
+}
+
 // ── Index scan types ──
 
 // FIX 2: Removed api_url from IndexScanRequest
@@ -195,13 +713,7 @@ async fn index_scan_handler(
     headers: axum::http::HeaderMap,
     Json(req): Json<IndexScanRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    // Auth check (FIX 4: constant-time comparison)
-    let auth = headers
-        .get("authorization")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
-    let expected = format!("Bearer {}", state.auth_token);
-    if auth.as_bytes().ct_eq(expected.as_bytes()).unwrap_u8() != 1 {
+    if !bearer_authorized(&headers, &state.auth_token) {
         return Err((StatusCode::UNAUTHORIZED, "Invalid token".into()));
     }
 
@@ -276,26 +788,72 @@ async fn index_scan_handler(
     }
 
     let repo_count = repos.len();
-    tracing::info!("Index scan starting: {} repos for {}", repo_count, quarter);
+    let since = history_since(
+        state
+            .index_since
+            .clone()
+            .unwrap_or_else(current_quarter_start),
+        &scan_dates,
+    );
+    tracing::info!(
+        "Index scan starting: {} repos for {} (history since {})",
+        repo_count,
+        quarter,
+        since
+    );
 
     // Fire-and-forget: spawn background task, return immediately
     // (Cloudflare Tunnel has ~100s timeout, scan takes ~30min)
-    let auth_token = state.auth_token.clone();
-    let vibereport_bin = state.vibereport_bin.clone();
     let state_clone = Arc::clone(&state);
     let scan_dates_for_response = scan_dates.clone();
+    let since_for_response = since.clone();
 
     let is_backfill = scan_dates.len() > 1;
 
+    let scan_id = Uuid::new_v4().to_string();
+    let (progress, _) = watch::channel(IndexScanProgress {
+        total: repo_count,
+        completed: 0,
+        failed: 0,
+        failed_repos: Vec::new(),
+        done: false,
+        error: None,
+        finished_at: None,
+    });
+    let progress = Arc::new(progress);
+    {
+        let mut scans = state.index_scans.lock().unwrap_or_else(|e| e.into_inner());
+        scans.retain(|_, p| {
+            p.borrow()
+                .finished_at
+                .is_none_or(|at| at.elapsed() < FINISHED_SCAN_TTL)
+        });
+        scans.insert(scan_id.clone(), Arc::clone(&progress));
+    }
+
     tokio::spawn(async move {
+        let finisher = ScanFinisher::new(Arc::clone(&progress));
         // Scan all repos in a single pass (no retry — shallow-since failures are deterministic).
         let scanned: Vec<(String, Option<serde_json::Value>)> = stream::iter(repos)
             .map(|slug| {
                 let sem = &state_clone.index_semaphore;
-                let bin = vibereport_bin.clone();
+                let metrics = &state_clone.metrics.index;
+                let vibereport = &state_clone.vibereport;
+                let progress = &progress;
+                let since = &since;
+                let disk = &state_clone.disk;
                 async move {
                     let _permit = sem.acquire().await.ok()?;
-                    let result = scan_single_repo_raw(&slug, &bin, 120, 60).await;
+                    let result =
+                        scan_single_repo_raw(&slug, vibereport, since, 120, 60, disk, metrics)
+                            .await;
+                    progress.send_modify(|p| {
+                        p.completed += 1;
+                        if result.is_none() {
+                            p.failed += 1;
+                            p.failed_repos.push(slug.clone());
+                        }
+                    });
                     Some((slug, result))
                 }
             })
@@ -305,24 +863,23 @@ async fn index_scan_handler(
             .await;
 
         let mut raw_results: Vec<(String, serde_json::Value)> = Vec::new();
-        let mut failed = 0u64;
+        let mut failed_slugs: Vec<String> = Vec::new();
         for (slug, result) in scanned {
             match result {
                 Some(data) => raw_results.push((slug, data)),
-                None => failed += 1,
+                None => failed_slugs.push(slug),
             }
         }
+        failed_slugs.sort();
 
         tracing::info!(
             "Index scan complete: {}/{} repos scanned ({} failed), posting results for {} date(s)",
             raw_results.len(),
             repo_count,
-            failed,
+            failed_slugs.len(),
             scan_dates.len()
         );
 
-        let client = reqwest::Client::new();
-
         if is_backfill {
             // Backfill mode: use daily_commits to compute cumulative per-date results.
             // For each scan_date, each repo's result = sum of daily_commits entries <= that date.
@@ -364,7 +921,7 @@ async fn index_scan_handler(
                     .filter(|r| r.total_commits > 0)
                     .collect();
 
-                post_results(&client, &api_url, &auth_token, scan_date, &results).await;
+                persist_results(&state_clone, scan_date, &results, &failed_slugs).await;
             }
         } else {
             // Normal mode: single date, use totals directly
@@ -378,62 +935,179 @@ async fn index_scan_handler(
                 .collect();
 
             let scan_date = &scan_dates[0];
-            post_results(&client, &api_url, &auth_token, scan_date, &results).await;
+            persist_results(&state_clone, scan_date, &results, &failed_slugs).await;
         }
+        state_clone.pending_results.notify_one();
+        finisher.finish();
     });
 
     Ok(Json(serde_json::json!({
         "status": "started",
+        "scan_id": scan_id,
         "repos": repo_count,
         "quarter": quarter,
         "scan_dates": scan_dates_for_response,
+        "since": since_for_response,
     })))
 }
 
+// ── Index scan progress (SSE) ──
+
+/// Streams a `progress` event on every finished repo and a final `done`
+/// event (both carrying the full `IndexScanProgress`), then closes.
+async fn scan_status_handler(
+    State(state): State<Arc<AppState>>,
+    extract::Path(id): extract::Path<String>,
+    headers: axum::http::HeaderMap,
+) -> Result<
+    Sse<impl futures::Stream<Item = Result<Event, std::convert::Infallible>>>,
+    (StatusCode, String),
+> {
+    if !bearer_authorized(&headers, &state.auth_token) {
+        return Err((StatusCode::UNAUTHORIZED, "Invalid token".into()));
+    }
+
+    let rx = state
+        .index_scans
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&id)
+        .map(|progress| progress.subscribe())
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Unknown scan id".into()))?;
+
+    // First event is the current snapshot, then one per change until done
+    let events = stream::unfold((rx, true, false), |(mut rx, first, finished)| async move {
+        if finished || (!first && rx.changed().await.is_err()) {
+            return None;
+        }
+        let progress = rx.borrow_and_update().clone();
+        let name = if progress.done { "done" } else { "progress" };
+        let event = Event::default()
+            .event(name)
+            .json_data(&progress)
+            .unwrap_or_else(|_| Event::default().event(name));
+        Some((Ok(event), (rx, false, progress.done)))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
 // ── Single repo scanner for index (returns raw JSON from vibereport) ──
 
+/// Clone and analyze `slug` with history from `since` (YYYY-MM-DD) on.
 async fn scan_single_repo_raw(
     slug: &str,
-    vibereport_bin: &str,
+    vibereport: &Vibereport,
+    since: &str,
+    clone_timeout_secs: u64,
+    analyze_timeout_secs: u64,
+    disk: &DiskBudget,
+    metrics: &ScanCounters,
+) -> Option<serde_json::Value> {
+    ScanCounters::inc(&metrics.scans);
+    let result = clone_and_analyze(
+        slug,
+        vibereport,
+        since,
+        clone_timeout_secs,
+        analyze_timeout_secs,
+        disk,
+        metrics,
+    )
+    .await;
+    if result.is_none() {
+        ScanCounters::inc(&metrics.failed);
+    }
+    result
+}
+
+async fn clone_and_analyze(
+    slug: &str,
+    vibereport: &Vibereport,
+    since: &str,
     clone_timeout_secs: u64,
     analyze_timeout_secs: u64,
+    disk: &DiskBudget,
+    metrics: &ScanCounters,
 ) -> Option<serde_json::Value> {
-    let uuid = Uuid::new_v4().to_string();
-    let tmp_dir = format!("/tmp/vibereport-idx-{}", uuid);
     let repo_url = format!("https://github.com/{}.git", slug);
 
+    let limits = disk.limits;
+    let _reservation = match disk.reserve() {
+        Ok(reservation) => reservation,
+        Err(free) => {
+            ScanCounters::inc(&metrics.disk_rejections);
+            tracing::warn!(
+                "Skipping {}: only {} MB free on /tmp",
+                slug,
+                megabytes(free)
+            );
+            return None;
+        }
+    };
+
+    let tmp_dir = ScanDir::new("vibereport-idx");
+
     let clone_fut = tokio::process::Command::new("git")
         .args([
             "clone",
             "--bare",
-            "--shallow-since=2026-01-01",
+            &format!("--shallow-since={}", since),
             &repo_url,
-            &tmp_dir,
+            tmp_dir.path(),
         ])
+        .kill_on_drop(true)
         .output();
 
     let clone = match tokio::time::timeout(
         std::time::Duration::from_secs(clone_timeout_secs),
-        clone_fut,
+        clone_within_cap(clone_fut, &tmp_dir, limits),
     )
     .await
     {
-        Ok(result) => result.ok()?,
+        Ok(Ok(result)) => result.ok()?,
+        Ok(Err(size)) => {
+            ScanCounters::inc(&metrics.disk_rejections);
+            tracing::warn!(
+                "Skipping {}: clone reached {} MB, over the {} MB cap",
+                slug,
+                megabytes(size),
+                megabytes(limits.max_clone)
+            );
+            return None;
+        }
         Err(_) => {
-            let _ = tokio::fs::remove_dir_all(&tmp_dir).await;
+            ScanCounters::inc(&metrics.clone_timeouts);
             tracing::warn!("Clone timed out for {} ({}s)", slug, clone_timeout_secs);
             return None;
         }
     };
 
     if !clone.status.success() {
-        let _ = tokio::fs::remove_dir_all(&tmp_dir).await;
         tracing::warn!("Clone failed for {}", slug);
         return None;
     }
 
-    let analyze_fut = tokio::process::Command::new(vibereport_bin)
-        .args([&tmp_dir, "--json", "--since", "2026-01-01", "--no-share"])
+    if let Err(size) = check_clone_size(&tmp_dir, limits).await {
+        ScanCounters::inc(&metrics.disk_rejections);
+        tracing::warn!(
+            "Skipping {}: clone is {} MB, over the {} MB cap",
+            slug,
+            megabytes(size),
+            megabytes(limits.max_clone)
+        );
+        return None;
+    }
+
+    let analyze_fut = tokio::process::Command::new(&vibereport.bin)
+        .args([
+            tmp_dir.path(),
+            vibereport.json_flag,
+            "--since",
+            since,
+            "--no-share",
+        ])
+        .kill_on_drop(true)
         .output();
 
     let analyze = match tokio::time::timeout(
@@ -444,7 +1118,7 @@ async fn scan_single_repo_raw(
     {
         Ok(result) => result.ok()?,
         Err(_) => {
-            let _ = tokio::fs::remove_dir_all(&tmp_dir).await;
+            ScanCounters::inc(&metrics.analysis_timeouts);
             tracing::warn!(
                 "Analysis timed out for {} ({}s)",
                 slug,
@@ -454,7 +1128,7 @@ async fn scan_single_repo_raw(
         }
     };
 
-    let _ = tokio::fs::remove_dir_all(&tmp_dir).await;
+    drop(tmp_dir);
 
     if !analyze.status.success() {
         tracing::warn!("Analysis failed for {}", slug);
@@ -462,27 +1136,68 @@ async fn scan_single_repo_raw(
     }
 
     let stdout = String::from_utf8_lossy(&analyze.stdout);
-    serde_json::from_str(&stdout).ok()
+    let data: serde_json::Value = serde_json::from_str(&stdout).ok()?;
+    if !is_supported_report(&data) {
+        tracing::warn!(
+            "Skipping {}: unsupported report schema_version {}",
+            slug,
+            data["schema_version"]
+        );
+        return None;
+    }
+    Some(data)
 }
 
 // ── Post results helper ──
 
-async fn post_results(
-    client: &reqwest::Client,
-    api_url: &str,
-    auth_token: &str,
+/// Write one date's results to `results_dir` for `resend_pending_results` to
+/// post, or post them right away when they can't be written (a full disk)
+/// rather than drop them. `failed` lists the panel repos that couldn't be
+/// scanned at all, so the dashboard can show them instead of them vanishing.
+async fn persist_results(
+    state: &AppState,
     scan_date: &str,
     results: &[RepoScanResult],
+    failed: &[String],
 ) {
     let post_body = serde_json::json!({
         "scan_date": scan_date,
         "results": results,
+        "failed": failed,
     });
+    if let Err(e) = persist_batch(&state.results_dir, scan_date, &post_body).await {
+        tracing::error!(
+            "Failed to persist index results for {}: {}; posting them directly",
+            scan_date,
+            e
+        );
+        let client = reqwest::Client::new();
+        if !post_batch(
+            &client,
+            &state.api_url,
+            &state.auth_token,
+            scan_date,
+            &post_body,
+        )
+        .await
+        {
+            tracing::error!("Index results for {} were lost", scan_date);
+        }
+    }
+}
 
+/// POST one `{scan_date, results, failed}` batch; true when the API accepted it.
+async fn post_batch(
+    client: &reqwest::Client,
+    api_url: &str,
+    auth_token: &str,
+    scan_date: &str,
+    post_body: &serde_json::Value,
+) -> bool {
     match client
         .post(format!("{}/api/index-results", api_url))
         .header("Authorization", format!("Bearer {}", auth_token))
-        .json(&post_body)
+        .json(post_body)
         .send()
         .await
     {
@@ -495,13 +1210,114 @@ async fn post_results(
                 status,
                 body
             );
+            status.is_success()
         }
         Err(e) => {
             tracing::error!("Failed to post index results for {}: {}", scan_date, e);
+            false
         }
     }
 }
 
+// ── Pending index results (survive API outages and restarts) ──
+
+/// Write a batch to `{results_dir}/{scan_date}-{uuid}.json` (via a temp file
+/// and rename, so a crash never leaves a half-written batch).
+async fn persist_batch(
+    results_dir: &Path,
+    scan_date: &str,
+    post_body: &serde_json::Value,
+) -> std::io::Result<PathBuf> {
+    tokio::fs::create_dir_all(results_dir).await?;
+    let name = format!("{}-{}", scan_date, Uuid::new_v4());
+    let tmp = results_dir.join(format!("{}.tmp", name));
+    let path = results_dir.join(format!("{}.json", name));
+    tokio::fs::write(&tmp, serde_json::to_vec(post_body)?).await?;
+    tokio::fs::rename(&tmp, &path).await?;
+    Ok(path)
+}
+
+/// Post every batch in `results_dir`, deleting each one the API accepts, then
+/// again every `RESEND_INTERVAL` or as soon as a scan persists new ones. Being
+/// the only poster, it never sends a batch twice at once.
+async fn resend_pending_results(state: Arc<AppState>) {
+    let client = reqwest::Client::new();
+    loop {
+        for path in pending_batches(&state.results_dir).await {
+            let body: serde_json::Value = match tokio::fs::read(&path)
+                .await
+                .ok()
+                .and_then(|b| serde_json::from_slice(&b).ok())
+            {
+                Some(body) => body,
+                None => {
+                    tracing::warn!("Skipping unreadable pending results {}", path.display());
+                    continue;
+                }
+            };
+            let scan_date = body["scan_date"].as_str().unwrap_or("unknown").to_string();
+            tracing::info!("Posting pending index results {}", path.display());
+            if post_batch(
+                &client,
+                &state.api_url,
+                &state.auth_token,
+                &scan_date,
+                &body,
+            )
+            .await
+            {
+                let _ = tokio::fs::remove_file(&path).await;
+            }
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(RESEND_INTERVAL) => {}
+            _ = state.pending_results.notified() => {}
+        }
+    }
+}
+
+/// `RESULTS_DIR` (relative to the directory the worker started in), else
+/// `$XDG_DATA_HOME/vps-worker/index-results`, falling back to
+/// `~/.local/share/vps-worker/index-results`. Always absolute.
+fn results_dir() -> Result<PathBuf, String> {
+    let non_empty = |key: &str| std::env::var_os(key).filter(|v| !v.is_empty());
+    let dir = match non_empty("RESULTS_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => non_empty("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+            .map(|dir| dir.join("vps-worker").join("index-results"))
+            .ok_or("RESULTS_DIR must be set when neither XDG_DATA_HOME nor HOME is")?,
+    };
+    std::path::absolute(&dir).map_err(|e| format!("RESULTS_DIR {}: {}", dir.display(), e))
+}
+
+/// Create `dir` and check a batch can be written there, so a bad
+/// `RESULTS_DIR` stops the worker at startup instead of every scan.
+fn prepare_results_dir(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(format!(".probe-{}.tmp", Uuid::new_v4()));
+    std::fs::create_dir_all(dir)
+        .and_then(|()| std::fs::write(&probe, b"{}"))
+        .and_then(|()| std::fs::remove_file(&probe))
+        .map_err(|e| format!("RESULTS_DIR {} is not writable: {}", dir.display(), e))
+}
+
+/// The finished batches in `results_dir`, oldest scan date first.
+async fn pending_batches(results_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(results_dir).await else {
+        return paths;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    paths
+}
+
 // ── Date range helper ──
 
 fn generate_date_range(from: &str, to: &str) -> Option<Vec<String>> {
@@ -519,6 +1335,16 @@ fn generate_date_range(from: &str, to: &str) -> Option<Vec<String>> {
     Some(dates)
 }
 
+/// Where an index scan's clone history starts: `start` (`INDEX_SINCE` or the
+/// quarter start), pulled back to the earliest scan date so a backfill reaching
+/// before it still sees those days' commits.
+fn history_since(start: String, scan_dates: &[String]) -> String {
+    match scan_dates.iter().min() {
+        Some(earliest) if *earliest < start => earliest.clone(),
+        _ => start,
+    }
+}
+
 // ── Quarter helper ──
 
 fn get_current_quarter() -> String {
@@ -527,8 +1353,123 @@ fn get_current_quarter() -> String {
     format!("{}-Q{}", now.year(), q)
 }
 
+/// First day of the quarter `get_current_quarter` names (YYYY-MM-DD): where
+/// index scans start counting unless `INDEX_SINCE` pins a date.
+fn current_quarter_start() -> String {
+    let now = chrono::Utc::now();
+    let first_month = (now.month() - 1) / 3 * 3 + 1;
+    format!("{}-{:02}-01", now.year(), first_month)
+}
+
+// ── Health check (no auth: probed by uptime monitors and the tunnel) ──
+
+async fn health_handler(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+        // 0 means new scans of that kind wait for a slot
+        "available_permits": {
+            "user": state.user_semaphore.available_permits(),
+            "index": state.index_semaphore.available_permits(),
+        },
+    }))
+}
+
+// ── Prometheus metrics (only routed when METRICS_ENABLED is set) ──
+
+type CounterField = fn(&ScanCounters) -> &AtomicU64;
+
+async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl axum::response::IntoResponse {
+    let kinds = [
+        (
+            "user",
+            &state.metrics.user,
+            &state.user_semaphore,
+            USER_SCAN_SLOTS,
+        ),
+        (
+            "index",
+            &state.metrics.index,
+            &state.index_semaphore,
+            INDEX_SCAN_SLOTS,
+        ),
+    ];
+    let counters: [(&str, &str, CounterField); 5] = [
+        ("scans_total", "Scans started", |c| &c.scans),
+        ("scans_failed_total", "Scans that returned no report", |c| {
+            &c.failed
+        }),
+        (
+            "clone_timeouts_total",
+            "Clones killed by the timeout",
+            |c| &c.clone_timeouts,
+        ),
+        (
+            "analysis_timeouts_total",
+            "Analyses killed by the timeout",
+            |c| &c.analysis_timeouts,
+        ),
+        (
+            "disk_rejections_total",
+            "Scans refused for low disk space or an oversized clone",
+            |c| &c.disk_rejections,
+        ),
+    ];
+
+    let mut body = String::new();
+    for (name, help, get) in counters {
+        body.push_str(&format!(
+            "# HELP vibereport_worker_{name} {help}.\n# TYPE vibereport_worker_{name} counter\n"
+        ));
+        for (kind, c, _, _) in &kinds {
+            let value = get(c).load(Ordering::Relaxed);
+            body.push_str(&format!(
+                "vibereport_worker_{name}{{kind=\"{kind}\"}} {value}\n"
+            ));
+        }
+    }
+    body.push_str("# HELP vibereport_worker_scans_in_flight Scans holding a semaphore permit.\n");
+    body.push_str("# TYPE vibereport_worker_scans_in_flight gauge\n");
+    for (kind, _, semaphore, slots) in &kinds {
+        let in_flight = slots.saturating_sub(semaphore.available_permits());
+        body.push_str(&format!(
+            "vibereport_worker_scans_in_flight{{kind=\"{kind}\"}} {in_flight}\n"
+        ));
+    }
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body)
+}
+
 // ── Main ──
 
+/// A whole-number setting from the environment, `default` when unset; the
+/// error names the variable and its `unit`.
+fn env_number<T: std::str::FromStr>(key: &str, default: T, unit: &str) -> Result<T, String> {
+    match std::env::var(key) {
+        Ok(v) => v
+            .trim()
+            .parse()
+            .map_err(|_| format!("{} must be a whole number of {}, got {:?}", key, unit, v)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// A `YYYY-MM-DD` date from the environment, `None` when unset or empty.
+fn env_date(key: &str) -> Result<Option<String>, String> {
+    match std::env::var(key) {
+        Ok(v) if !v.trim().is_empty() => chrono::NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d")
+            .map(|date| Some(date.format("%Y-%m-%d").to_string()))
+            .map_err(|_| format!("{} must be a YYYY-MM-DD date, got {:?}", key, v)),
+        _ => Ok(None),
+    }
+}
+
+/// Report a bad setting and exit before the worker starts serving.
+fn startup_error(message: &str) -> ! {
+    tracing::error!("{}", message);
+    std::process::exit(1)
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt::init();
@@ -539,23 +1480,301 @@ async fn main() {
     // FIX 2: Read API_URL from environment
     let api_url = std::env::var("API_URL")
         .unwrap_or_else(|_| "https://vibereport-api.clement-serizay.workers.dev".into());
+    let results_dir = results_dir()
+        .and_then(|dir| prepare_results_dir(&dir).map(|()| dir))
+        .unwrap_or_else(|e| startup_error(&e));
+    let scan_rate_limit = env_number(
+        "SCAN_RATE_LIMIT",
+        DEFAULT_SCAN_RATE_LIMIT,
+        "scans per minute",
+    )
+    .unwrap_or_else(|e| startup_error(&e));
+    let timeout_secs = |key: &str, default: u64| {
+        env_number(key, default, "seconds")
+            .map(Duration::from_secs)
+            .unwrap_or_else(|e| startup_error(&e))
+    };
+    let user_clone_timeout =
+        timeout_secs("SCAN_CLONE_TIMEOUT_SECS", DEFAULT_USER_CLONE_TIMEOUT_SECS);
+    let user_analyze_timeout = timeout_secs(
+        "SCAN_ANALYZE_TIMEOUT_SECS",
+        DEFAULT_USER_ANALYZE_TIMEOUT_SECS,
+    );
+    let size_mb = |key: &str, default: u64| {
+        env_number(key, default, "megabytes")
+            .and_then(|mb| {
+                mb.checked_mul(1024 * 1024)
+                    .ok_or_else(|| format!("{} is too large", key))
+            })
+            .unwrap_or_else(|e| startup_error(&e))
+    };
+    let disk_limits = DiskLimits {
+        min_free: size_mb("SCAN_MIN_FREE_MB", DEFAULT_MIN_FREE_MB),
+        max_clone: size_mb("SCAN_MAX_CLONE_MB", DEFAULT_MAX_CLONE_MB),
+    };
+    let index_since = env_date("INDEX_SINCE").unwrap_or_else(|e| startup_error(&e));
 
     let state = Arc::new(AppState {
-        user_semaphore: Semaphore::new(2),
-        index_semaphore: Semaphore::new(10),
+        user_semaphore: Semaphore::new(USER_SCAN_SLOTS),
+        index_semaphore: Semaphore::new(INDEX_SCAN_SLOTS),
         auth_token,
-        vibereport_bin,
+        vibereport: Vibereport::detect(vibereport_bin).await,
         api_url,
+        metrics: Metrics::default(),
+        scan_rate_limiter: RateLimiter::new(scan_rate_limit),
+        index_scans: Mutex::new(HashMap::new()),
+        results_dir,
+        pending_results: Notify::new(),
+        user_clone_timeout,
+        user_analyze_timeout,
+        index_since,
+        disk: DiskBudget::new(disk_limits),
     });
 
-    let app = Router::new()
-        .route("/scan", post(scan_handler))
+    // The only poster of index results: batches from a previous run first,
+    // then each scan's as it persists them
+    tokio::spawn(resend_pending_results(Arc::clone(&state)));
+
+    let mut app = Router::new()
+        .route("/health", get(health_handler))
+        .route("/scan", get(scan_get_handler).post(scan_handler))
         .route("/index-scan", post(index_scan_handler))
-        .with_state(state);
+        .route("/scan-status/{id}", get(scan_status_handler));
+    // Unauthenticated, so opt-in: scrape it through the tunnel or locally
+    if std::env::var_os("METRICS_ENABLED").is_some_and(|v| !v.is_empty() && v != "0") {
+        app = app.route("/metrics", get(metrics_handler));
+    }
+    let app = app.with_state(state);
 
     // FIX 6: Bind to 127.0.0.1 (cloudflared runs on the same machine)
     let addr = format!("127.0.0.1:{}", port);
     tracing::info!("VPS worker listening on {}", addr);
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
+}
+
+// ━━ Tests ━━
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A clone dir with something in it, like after `git clone`.
+    fn populated_scan_dir() -> (ScanDir, PathBuf) {
+        let dir = ScanDir::new("vibereport-test");
+        std::fs::create_dir_all(dir.0.join("src")).unwrap();
+        std::fs::write(dir.0.join("src/main.rs"), "fn main() {}").unwrap();
+        let path = dir.0.clone();
+        (dir, path)
+    }
+
+    #[tokio::test]
+    async fn scan_dir_is_removed_on_an_early_error_return() {
+        async fn failing_scan(path: &mut Option<PathBuf>) -> Result<(), &'static str> {
+            let (_dir, p) = populated_scan_dir();
+            *path = Some(p);
+            let _commits: u32 = "not a number".parse().map_err(|_| "Parse error")?;
+            Ok(())
+        }
+
+        let mut path = None;
+        assert!(failing_scan(&mut path).await.is_err());
+        let path = path.unwrap();
+
+        // Removal runs on the blocking pool
+        for _ in 0..100 {
+            if !path.exists() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("{} was left behind", path.display());
+    }
+
+    #[tokio::test]
+    async fn pending_batches_are_posted_oldest_date_first() {
+        let dir = ScanDir::new("vibereport-results-test");
+        let results = [RepoScanResult {
+            repo_slug: "a/b".into(),
+            total_commits: 3,
+            ai_commits: 1,
+        }];
+        for date in ["2026-10-02", "2026-09-30", "2026-10-01"] {
+            let body = serde_json::json!({ "scan_date": date, "results": results });
+            persist_batch(&dir.0, date, &body).await.unwrap();
+        }
+        std::fs::write(dir.0.join("2026-09-01-partial.tmp"), "{").unwrap();
+
+        let names: Vec<String> = pending_batches(&dir.0)
+            .await
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy()[..10].to_string())
+            .collect();
+        assert_eq!(names, ["2026-09-30", "2026-10-01", "2026-10-02"]);
+    }
+
+    #[test]
+    fn unwritable_results_dir_is_a_startup_error() {
+        let dir = ScanDir::new("vibereport-results-dir-test");
+        assert!(prepare_results_dir(&dir.0.join("nested/results")).is_ok());
+        std::fs::write(dir.0.join("file"), "").unwrap();
+        let err = prepare_results_dir(&dir.0.join("file")).unwrap_err();
+        assert!(err.contains("not writable"), "{}", err);
+    }
+
+    #[test]
+    fn concurrent_clones_share_the_disk_budget() {
+        let budget = DiskBudget::new(DiskLimits {
+            min_free: 120,
+            max_clone: 50,
+        });
+        let first = budget.reserve_from(200).unwrap();
+        let second = budget.reserve_from(200).unwrap();
+        // 200 free, 100 promised to the two clones in flight
+        assert_eq!(budget.reserve_from(200).err(), Some(100));
+        drop(first);
+        drop(second);
+        assert!(budget.reserve_from(200).is_ok());
+    }
+
+    #[tokio::test]
+    async fn clone_over_the_cap_is_stopped_while_running() {
+        let (dir, _) = populated_scan_dir();
+        std::fs::write(dir.0.join("pack"), vec![0u8; 4096]).unwrap();
+        let limits = DiskLimits {
+            min_free: 0,
+            max_clone: 1024,
+        };
+        let endless_clone = std::future::pending::<std::io::Result<std::process::Output>>();
+        let result = tokio::time::timeout(
+            Duration::from_secs(10),
+            clone_within_cap(endless_clone, &dir, limits),
+        )
+        .await
+        .expect("the watchdog should stop the clone");
+        assert!(matches!(result, Err(size) if size > 1024));
+    }
+
+    #[test]
+    fn bad_numeric_setting_is_an_error_not_a_panic() {
+        std::env::set_var("VIBEREPORT_TEST_SIZE_MB", "lots");
+        let err = env_number("VIBEREPORT_TEST_SIZE_MB", 1, "megabytes").unwrap_err();
+        assert!(err.contains("VIBEREPORT_TEST_SIZE_MB must be a whole number of megabytes"));
+        assert_eq!(
+            env_number("VIBEREPORT_TEST_UNSET_MB", 7, "megabytes"),
+            Ok(7)
+        );
+    }
+
+    #[test]
+    fn index_since_must_be_a_real_date() {
+        std::env::set_var("VIBEREPORT_TEST_SINCE", "2026-02-30");
+        let err = env_date("VIBEREPORT_TEST_SINCE").unwrap_err();
+        assert!(err.contains("VIBEREPORT_TEST_SINCE must be a YYYY-MM-DD date"));
+        std::env::set_var("VIBEREPORT_TEST_SINCE", "2026-02-28");
+        assert_eq!(
+            env_date("VIBEREPORT_TEST_SINCE"),
+            Ok(Some("2026-02-28".into()))
+        );
+        assert_eq!(env_date("VIBEREPORT_TEST_UNSET_SINCE"), Ok(None));
+    }
+
+    #[tokio::test]
+    async fn panicking_scan_task_still_ends_the_stream() {
+        let (progress, mut rx) = watch::channel(IndexScanProgress {
+            total: 1,
+            completed: 0,
+            failed: 0,
+            failed_repos: Vec::new(),
+            done: false,
+            error: None,
+            finished_at: None,
+        });
+        let progress = Arc::new(progress);
+        let task = tokio::spawn(async move {
+            let _finisher = ScanFinisher::new(progress);
+            panic!("scan blew up");
+        });
+        assert!(task.await.unwrap_err().is_panic());
+
+        let last = rx.borrow_and_update().clone();
+        assert!(last.done);
+        assert!(last.error.is_some());
+    }
+
+    #[test]
+    fn rate_limiter_empties_each_bucket_separately() {
+        let limiter = RateLimiter::new(2);
+        let a: IpAddr = "203.0.113.1".parse().unwrap();
+        let b: IpAddr = "203.0.113.2".parse().unwrap();
+        assert!(limiter.check(a));
+        assert!(limiter.check(a));
+        assert!(!limiter.check(a));
+        assert!(limiter.check(b));
+    }
+
+    #[test]
+    fn rate_limit_of_zero_never_limits() {
+        let limiter = RateLimiter::new(0);
+        let ip: IpAddr = "203.0.113.1".parse().unwrap();
+        assert!((0..100).all(|_| limiter.check(ip)));
+    }
+
+    #[test]
+    fn client_ip_trusts_cloudflare_not_forwarded_for() {
+        let tunnel: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("x-forwarded-for", "198.51.100.7".parse().unwrap());
+        assert_eq!(client_ip(&headers, tunnel), None);
+
+        headers.insert("cf-connecting-ip", "203.0.113.9".parse().unwrap());
+        assert_eq!(client_ip(&headers, tunnel), "203.0.113.9".parse().ok());
+    }
+
+    #[test]
+    fn client_ip_falls_back_to_a_remote_peer() {
+        let peer: SocketAddr = "203.0.113.4:443".parse().unwrap();
+        let headers = axum::http::HeaderMap::new();
+        assert_eq!(client_ip(&headers, peer), Some(peer.ip()));
+    }
+
+    #[test]
+    fn older_vibereport_without_json_compact_gets_json() {
+        assert_eq!(
+            json_flag_for("      --json          Print the report as JSON\n      --json-compact  Print minified JSON\n"),
+            "--json-compact"
+        );
+        assert_eq!(
+            json_flag_for("      --json  Print the report as JSON\n"),
+            "--json"
+        );
+    }
+
+    #[test]
+    fn backfill_before_the_quarter_starts_history_at_its_first_date() {
+        let dates = generate_date_range("2026-09-28", "2026-10-02").unwrap();
+        assert_eq!(history_since("2026-10-01".into(), &dates), "2026-09-28");
+    }
+
+    #[test]
+    fn scans_inside_the_quarter_keep_the_quarter_start() {
+        let dates = vec!["2026-10-15".to_string()];
+        assert_eq!(history_since("2026-10-01".into(), &dates), "2026-10-01");
+    }
+
+    #[test]
+    fn scan_dir_is_removed_on_panic_outside_a_runtime() {
+        let (dir, path) = populated_scan_dir();
+        let result = std::panic::catch_unwind(move || {
+            let _dir = dir;
+            panic!("analysis blew up");
+        });
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
 }
@@ -0,0 +1,50 @@
+-- Non-GitHub repo fingerprints moved from `root_commit:url` (complete clones)
+-- and the raw URL (shallow clones) to the URL minus trailing `/` and `.git`,
+-- so both kinds of clone share one key. GitHub (`github.com/...`), anonymous
+-- (`anon:...`) and remote-less (`root_commit:`) fingerprints are unchanged.
+
+CREATE TABLE fingerprint_moves (old_fp TEXT PRIMARY KEY, new_fp TEXT NOT NULL);
+
+INSERT INTO fingerprint_moves (old_fp, new_fp)
+SELECT fp,
+       CASE WHEN substr(url, -4) = '.git' THEN substr(url, 1, length(url) - 4) ELSE url END
+FROM (
+  SELECT fp,
+         rtrim(CASE WHEN instr(fp, ':') = 41 THEN substr(fp, 42) ELSE fp END, '/') AS url
+  FROM (
+    SELECT repo_fingerprint AS fp FROM reports
+    UNION
+    SELECT repo_fingerprint FROM scan_history
+  )
+  WHERE fp IS NOT NULL
+    AND fp NOT LIKE 'github.com/%'
+    AND fp NOT LIKE 'anon:%'
+    AND NOT (instr(fp, ':') = 41 AND length(fp) = 41)
+);
+
+DELETE FROM fingerprint_moves WHERE old_fp = new_fp;
+
+-- A shallow and a full clone of one repo were two reports; keep the most
+-- recently updated one under the new key
+DELETE FROM reports WHERE id IN (
+  SELECT a.id
+  FROM (
+    SELECT r.id, r.updated_at, COALESCE(m.new_fp, r.repo_fingerprint) AS fp
+    FROM reports r LEFT JOIN fingerprint_moves m ON m.old_fp = r.repo_fingerprint
+  ) a
+  JOIN (
+    SELECT r.id, r.updated_at, COALESCE(m.new_fp, r.repo_fingerprint) AS fp
+    FROM reports r LEFT JOIN fingerprint_moves m ON m.old_fp = r.repo_fingerprint
+  ) b ON a.fp = b.fp
+     AND (b.updated_at > a.updated_at OR (b.updated_at = a.updated_at AND b.id > a.id))
+);
+
+UPDATE reports
+SET repo_fingerprint = (SELECT new_fp FROM fingerprint_moves WHERE old_fp = reports.repo_fingerprint)
+WHERE repo_fingerprint IN (SELECT old_fp FROM fingerprint_moves);
+
+UPDATE scan_history
+SET repo_fingerprint = (SELECT new_fp FROM fingerprint_moves WHERE old_fp = scan_history.repo_fingerprint)
+WHERE repo_fingerprint IN (SELECT old_fp FROM fingerprint_moves);
+
+DROP TABLE fingerprint_moves;
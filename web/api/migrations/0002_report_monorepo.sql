@@ -0,0 +1,3 @@
+-- Monorepo flag from the share payload / VPS report, so the report page can
+-- apply the CLI's monorepo allowance to the deps-bloat points.
+ALTER TABLE reports ADD COLUMN monorepo INTEGER DEFAULT 0;
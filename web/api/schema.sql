@@ -9,6 +9,7 @@ CREATE TABLE IF NOT EXISTS reports (
   score_grade TEXT NOT NULL,
   roast TEXT NOT NULL,
   deps_count INTEGER DEFAULT 0,
+  monorepo INTEGER DEFAULT 0,
   has_tests INTEGER DEFAULT 0,
   total_commits INTEGER DEFAULT 0,
   ai_commits INTEGER DEFAULT 0,
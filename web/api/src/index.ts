@@ -174,9 +174,30 @@ function detectAiTool(msg: string, authorEmail?: string): string {
   if (msg.includes('co-authored-by: codex') || msg.includes('generated by codex') || msg.includes('codex-cli')) return 'Codex CLI'
   if (msg.includes('co-authored-by: copilot') || msg.includes('github-copilot')) return 'GitHub Copilot'
   if (msg.includes('co-authored-by: gemini') || (msg.includes('noreply@google.com') && msg.includes('gemini'))) return 'Gemini CLI'
+  if (msg.includes('co-authored-by: windsurf') || msg.includes('generated with windsurf') || msg.includes('co-authored-by: codeium')) return 'Windsurf'
+  if ((msg.includes('co-authored-by: cody') && msg.includes('sourcegraph')) || msg.includes('generated with cody') || msg.includes('generated by cody')) return 'Cody'
+  if (msg.includes('co-authored-by: tabnine') || msg.includes('generated with tabnine') || msg.includes('generated by tabnine')) return 'Tabnine'
+  const emails = [...msg.matchAll(/^\s*co-authored-by:[^<\n]*<([^>\n]+)>/gm)].map((m) => m[1].trim())
+  emails.push(email)
+  for (const e of emails) {
+    const tool = toolForEmail(e)
+    if (tool) return tool
+  }
   return 'Human'
 }
 
+// Known AI co-author / author emails (mirrors tool_for_email in ai_detect.rs)
+function toolForEmail(email: string): string | null {
+  if (email === 'noreply@anthropic.com') return 'Claude Code'
+  if (email === 'cursoragent@cursor.com') return 'Cursor'
+  if (email === 'noreply@aider.chat') return 'Aider'
+  if (email === 'copilot@github.com' || email.endsWith('+copilot@users.noreply.github.com')) return 'GitHub Copilot'
+  if (email === 'noreply@windsurf.com' || email === 'noreply@codeium.com') return 'Windsurf'
+  if (email === 'cody@sourcegraph.com') return 'Cody'
+  if (email === 'noreply@tabnine.com') return 'Tabnine'
+  return null
+}
+
 // Server-side grade calculation (mirrors src/score/calculator.rs)
 function gradeFromPoints(points: number): string {
   if (points > 100) return 'S+'
@@ -208,7 +229,7 @@ function pickRoast(points: number, aiRatio: number): string {
 }
 
 // Valid chaos badges allowlist
-const VALID_BADGES = ['env-in-git', 'hardcoded-secrets', 'no-tests', 'dependency-hell', 'no-linting', 'no-ci', 'boomer-ai', 'node-modules-in-git', 'mega-commit', 'no-gitignore', 'no-readme', 'todo-flood', 'single-branch'];
+const VALID_BADGES = ['env-in-git', 'hardcoded-secrets', 'private-keys', 'no-tests', 'dependency-hell', 'no-linting', 'no-ci', 'boomer-ai', 'node-modules-in-git', 'mega-commit', 'no-gitignore', 'no-readme', 'no-license', 'todo-flood', 'single-branch', 'low-effort-commits'];
 
 // ── POST /api/reports — Submit a new report ──
 app.post('/api/reports', async (c) => {
@@ -282,6 +303,7 @@ app.post('/api/reports', async (c) => {
   const repoName = typeof body.repo_name === 'string' ? body.repo_name : null
   const aiTool = typeof body.ai_tool === 'string' ? body.ai_tool : null
   const depsCount = typeof body.deps_count === 'number' ? body.deps_count : 0
+  const monorepo = body.monorepo ? 1 : 0
   const hasTests = body.has_tests ? 1 : 0
   const totalLines = typeof body.total_lines === 'number' ? body.total_lines : 0
   const languages = typeof body.languages === 'string' ? body.languages : '{}'
@@ -293,8 +315,8 @@ app.post('/api/reports', async (c) => {
   if (fingerprint) {
     // Upsert: update existing report if fingerprint matches
     await db.prepare(
-      `INSERT INTO reports (id, repo_fingerprint, github_username, repo_name, ai_ratio, ai_tool, score_points, score_grade, roast, deps_count, has_tests, total_lines, languages, total_commits, ai_commits, vibe_score, chaos_badges, scan_source, updated_at)
-       VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'cli', datetime('now'))
+      `INSERT INTO reports (id, repo_fingerprint, github_username, repo_name, ai_ratio, ai_tool, score_points, score_grade, roast, deps_count, monorepo, has_tests, total_lines, languages, total_commits, ai_commits, vibe_score, chaos_badges, scan_source, updated_at)
+       VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'cli', datetime('now'))
        ON CONFLICT(repo_fingerprint) DO UPDATE SET
          github_username = excluded.github_username,
          repo_name = excluded.repo_name,
@@ -304,6 +326,7 @@ app.post('/api/reports', async (c) => {
          score_grade = excluded.score_grade,
          roast = excluded.roast,
          deps_count = excluded.deps_count,
+         monorepo = excluded.monorepo,
          has_tests = excluded.has_tests,
          total_lines = excluded.total_lines,
          languages = excluded.languages,
@@ -316,7 +339,7 @@ app.post('/api/reports', async (c) => {
     ).bind(
       id, fingerprint, githubUsername, repoName,
       body.ai_ratio, aiTool, body.score_points, scoreGrade, roast,
-      depsCount, hasTests, totalLines, languages,
+      depsCount, monorepo, hasTests, totalLines, languages,
       totalCommits, aiCommits, vibeScore, validatedBadges,
     ).run()
     // Retrieve the actual stored id (upsert keeps original id on conflict)
@@ -327,12 +350,12 @@ app.post('/api/reports', async (c) => {
   } else {
     // No fingerprint: plain insert (for backwards compatibility)
     await db.prepare(
-      `INSERT INTO reports (id, github_username, repo_name, ai_ratio, ai_tool, score_points, score_grade, roast, deps_count, has_tests, total_lines, languages, total_commits, ai_commits, vibe_score, chaos_badges, scan_source)
-       VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'cli')`
+      `INSERT INTO reports (id, github_username, repo_name, ai_ratio, ai_tool, score_points, score_grade, roast, deps_count, monorepo, has_tests, total_lines, languages, total_commits, ai_commits, vibe_score, chaos_badges, scan_source)
+       VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'cli')`
     ).bind(
       id, githubUsername, repoName,
       body.ai_ratio, aiTool, body.score_points, scoreGrade, roast,
-      depsCount, hasTests, totalLines, languages,
+      depsCount, monorepo, hasTests, totalLines, languages,
       totalCommits, aiCommits, vibeScore, validatedBadges,
     ).run()
   }
@@ -468,15 +491,18 @@ app.post('/api/scan', async (c) => {
           if (vibe.node_modules_in_git) chaosBadges.push('node-modules')
           if (vibe.no_gitignore) chaosBadges.push('no-gitignore')
           if (vibe.no_readme) chaosBadges.push('no-readme')
+          if (vibe.no_license) chaosBadges.push('no-license')
           if (vibe.todo_flood) chaosBadges.push('todo-flood')
           if (vibe.single_branch) chaosBadges.push('single-branch')
           if (vibe.mega_commit) chaosBadges.push('mega-commit')
+          if (vibe.low_effort_commits) chaosBadges.push('low-effort-commits')
         }
         // Add badges from other data
         const tests = vpsData.tests
-        if (tests && typeof tests === 'object' && !tests.has_tests) chaosBadges.push('no-tests')
+        if (tests && typeof tests === 'object' && (!tests.has_tests || !tests.test_files)) chaosBadges.push('no-tests')
         const security = vpsData.security
         if (security && typeof security === 'object' && security.env_in_git) chaosBadges.push('env-in-git')
+        if (security && typeof security === 'object' && security.private_keys > 0) chaosBadges.push('private-keys')
 
         // Compute fingerprint — normalized GitHub URL (stable across scan methods)
         const fingerprint = `github.com/${owner}/${repo}`
@@ -485,8 +511,8 @@ app.post('/api/scan', async (c) => {
         const id = generateId()
 
         await db.prepare(
-          `INSERT INTO reports (id, repo_fingerprint, github_username, repo_name, ai_ratio, ai_tool, score_points, score_grade, roast, total_commits, ai_commits, deps_count, has_tests, total_lines, languages, vibe_score, chaos_badges, scan_source, updated_at)
-           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'web_vps', datetime('now'))
+          `INSERT INTO reports (id, repo_fingerprint, github_username, repo_name, ai_ratio, ai_tool, score_points, score_grade, roast, total_commits, ai_commits, deps_count, monorepo, has_tests, total_lines, languages, vibe_score, chaos_badges, scan_source, updated_at)
+           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'web_vps', datetime('now'))
            ON CONFLICT(repo_fingerprint) DO UPDATE SET
              ai_ratio = excluded.ai_ratio,
              ai_tool = excluded.ai_tool,
@@ -496,6 +522,7 @@ app.post('/api/scan', async (c) => {
              total_commits = excluded.total_commits,
              ai_commits = excluded.ai_commits,
              deps_count = excluded.deps_count,
+             monorepo = excluded.monorepo,
              has_tests = excluded.has_tests,
              total_lines = excluded.total_lines,
              languages = excluded.languages,
@@ -507,6 +534,7 @@ app.post('/api/scan', async (c) => {
           id, fingerprint, owner, repo, aiRatio, primaryTool, vibeScore, grade, roast,
           totalCommits, aiCommits,
           typeof vpsData.deps?.total === 'number' ? vpsData.deps.total : 0,
+          vpsData.monorepo ? 1 : 0,
           tests?.has_tests ? 1 : 0,
           typeof vpsData.total_lines === 'number' ? vpsData.total_lines : 0,
           typeof vpsData.languages === 'object' ? JSON.stringify(vpsData.languages) : '{}',
@@ -632,9 +660,9 @@ app.post('/api/scan', async (c) => {
       // (we can't check filesystem from GitHub API, so skip boomer-ai)
     }
     // These badges require filesystem access — only VPS worker can detect them:
-    // env-in-git, hardcoded-secrets, no-tests, no-linting, no-ci-cd,
-    // boomer-ai, node-modules, no-gitignore, no-readme, todo-flood,
-    // single-branch, mega-commit
+    // env-in-git, hardcoded-secrets, private-keys, no-tests, no-linting, no-ci-cd,
+    // boomer-ai, node-modules, no-gitignore, no-readme, no-license, todo-flood,
+    // single-branch, mega-commit, low-effort-commits
 
     return c.json({
       id: reportId,
@@ -721,6 +749,7 @@ app.get('/api/reports/:id', async (c) => {
     total_lines: row.total_lines,
     has_tests: Boolean(row.has_tests),
     deps_count: row.deps_count || 0,
+    monorepo: Boolean(row.monorepo),
     chaos_badges: chaosBadges,
     scan_source: row.scan_source || 'cli',
     languages,
@@ -897,7 +926,8 @@ app.post('/api/index-results', async (c) => {
     return c.json({ error: 'Unauthorized' }, 401)
   }
 
-  let body: { scan_date: string; results: Array<{ repo_slug: string; total_commits: number; ai_commits: number }> }
+  // `failed`: panel repos the VPS couldn't scan (absent in batches from older workers)
+  let body: { scan_date: string; results: Array<{ repo_slug: string; total_commits: number; ai_commits: number }>; failed?: string[] }
   try {
     body = await c.req.json()
   } catch {
@@ -955,7 +985,12 @@ app.post('/api/index-results', async (c) => {
        ai_percent = excluded.ai_percent`
   ).bind(scanDate, quarter, totalRepos, totalCommits, totalAiCommits, Math.round(aiPercent * 100) / 100).run()
 
-  return c.json({ ok: true, snapshot: { scanDate, totalRepos, totalCommits, totalAiCommits, aiPercent: Math.round(aiPercent * 100) / 100 } })
+  const failed = Array.isArray(body.failed) ? body.failed.filter((s) => typeof s === 'string') : []
+  if (failed.length > 0) {
+    console.warn(`Index scan ${scanDate}: ${failed.length} repo(s) failed: ${failed.join(', ')}`)
+  }
+
+  return c.json({ ok: true, failed, snapshot: { scanDate, totalRepos, totalCommits, totalAiCommits, aiPercent: Math.round(aiPercent * 100) / 100 } })
 })
 
 // ── POST /api/index-clear — Admin: clear index scans & snapshots (auth required) ──